@@ -0,0 +1,180 @@
+//! Gematria-rs compile-time macros
+//!
+//! This crate provides the [`gematria!`] macro, re-exported by `gematria_rs` under its `macros` feature.
+//! The macro validates a Hebrew literal when the crate using it is compiled, and expands to its value
+//! as a `u64` constant, evaluated with `GematriaMethod::word_value`.
+
+use proc_macro::{Delimiter, Group, Ident, Literal, Punct, Spacing, Span, TokenStream, TokenTree};
+
+/// Expands to the gematria value of a Hebrew literal, as a `u64` constant.
+///
+/// The method defaults to Mispar Hechrechi, and may be given as a second argument,
+/// naming a variant of `GematriaMethod`. Characters other than the Hebrew letters, their points
+/// and cantillation marks, and whitespace fail the compilation, as do the methods without
+/// compile-time values (such as the milui methods).
+///
+/// # Examples
+///
+/// ```
+/// use gematria_rs::gematria;
+///
+/// const SHALOM: u64 = gematria!("שלום");
+/// assert_eq!(SHALOM, 376);
+/// assert_eq!(gematria!("שָׁלוֹם", MisparGadol), 936);
+/// ```
+///
+/// ```compile_fail
+/// use gematria_rs::gematria;
+///
+/// // Latin letters are not valued
+/// let value = gematria!("shalom");
+/// ```
+#[proc_macro]
+pub fn gematria(input: TokenStream) -> TokenStream {
+    match expand(input) {
+        Ok(tokens) => tokens,
+        Err((span, message)) => compile_error(span, &message),
+    }
+}
+
+// Util function to expand the macro, or to get the error and the span it points to.
+fn expand(input: TokenStream) -> Result<TokenStream, (Span, String)> {
+    let mut tokens = input.into_iter();
+    let literal = match tokens.next() {
+        Some(TokenTree::Literal(literal)) => literal,
+        Some(other) => return Err((other.span(), "expected a string literal".to_string())),
+        None => {
+            return Err((
+                Span::call_site(),
+                "expected a string literal, such as `gematria!(\"שלום\")`".to_string(),
+            ))
+        }
+    };
+    let text = parse_string(&literal.to_string())
+        .ok_or_else(|| (literal.span(), "expected a string literal".to_string()))?;
+    if let Some(c) = text.chars().find(|&c| !is_valued_character(c)) {
+        return Err((
+            literal.span(),
+            format!("`{}` (U+{:04X}) is not a Hebrew letter", c, c as u32),
+        ));
+    }
+
+    let method = match tokens.next() {
+        None => Ident::new("MisparHechrechi", Span::call_site()),
+        Some(TokenTree::Punct(punct)) if punct.as_char() == ',' => match tokens.next() {
+            Some(TokenTree::Ident(method)) => method,
+            // A trailing comma, without a method
+            None => Ident::new("MisparHechrechi", Span::call_site()),
+            Some(other) => return Err((other.span(), "expected a method name".to_string())),
+        },
+        Some(other) => return Err((other.span(), "expected `,`".to_string())),
+    };
+    match tokens.next() {
+        None => {}
+        Some(TokenTree::Punct(punct)) if punct.as_char() == ',' && tokens.next().is_none() => {}
+        Some(other) => return Err((other.span(), "unexpected token".to_string())),
+    }
+
+    // The value is evaluated in a constant, so a method without compile-time values fails the compilation
+    let expansion = format!(
+        "{{
+            const VALUE: u64 = match ::gematria_rs::GematriaMethod::{method}.word_value({literal}) {{
+                Some(value) => value,
+                None => panic!(\"the method has no compile-time values\"),
+            }};
+            VALUE
+        }}",
+    );
+    expansion
+        .parse()
+        .map_err(|_| (method.span(), "expected a method name".to_string()))
+}
+
+// Util function to check whether a character may appear in a literal.
+fn is_valued_character(c: char) -> bool {
+    matches!(c, '\u{05D0}'..='\u{05EA}' | '\u{0591}'..='\u{05C7}') && !is_punctuation(c)
+        || c.is_whitespace()
+}
+
+// Util function to check whether a character of the Hebrew points block is a punctuation mark.
+fn is_punctuation(c: char) -> bool {
+    matches!(c, '\u{05BE}' | '\u{05C0}' | '\u{05C3}' | '\u{05C6}')
+}
+
+// Util function to get the content of a string literal, as written in the source.
+fn parse_string(source: &str) -> Option<String> {
+    if let Some(raw) = source.strip_prefix('r') {
+        let hashes = raw.len() - raw.trim_start_matches('#').len();
+        let raw = raw.get(hashes..raw.len().checked_sub(hashes)?)?;
+        return Some(raw.strip_prefix('"')?.strip_suffix('"')?.to_string());
+    }
+    unescape(source.strip_prefix('"')?.strip_suffix('"')?)
+}
+
+// Util function to resolve the escapes of a string literal.
+fn unescape(content: &str) -> Option<String> {
+    let mut text = String::with_capacity(content.len());
+    let mut chars = content.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            text.push(c);
+            continue;
+        }
+        match chars.next()? {
+            'n' => text.push('\n'),
+            'r' => text.push('\r'),
+            't' => text.push('\t'),
+            '0' => text.push('\0'),
+            c @ ('\\' | '"' | '\'') => text.push(c),
+            'u' => {
+                let rest = chars.as_str().strip_prefix('{')?;
+                let end = rest.find('}')?;
+                let code = u32::from_str_radix(&rest[..end].replace('_', ""), 16).ok()?;
+                text.push(char::from_u32(code)?);
+                chars = rest[end + 1..].chars();
+            }
+            // A line continuation skips the line break and the leading whitespace of the next line
+            '\n' => chars = chars.as_str().trim_start().chars(),
+            _ => return None,
+        }
+    }
+    Some(text)
+}
+
+// Util function to report an error at a span, as a `compile_error!` invocation.
+fn compile_error(span: Span, message: &str) -> TokenStream {
+    let mut literal = Literal::string(message);
+    literal.set_span(span);
+    let mut bang = Punct::new('!', Spacing::Alone);
+    bang.set_span(span);
+    let mut group = Group::new(Delimiter::Parenthesis, TokenTree::Literal(literal).into());
+    group.set_span(span);
+    [
+        TokenTree::Ident(Ident::new("compile_error", span)),
+        TokenTree::Punct(bang),
+        TokenTree::Group(group),
+    ]
+    .into_iter()
+    .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_string() {
+        assert_eq!(parse_string("\"שלום\"").as_deref(), Some("שלום"));
+        assert_eq!(parse_string("r#\"שלום\"#").as_deref(), Some("שלום"));
+        assert_eq!(
+            parse_string("\"\\u{5e9}\\u{5DC}ום\\\n    עולם\"").as_deref(),
+            Some("שלוםעולם")
+        );
+        assert_eq!(parse_string("b\"abc\""), None);
+        assert_eq!(parse_string("42"), None);
+
+        assert!("בְּרֵאשִׁית בָּרָא".chars().all(is_valued_character));
+        assert!(!"שלום-עולם".chars().all(is_valued_character));
+        assert!(!"רמב״ם".chars().all(is_valued_character));
+    }
+}
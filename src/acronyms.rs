@@ -0,0 +1,156 @@
+// Notarikon, words made of the first (roshei teivot) or last (sofei teivot) letters of the words of a phrase.
+
+use crate::{GematriaContext, GematriaError, GematriaMethod, GematriaResult};
+
+impl GematriaContext {
+    /// Extracts the first letter of every word of a phrase (roshei teivot), skipping vowels and punctuation.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gematria_rs::GematriaContext;
+    ///
+    /// let gmctx = GematriaContext::default();
+    /// assert_eq!(gmctx.notarikon("גַּם זוֹ לְטוֹבָה"), "גזל");
+    /// ```
+    pub fn notarikon(&self, phrase: &str) -> String {
+        self.tokenizer
+            .tokenize(phrase)
+            .filter_map(|word| word.chars().find(|c| self.get_character_index(c).is_some()))
+            .collect()
+    }
+
+    /// Calculates the gematria value of the notarikon of a phrase, the acronym being the word of the result.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gematria_rs::GematriaContext;
+    ///
+    /// let gmctx = GematriaContext::default();
+    /// let result = gmctx.calculate_notarikon("גם זו לטובה");
+    ///
+    /// assert_eq!(result.word(), "גזל");
+    /// assert_eq!(result.value(), 40);
+    /// ```
+    pub fn calculate_notarikon(&self, phrase: &str) -> GematriaResult {
+        self.calculate_value(&self.notarikon(phrase))
+    }
+
+    /// Extracts the last letter of every word of a phrase (sofei teivot), keeping final forms.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gematria_rs::GematriaContext;
+    ///
+    /// let gmctx = GematriaContext::default();
+    /// assert_eq!(gmctx.sofei_teivot("בְּרֵאשִׁית בָּרָא אֱלֹהִים"), "תאם");
+    /// ```
+    pub fn sofei_teivot(&self, phrase: &str) -> String {
+        self.tokenizer
+            .tokenize(phrase)
+            .filter_map(|word| {
+                word.chars()
+                    .rev()
+                    .find(|c| self.get_character_index(c).is_some())
+            })
+            .collect()
+    }
+
+    /// Calculates the gematria value of the sofei teivot of a phrase under the given method,
+    /// with the settings of the context. The derived word is the word of the result.
+    ///
+    /// Fails with [`GematriaError::UnsupportedMethod`] for a method without an implementation.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gematria_rs::{GematriaContext, GematriaMethod};
+    ///
+    /// let gmctx = GematriaContext::default();
+    /// let result = gmctx.calculate_sofei_teivot("בראשית ברא אלהים", GematriaMethod::MisparGadol)?;
+    ///
+    /// assert_eq!(result.word(), "תאם");
+    /// assert_eq!(result.value(), 1001);
+    /// assert_eq!(result.method(), &GematriaMethod::MisparGadol);
+    /// # Ok::<(), gematria_rs::Error>(())
+    /// ```
+    pub fn calculate_sofei_teivot(
+        &self,
+        phrase: &str,
+        method: GematriaMethod,
+    ) -> Result<GematriaResult, GematriaError> {
+        self.calculate_value_with_method(&self.sofei_teivot(phrase), method)
+    }
+
+    // Util function to calculate a value under another method than the one of the context.
+    fn calculate_value_with_method(
+        &self,
+        text: &str,
+        method: GematriaMethod,
+    ) -> Result<GematriaResult, GematriaError> {
+        if method == self.get_current_method() {
+            return Ok(self.calculate_value(text));
+        }
+
+        let (_, strategy) = self
+            .method_strategies(&[method])?
+            .pop()
+            .expect("a strategy for every method");
+        let strategy = strategy
+            .as_deref()
+            .unwrap_or(self.calculation_strategy.as_ref());
+        let normalized = self.normalize(text);
+        let value = self.calculate_letters_value(
+            strategy,
+            &self.get_word_letters(&normalized),
+            &normalized,
+        );
+        Ok(
+            GematriaResult::new(value, method, self.handle_vowels(&normalized))
+                .with_original_word(text.to_string())
+                .with_kolel(self.kolel)
+                .with_breakdown(self.letter_breakdown(strategy, &normalized)),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_notarikon_skips_marks() {
+        let gmctx = GematriaContext::default();
+
+        // Words joined by a maqaf are separate words, punctuation alone is not a word
+        assert_eq!(gmctx.notarikon("׳אֶת־הָאָרֶץ ׃ (שָׁמַיִם)"), "אהש");
+        assert_eq!(gmctx.calculate_notarikon("").value(), 0);
+    }
+
+    #[test]
+    fn test_sofei_teivot_methods() {
+        let gmctx = GematriaContext::default();
+        let katan = gmctx
+            .calculate_sofei_teivot("שָׁלוֹם עֲלֵיכֶם׃", GematriaMethod::MisparKatan)
+            .unwrap();
+
+        assert_eq!(katan.word(), "םם");
+        assert_eq!(katan.value(), 12);
+        assert_eq!(katan.breakdown(), [('ם', 6), ('ם', 6)]);
+        assert_eq!(
+            gmctx
+                .calculate_sofei_teivot("שלום עליכם", GematriaMethod::MisparHechrechi)
+                .unwrap()
+                .value(),
+            80
+        );
+        assert_eq!(
+            gmctx.calculate_sofei_teivot("שלום", GematriaMethod::MisparBoneh),
+            Err(GematriaError::UnsupportedMethod(
+                GematriaMethod::MisparBoneh
+            ))
+        );
+    }
+}
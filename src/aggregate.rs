@@ -0,0 +1,190 @@
+// Aggregated gematria values of whole documents (verses, chapters, books, ...).
+
+use std::collections::{HashMap, HashSet};
+
+use crate::GematriaContext;
+
+/// Defines how the values of the words of a document are combined into a single total.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum AggregatePolicy {
+    /// Sums the value of every word of the document.
+    #[default]
+    Sum,
+    /// Sums the value of every distinct word of the document once, ignoring repetitions.
+    DistinctWords,
+}
+
+/// `DocumentAggregate` holds the total gematria value of a single labeled document.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DocumentAggregate {
+    // The document label, e.g. "Genesis 1".
+    label: String,
+
+    // The aggregated gematria value.
+    value: u64,
+
+    // The number of words in the document.
+    words: usize,
+}
+
+/// `AggregateIndex` indexes the aggregated values of many documents,
+/// allowing queries over the document totals.
+///
+/// # Examples
+///
+/// ```
+/// use gematria_rs::{AggregatePolicy, GematriaContext};
+///
+/// let gmctx = GematriaContext::default();
+/// let index = gmctx.aggregate_documents(
+///     vec![("first", "יהוה יהוה"), ("second", "שלום עולם")],
+///     AggregatePolicy::Sum,
+/// );
+///
+/// // Which documents total a multiple of 26?
+/// let labels: Vec<&str> = index
+///     .filter(|value| value % 26 == 0)
+///     .iter()
+///     .map(|doc| doc.label())
+///     .collect();
+/// assert_eq!(labels, vec!["first"]);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct AggregateIndex {
+    // The aggregated documents, in insertion order.
+    documents: Vec<DocumentAggregate>,
+
+    // Aggregated value to the positions of the matching documents.
+    by_value: HashMap<u64, Vec<usize>>,
+}
+
+impl DocumentAggregate {
+    /// Gets the document label.
+    pub fn label(&self) -> &str {
+        &self.label
+    }
+
+    /// Gets the aggregated gematria value.
+    pub fn value(&self) -> u64 {
+        self.value
+    }
+
+    /// Gets the number of words in the document.
+    pub fn words(&self) -> usize {
+        self.words
+    }
+}
+
+impl AggregateIndex {
+    /// Gets all the aggregated documents, in insertion order.
+    pub fn documents(&self) -> &[DocumentAggregate] {
+        &self.documents
+    }
+
+    /// Gets the documents totaling exactly the given value.
+    pub fn with_value(&self, value: u64) -> Vec<&DocumentAggregate> {
+        self.by_value
+            .get(&value)
+            .map(|positions| positions.iter().map(|&i| &self.documents[i]).collect())
+            .unwrap_or_default()
+    }
+
+    /// Gets the documents whose total satisfies the given predicate.
+    pub fn filter<P: Fn(u64) -> bool>(&self, predicate: P) -> Vec<&DocumentAggregate> {
+        self.documents
+            .iter()
+            .filter(|doc| predicate(doc.value))
+            .collect()
+    }
+
+    fn push(&mut self, document: DocumentAggregate) {
+        self.by_value
+            .entry(document.value)
+            .or_default()
+            .push(self.documents.len());
+        self.documents.push(document);
+    }
+}
+
+impl GematriaContext {
+    /// Calculates the total gematria value of a whole document, combining its words by the given policy.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// use gematria_rs::{AggregatePolicy, GematriaContext};
+    ///
+    /// let gmctx = GematriaContext::default();
+    /// assert_eq!(gmctx.calculate_document("יין יין סוד", AggregatePolicy::Sum), 210);
+    /// assert_eq!(gmctx.calculate_document("יין יין סוד", AggregatePolicy::DistinctWords), 140);
+    /// ```
+    pub fn calculate_document(&self, text: &str, policy: AggregatePolicy) -> u64 {
+        self.aggregate(text, policy).0
+    }
+
+    /// Calculates and indexes the totals of many labeled documents.
+    pub fn aggregate_documents<I, L, T>(
+        &self,
+        documents: I,
+        policy: AggregatePolicy,
+    ) -> AggregateIndex
+    where
+        I: IntoIterator<Item = (L, T)>,
+        L: Into<String>,
+        T: AsRef<str>,
+    {
+        let mut index = AggregateIndex::default();
+        for (label, text) in documents {
+            let (value, words) = self.aggregate(text.as_ref(), policy);
+            index.push(DocumentAggregate {
+                label: label.into(),
+                value,
+                words,
+            });
+        }
+
+        index
+    }
+
+    // Util function returning the aggregated value and the number of words of a document.
+    fn aggregate(&self, text: &str, policy: AggregatePolicy) -> (u64, usize) {
+        let mut seen = HashSet::new();
+        let mut words = 0;
+        let mut total: u64 = 0;
+        for word in self.tokenizer.tokenize(text) {
+            words += 1;
+            let processed_text = self.handle_vowels(word);
+            if policy == AggregatePolicy::DistinctWords && !seen.insert(processed_text) {
+                continue;
+            }
+            total = total.saturating_add(self.calculate_value(word).value());
+        }
+
+        (total, words)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_aggregate_documents() {
+        let gmctx = GematriaContext::default();
+        let index = gmctx.aggregate_documents(
+            vec![
+                ("a".to_string(), "נכנס יין"),
+                ("b".to_string(), "יצא סוד"),
+                ("c".to_string(), "סוד סוד"),
+            ],
+            AggregatePolicy::DistinctWords,
+        );
+
+        assert_eq!(index.documents().len(), 3);
+        assert_eq!(index.documents()[0].value(), 250);
+        assert_eq!(index.documents()[2].value(), 70);
+        assert_eq!(index.documents()[2].words(), 2);
+        assert_eq!(index.with_value(171)[0].label(), "b");
+        assert!(index.with_value(1).is_empty());
+    }
+}
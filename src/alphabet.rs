@@ -0,0 +1,137 @@
+// Alphabet systems: the letters valued by a context, their final forms and their marks.
+
+use std::fmt;
+use std::sync::Arc;
+
+use crate::{create_hebrew_index_map, is_hebrew_vowel, methods::base_letter_index, CharMap};
+
+/// A trait describing the alphabet of a [`crate::GematriaContext`]: its letters and their order,
+/// its final forms and the marks (such as vowels) written along its letters.
+///
+/// Letters are given 1 based indices, which are valued by the calculation methods.
+/// The built-in alphabets are [`HebrewAlphabet`], [`crate::GreekAlphabet`] and [`crate::EnglishAlphabet`],
+/// other alphabets are set with [`crate::GematriaBuilder::with_alphabet`].
+///
+/// # Examples
+///
+/// ```
+/// use gematria_rs::{AlphabetSystem, CharMap, GematriaBuilder, GematriaMethod};
+///
+/// /// The Arabic abjad, valued from ا = 1 to غ = 1000.
+/// struct Abjad;
+///
+/// impl AlphabetSystem for Abjad {
+///     fn name(&self) -> &str {
+///         "Arabic"
+///     }
+///
+///     fn letters(&self) -> CharMap {
+///         "ابجدهوزحطيكلمنسعفصقرشتثخذضظغ".chars().zip(1..).collect()
+///     }
+///
+///     fn is_mark(&self, c: char) -> bool {
+///         matches!(c, '\u{064B}'..='\u{065F}' | '\u{0670}')
+///     }
+/// }
+///
+/// // Mispar Gadol values the indices 1 to 28 as 1 to 1000
+/// let gmctx = GematriaBuilder::new()
+///     .with_method(GematriaMethod::MisparGadol)
+///     .with_alphabet(Abjad)
+///     .init_gematria();
+///
+/// assert_eq!(gmctx.calculate_value("كِتَاب").value(), 423);
+/// assert_eq!(gmctx.calculate_value("كِتَاب").word(), "كتاب");
+/// ```
+pub trait AlphabetSystem: Send + Sync {
+    /// Gets the name of the alphabet.
+    fn name(&self) -> &str;
+
+    /// Gets the letters of the alphabet (including their variant forms) mapped to their index (1 based).
+    fn letters(&self) -> CharMap;
+
+    /// Gets the index of the regular letter of a final form, `None` for other indices.
+    ///
+    /// Defaults to `None`, for alphabets without final forms of their own index.
+    fn final_form_base(&self, _letter_index: u32) -> Option<u32> {
+        None
+    }
+
+    /// Checks whether a character is a mark written along the letters (such as a vowel point or an accent),
+    /// which is not valued by the letter methods and is removed from words unless vowels are preserved.
+    ///
+    /// Defaults to false.
+    fn is_mark(&self, _c: char) -> bool {
+        false
+    }
+}
+
+/// The Hebrew alphabet, the default alphabet of contexts: the letters א to ת (indices 1 to 22)
+/// followed by the final forms ך, ם, ן, ף and ץ (indices 23 to 27).
+///
+/// Its marks are the vowel points, the cantillation marks and the Hebrew punctuation (U+0591 to U+05C7).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct HebrewAlphabet;
+
+impl AlphabetSystem for HebrewAlphabet {
+    fn name(&self) -> &str {
+        "Hebrew"
+    }
+
+    fn letters(&self) -> CharMap {
+        create_hebrew_index_map()
+    }
+
+    fn final_form_base(&self, letter_index: u32) -> Option<u32> {
+        match letter_index {
+            23..=27 => Some(base_letter_index(letter_index)),
+            _ => None,
+        }
+    }
+
+    fn is_mark(&self, c: char) -> bool {
+        is_hebrew_vowel(c)
+    }
+}
+
+/// Checks whether a character is a combining diacritical mark (U+0300 to U+036F),
+/// the accents and breathings of the Greek and Latin alphabets once decomposed.
+pub(crate) fn is_combining_mark(c: char) -> bool {
+    matches!(c, '\u{0300}'..='\u{036F}')
+}
+
+/// An alphabet shared by a builder and its contexts.
+#[derive(Clone)]
+pub(crate) struct SharedAlphabet(pub(crate) Arc<dyn AlphabetSystem>);
+
+impl fmt::Debug for SharedAlphabet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("SharedAlphabet")
+            .field(&self.0.name())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{GematriaBuilder, GematriaMethod, GreekAlphabet, SofitPolicy};
+
+    #[test]
+    fn test_final_forms_follow_the_alphabet() {
+        // φ is the 23rd Greek letter, not a final form
+        let gmctx = GematriaBuilder::new()
+            .with_method(GematriaMethod::Isopsephy)
+            .with_sofit_policy(SofitPolicy::Regular)
+            .init_gematria();
+        assert_eq!(gmctx.calculate_value("φως").value(), 1500);
+        assert_eq!(gmctx.alphabet().name(), "Greek");
+        assert_eq!(GreekAlphabet::new().final_form_base(23), None);
+
+        let gmctx = GematriaBuilder::new()
+            .with_sofit_policy(SofitPolicy::Regular)
+            .init_gematria();
+        assert_eq!(gmctx.calculate_value("ך").value(), 20);
+        assert_eq!(gmctx.alphabet().name(), "Hebrew");
+    }
+}
@@ -0,0 +1,137 @@
+// Arbitrary precision values, for the totals overflowing a `u64`.
+
+use num_bigint::BigUint;
+
+use crate::{
+    methods::reduce_to_single_digit, GematriaCalculation, GematriaContext, GematriaMethod, Modifier,
+};
+
+impl GematriaContext {
+    /// Calculates the exact gematria value of a Hebrew word or phrase, with arbitrary precision,
+    /// where [`GematriaContext::calculate_value`] saturates at `u64::MAX`.
+    ///
+    /// The finalization of the built-in methods is exact. A custom strategy is finalized exactly
+    /// as long as its total fits in a `u64`, and left as the sum of its letters otherwise.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gematria_rs::{BigUint, GematriaBuilder, Modifier};
+    ///
+    /// let gmctx = GematriaBuilder::new()
+    ///     .with_modifier(Modifier::SquareTotal)
+    ///     .with_modifier(Modifier::SquareTotal)
+    ///     .with_modifier(Modifier::SquareTotal)
+    ///     .init_gematria();
+    ///
+    /// // שלום = 376, squared three times
+    /// assert_eq!(gmctx.calculate_value_big("שלום"), BigUint::from(376u32).pow(8));
+    /// assert_eq!(gmctx.calculate_value("שלום").value(), u64::MAX);
+    /// ```
+    pub fn calculate_value_big(&self, text: &str) -> BigUint {
+        let normalized = self.normalize(text);
+        let (value, _, abbreviation) = self.calculate_normalized(&normalized);
+        match value {
+            Some(value) => BigUint::from(value),
+            None => {
+                let text = abbreviation.as_deref().unwrap_or(&normalized);
+                self.big_text_value(self.calculation_strategy.as_ref(), text)
+            }
+        }
+    }
+
+    // Util function to calculate the value of a normalized text with arbitrary precision.
+    fn big_text_value(&self, strategy: &dyn GematriaCalculation, text: &str) -> BigUint {
+        let mut total = BigUint::default();
+        for word in self.tokenizer.tokenize(text) {
+            // Letters are valued by their position within their own word
+            let indices = word.chars().filter_map(|c| self.get_character_index(&c));
+            for (i, &index) in indices.enumerate() {
+                total += strategy.calculate_positional_value(index, i + 1);
+            }
+        }
+        for c in text.chars() {
+            if self.get_character_index(&c).is_none() {
+                if let Some(value) = strategy.calculate_symbol_value(c) {
+                    total += value;
+                }
+            }
+        }
+
+        let total = self
+            .modifiers
+            .iter()
+            .fold(finalize_big_total(strategy, total), apply_big_modifier);
+        match self.kolel {
+            Some(kolel) => total + self.kolel_amount(text, kolel),
+            None => total,
+        }
+    }
+}
+
+// Util function to finalize the total of a strategy with arbitrary precision.
+fn finalize_big_total(strategy: &dyn GematriaCalculation, total: BigUint) -> BigUint {
+    if let Some(total) = u64::try_from(&total)
+        .ok()
+        .and_then(|total| strategy.checked_finalize_total(total))
+    {
+        return BigUint::from(total);
+    }
+
+    match strategy.method_type() {
+        GematriaMethod::MisparHaMerubahHaKlali => &total * &total,
+        GematriaMethod::MisparKatanMispari => reduce_big_to_single_digit(total),
+        _ => total,
+    }
+}
+
+// Util function to apply a modifier on a total with arbitrary precision.
+fn apply_big_modifier(value: BigUint, modifier: &Modifier) -> BigUint {
+    match modifier {
+        Modifier::ReduceToSingleDigit => reduce_big_to_single_digit(value),
+        Modifier::Modulo(0) => value,
+        Modifier::Modulo(n) => value % n,
+        Modifier::PlusKolel => value + 1u32,
+        Modifier::SquareTotal => &value * &value,
+    }
+}
+
+// Util function to reduce a value to a single digit by repeatedly summing its digits.
+fn reduce_big_to_single_digit(value: BigUint) -> BigUint {
+    // The sum of the digits of any practical value fits in a `u64`
+    let digits_sum: u64 = value.to_radix_le(10).into_iter().map(u64::from).sum();
+    BigUint::from(reduce_to_single_digit(digits_sum))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{BigUint, GematriaBuilder, GematriaMethod, Modifier};
+
+    #[test]
+    fn test_big_values() {
+        let gmctx = GematriaBuilder::new()
+            .with_method(GematriaMethod::MisparHaMerubahHaKlali)
+            .with_modifier(Modifier::SquareTotal)
+            .init_gematria();
+        assert_eq!(
+            gmctx.calculate_value_big("שלום"),
+            BigUint::from(141376u64 * 141376)
+        );
+
+        let text = "ת".repeat(100_000);
+        let expected = BigUint::from(40_000_000u64).pow(4);
+        assert_eq!(gmctx.calculate_value_big(&text), expected);
+
+        let gmctx = GematriaBuilder::new()
+            .with_method(GematriaMethod::MisparKatanMispari)
+            .with_modifier(Modifier::SquareTotal)
+            .with_modifier(Modifier::SquareTotal)
+            .with_modifier(Modifier::ReduceToSingleDigit)
+            .init_gematria();
+        let text = "ש".repeat(1000);
+        assert_eq!(
+            gmctx.calculate_value_big(&text),
+            BigUint::from(gmctx.calculate_value(&text).value())
+        );
+    }
+}
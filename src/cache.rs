@@ -0,0 +1,624 @@
+// Cache of calculated values, optionally bounded by evicting the least recently used values,
+// and its persistence to disk.
+
+use std::collections::{BTreeMap, HashMap};
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, BufWriter, Write};
+use std::mem;
+use std::path::Path;
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+
+use crate::{GematriaContext, GematriaMethod};
+
+/// The first line of persisted caches, followed by the version of the crate which wrote the cache.
+const CACHE_MAGIC: &str = "gematria-cache v1";
+
+/// The key of a cached value, the method, the fingerprint of the other settings
+/// (see [`crate::GematriaConfig`]), the identity of the custom strategy (0 for the built-in methods),
+/// the kind of value and the text it was calculated on.
+pub(crate) type CacheKey = (GematriaMethod, u64, u64, CacheKind, String);
+
+/// The kind of a cached value, as the value of a character differs from the value of the same
+/// character as a text, which the modifiers and kolel apply on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub(crate) enum CacheKind {
+    /// The value of a text, see [`GematriaContext::calculate_value`].
+    Text,
+
+    /// The value of a single character, see [`GematriaContext::calculate_char_value`].
+    Char,
+}
+
+/// `SharedCache` is a cache of calculated values which may be shared by several contexts,
+/// such as a context per method answering queries under every method, see [`crate::GematriaBuilder::with_shared_cache`].
+///
+/// Values are cached by method, settings (shift, modifiers, kolel, milui spellings, transliteration...),
+/// custom strategy and text, so contexts configured differently share a cache without reading each other's values.
+/// Every strategy passed to [`crate::GematriaBuilder::with_strategy`] or [`GematriaContext::set_strategy`]
+/// has its own values, shared by the contexts built from the same builder.
+/// Changing a setting of one of the contexts clears the shared cache.
+///
+/// # Examples
+///
+/// ```
+/// use gematria_rs::{GematriaBuilder, GematriaMethod, SharedCache};
+///
+/// let cache = SharedCache::with_capacity(10_000);
+/// let standard = GematriaBuilder::new()
+///     .with_shared_cache(cache.clone())
+///     .init_gematria();
+/// let gadol = GematriaBuilder::new()
+///     .with_method(GematriaMethod::MisparGadol)
+///     .with_shared_cache(cache.clone())
+///     .init_gematria();
+///
+/// assert_eq!(standard.calculate_value("שלום").value(), 376);
+/// assert_eq!(gadol.calculate_value("שלום").value(), 936);
+/// assert_eq!(cache.stats().entries(), 2);
+/// ```
+#[derive(Debug, Clone)]
+pub struct SharedCache(Arc<Mutex<ValueCache>>);
+
+impl Default for SharedCache {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SharedCache {
+    /// Creates an unbounded cache.
+    pub fn new() -> Self {
+        SharedCache(Arc::new(Mutex::new(ValueCache::new(None))))
+    }
+
+    /// Creates a cache of up to `capacity` values, evicting the least recently used value once full.
+    pub fn with_capacity(capacity: usize) -> Self {
+        SharedCache(Arc::new(Mutex::new(ValueCache::new(Some(capacity)))))
+    }
+
+    /// Gets the usage statistics of the cache, for all the contexts sharing it.
+    pub fn stats(&self) -> CacheStats {
+        self.lock().stats()
+    }
+
+    /// Discards the cached values, for all the contexts sharing the cache.
+    pub fn clear(&self) {
+        self.lock().clear();
+    }
+
+    /// Locks the cache.
+    pub(crate) fn lock(&self) -> MutexGuard<'_, ValueCache> {
+        // A panic while holding the lock cannot leave the cache inconsistent, so poisoning is ignored
+        self.0.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+/// `CacheStats` reports the usage of the cache of a context, see [`GematriaContext::cache_stats`].
+///
+/// # Examples
+///
+/// ```
+/// use gematria_rs::GematriaBuilder;
+///
+/// let gmctx = GematriaBuilder::new().with_cache_capacity(100).init_gematria();
+/// gmctx.calculate_value("שלום");
+/// gmctx.calculate_value("שלום");
+///
+/// let stats = gmctx.cache_stats().unwrap();
+/// assert_eq!((stats.hits(), stats.misses()), (1, 1));
+/// assert_eq!(stats.entries(), 1);
+/// assert_eq!(stats.capacity(), Some(100));
+///
+/// gmctx.clear_cache();
+/// assert_eq!(gmctx.cache_stats().unwrap().entries(), 0);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CacheStats {
+    // The number of values read from the cache.
+    hits: u64,
+
+    // The number of values looked up and missing from the cache.
+    misses: u64,
+
+    // The number of cached values.
+    entries: usize,
+
+    // The maximum number of cached values, `None` when unbounded.
+    capacity: Option<usize>,
+
+    // The approximate memory used by the cached values, in bytes.
+    memory_bytes: usize,
+}
+
+impl CacheStats {
+    /// Gets the number of values read from the cache, since the context was created.
+    pub fn hits(&self) -> u64 {
+        self.hits
+    }
+
+    /// Gets the number of values looked up and missing from the cache (and calculated),
+    /// since the context was created.
+    pub fn misses(&self) -> u64 {
+        self.misses
+    }
+
+    /// Gets the ratio of lookups read from the cache, `None` before any lookup.
+    pub fn hit_ratio(&self) -> Option<f64> {
+        let lookups = self.hits + self.misses;
+        (lookups > 0).then(|| self.hits as f64 / lookups as f64)
+    }
+
+    /// Gets the number of cached values.
+    pub fn entries(&self) -> usize {
+        self.entries
+    }
+
+    /// Gets the maximum number of cached values, `None` when unbounded.
+    pub fn capacity(&self) -> Option<usize> {
+        self.capacity
+    }
+
+    /// Gets the approximate memory used by the cached values (keys, values and bookkeeping), in bytes.
+    ///
+    /// Unused capacity of the underlying maps is not accounted for.
+    pub fn memory_bytes(&self) -> usize {
+        self.memory_bytes
+    }
+}
+
+/// A cache of calculated values, unbounded or holding up to a capacity of values.
+///
+/// Bounded caches evict the least recently used value once full, values being used when cached or read.
+#[derive(Debug, Default)]
+pub(crate) struct ValueCache {
+    // The cached values, along with the tick of their last use.
+    values: HashMap<CacheKey, (u64, u64)>,
+
+    // The keys of the cached values by tick of last use, the least recently used first (bounded caches only).
+    recency: BTreeMap<u64, CacheKey>,
+
+    // The tick of the last use.
+    tick: u64,
+
+    // The maximum number of cached values, `None` when unbounded.
+    capacity: Option<usize>,
+
+    // The number of lookups found in the cache.
+    hits: u64,
+
+    // The number of lookups missing from the cache.
+    misses: u64,
+
+    // The total length of the cached texts, in bytes.
+    text_bytes: usize,
+}
+
+impl ValueCache {
+    /// Creates a cache holding up to a capacity of values, unbounded when `None`.
+    pub(crate) fn new(capacity: Option<usize>) -> Self {
+        ValueCache {
+            capacity,
+            ..Default::default()
+        }
+    }
+
+    /// Gets the maximum number of cached values, `None` when unbounded.
+    pub(crate) fn capacity(&self) -> Option<usize> {
+        self.capacity
+    }
+
+    /// Gets the number of cached values.
+    #[cfg(test)]
+    pub(crate) fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Gets the usage statistics of the cache.
+    pub(crate) fn stats(&self) -> CacheStats {
+        // Every value is held by the map, and its key once more by the recency order of bounded caches
+        let mut entry_bytes = mem::size_of::<(CacheKey, (u64, u64))>();
+        if self.capacity.is_some() {
+            entry_bytes += mem::size_of::<(u64, CacheKey)>();
+        }
+        CacheStats {
+            hits: self.hits,
+            misses: self.misses,
+            entries: self.values.len(),
+            capacity: self.capacity,
+            memory_bytes: self.values.len() * entry_bytes + self.text_bytes,
+        }
+    }
+
+    /// Gets a cached value, marking it as the most recently used.
+    pub(crate) fn get(&mut self, key: &CacheKey) -> Option<u64> {
+        let tick = self.next_tick();
+        let bounded = self.capacity.is_some();
+        let Some((value, last_use)) = self.values.get_mut(key) else {
+            self.misses += 1;
+            return None;
+        };
+        self.hits += 1;
+        if bounded {
+            let key = self.recency.remove(last_use)?;
+            self.recency.insert(tick, key);
+            *last_use = tick;
+        }
+        Some(*value)
+    }
+
+    /// Caches a value, evicting the least recently used value when the cache is full.
+    pub(crate) fn insert(&mut self, key: CacheKey, value: u64) {
+        let Some(capacity) = self.capacity else {
+            let text_bytes = key.4.len();
+            if self.values.insert(key, (value, 0)).is_none() {
+                self.text_bytes += text_bytes;
+            }
+            return;
+        };
+        if capacity == 0 {
+            return;
+        }
+
+        let tick = self.next_tick();
+        if let Some((_, last_use)) = self.values.insert(key.clone(), (value, tick)) {
+            self.recency.remove(&last_use);
+        } else {
+            self.text_bytes += key.4.len();
+            if self.values.len() > capacity {
+                if let Some((_, evicted)) = self.recency.pop_first() {
+                    self.values.remove(&evicted);
+                    self.text_bytes -= evicted.4.len();
+                }
+            }
+        }
+        self.recency.insert(tick, key);
+    }
+
+    /// Gets the cached values, the least recently used first for bounded caches.
+    pub(crate) fn entries(&self) -> Vec<(&CacheKey, u64)> {
+        match self.capacity {
+            Some(_) => self
+                .recency
+                .values()
+                .map(|key| (key, self.values[key].0))
+                .collect(),
+            None => {
+                let mut entries: Vec<(&CacheKey, u64)> = self
+                    .values
+                    .iter()
+                    .map(|(key, &(value, _))| (key, value))
+                    .collect();
+                entries.sort_unstable();
+                entries
+            }
+        }
+    }
+
+    /// Discards the cached values.
+    pub(crate) fn clear(&mut self) {
+        self.values.clear();
+        self.recency.clear();
+        self.text_bytes = 0;
+    }
+
+    // Util function to advance the tick of uses.
+    fn next_tick(&mut self) -> u64 {
+        self.tick += 1;
+        self.tick
+    }
+}
+
+// Util function to escape the backslashes, tabs and line breaks of a cached text.
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '\\' => escaped.push_str("\\\\"),
+            '\t' => escaped.push_str("\\t"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+// Util function to unescape a cached text, `None` for an invalid escape.
+fn unescape(text: &str) -> Option<String> {
+    let mut unescaped = String::with_capacity(text.len());
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        let c = match c {
+            '\\' => match chars.next()? {
+                '\\' => '\\',
+                't' => '\t',
+                'n' => '\n',
+                'r' => '\r',
+                _ => return None,
+            },
+            c => c,
+        };
+        unescaped.push(c);
+    }
+    Some(unescaped)
+}
+
+// Util function to parse a cached entry, a method, a value and a text separated by tabs,
+// cached under the given settings.
+fn parse_entry(line: &str, settings: u64) -> Option<(CacheKey, u64)> {
+    let mut fields = line.splitn(3, '\t');
+    let method: GematriaMethod = fields.next()?.parse().ok()?;
+    let value = fields.next()?.parse().ok()?;
+    let text = unescape(fields.next()?)?;
+    Some(((method, settings, 0, CacheKind::Text, text), value))
+}
+
+// Util function to build an invalid data error.
+fn invalid_data(message: String) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
+
+impl GematriaContext {
+    /// Writes the cached values to the given writer, along with the settings they were calculated with.
+    ///
+    /// The values of a [`GematriaMethod::Custom`] strategy are not written, as the strategy itself is not,
+    /// nor are the values of single characters. Nothing but the header is written when caching is disabled.
+    pub fn write_cache_to<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        writeln!(writer, "{} {}", CACHE_MAGIC, env!("CARGO_PKG_VERSION"))?;
+        writeln!(writer, "{}", self.config().fingerprint())?;
+
+        let Some(ref cache) = self.cache else {
+            return Ok(());
+        };
+        let cache = cache.lock();
+        // Only the values calculated with the settings of the context, which are written in the header
+        for ((method, settings, _, kind, text), value) in cache.entries() {
+            if *method != GematriaMethod::Custom
+                && *settings == self.settings
+                && *kind == CacheKind::Text
+            {
+                writeln!(writer, "{}\t{}\t{}", method.name(), value, escape(text))?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Reads values written by [`GematriaContext::write_cache_to`] into the cache, returning the number of values read.
+    ///
+    /// Fails with [`io::ErrorKind::InvalidData`] for a cache written by another crate version or
+    /// with other settings (its values may differ), and for a corrupted cache.
+    /// Nothing is read when caching is disabled.
+    pub fn read_cache_from<R: BufRead>(&self, reader: &mut R) -> io::Result<usize> {
+        let mut lines = reader.lines();
+        let header = lines.next().transpose()?.unwrap_or_default();
+        let expected = format!("{} {}", CACHE_MAGIC, env!("CARGO_PKG_VERSION"));
+        if header != expected {
+            return Err(invalid_data(match header.starts_with(CACHE_MAGIC) {
+                true => format!("cache written by another version ({})", header),
+                false => "not a gematria cache".to_string(),
+            }));
+        }
+        let fingerprint = lines.next().transpose()?.unwrap_or_default();
+        if fingerprint != self.config().fingerprint() {
+            return Err(invalid_data(
+                "cache written with other settings".to_string(),
+            ));
+        }
+
+        let Some(ref cache) = self.cache else {
+            return Ok(0);
+        };
+        let mut entries = Vec::new();
+        for (number, line) in lines.enumerate() {
+            let line = line?;
+            let entry = parse_entry(&line, self.settings).ok_or_else(|| {
+                invalid_data(format!("corrupted gematria cache (entry {})", number + 1))
+            })?;
+            entries.push(entry);
+        }
+
+        let read = entries.len();
+        let mut cache = cache.lock();
+        for (key, value) in entries {
+            cache.insert(key, value);
+        }
+        Ok(read)
+    }
+
+    /// Saves the cached values to a file, see [`GematriaContext::write_cache_to`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gematria_rs::GematriaBuilder;
+    ///
+    /// let path = std::env::temp_dir().join("gematria-doc-cache.txt");
+    /// let gmctx = GematriaBuilder::new().with_cache(true).init_gematria();
+    /// gmctx.calculate_value("שלום");
+    /// gmctx.save_cache(&path)?;
+    ///
+    /// // A later run reloads the values instead of calculating them again
+    /// let gmctx = GematriaBuilder::new().with_cache(true).init_gematria();
+    /// assert_eq!(gmctx.load_cache(&path)?, 1);
+    /// # std::fs::remove_file(&path)?;
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    pub fn save_cache<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        let mut writer = BufWriter::new(File::create(path)?);
+        self.write_cache_to(&mut writer)?;
+        writer.flush()
+    }
+
+    /// Loads values saved by [`GematriaContext::save_cache`] from a file into the cache,
+    /// see [`GematriaContext::read_cache_from`].
+    pub fn load_cache<P: AsRef<Path>>(&self, path: P) -> io::Result<usize> {
+        self.read_cache_from(&mut BufReader::new(File::open(path)?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{std_gematria_value, GematriaBuilder, GematriaCalculation, Kolel, Modifier};
+
+    #[test]
+    fn test_least_recently_used_is_evicted() {
+        let key = |word: &str| {
+            (
+                GematriaMethod::MisparHechrechi,
+                0,
+                0,
+                CacheKind::Text,
+                word.to_string(),
+            )
+        };
+        let mut cache = ValueCache::new(Some(2));
+        cache.insert(key("א"), 1);
+        cache.insert(key("ב"), 2);
+        // Reading א makes ב the least recently used
+        assert_eq!(cache.get(&key("א")), Some(1));
+        cache.insert(key("ג"), 3);
+
+        assert_eq!(cache.len(), 2);
+        assert_eq!(cache.get(&key("ב")), None);
+        assert_eq!(cache.get(&key("א")), Some(1));
+        assert_eq!(cache.get(&key("ג")), Some(3));
+        // The evicted text is no longer accounted for
+        let stats = cache.stats();
+        assert_eq!((stats.hits(), stats.misses(), stats.entries()), (3, 1, 2));
+        assert_eq!(cache.text_bytes, "אג".len());
+        cache.clear();
+        assert_eq!(cache.stats().memory_bytes(), 0);
+
+        let mut cache = ValueCache::new(Some(0));
+        cache.insert(key("א"), 1);
+        assert_eq!(cache.len(), 0);
+    }
+
+    #[test]
+    fn test_shared_cache_keeps_settings_apart() {
+        let cache = SharedCache::new();
+        let standard = GematriaBuilder::new()
+            .with_shared_cache(cache.clone())
+            .init_gematria();
+        let squared = GematriaBuilder::new()
+            .with_modifier(Modifier::SquareTotal)
+            .with_kolel(Kolel::One)
+            .with_shared_cache(cache.clone())
+            .init_gematria();
+
+        assert_eq!(standard.calculate_value("שלום").value(), 376);
+        assert_eq!(squared.calculate_value("שלום").value(), 376 * 376 + 1);
+        assert_eq!(standard.calculate_value("שלום").value(), 376);
+        assert_eq!(cache.stats().entries(), 2);
+
+        // Changing a setting keys the values of the context apart again
+        let mut kolel = GematriaBuilder::new()
+            .with_shared_cache(cache.clone())
+            .init_gematria();
+        kolel.set_kolel(Some(Kolel::One));
+        assert_eq!(kolel.calculate_value("שלום").value(), 377);
+        assert_eq!(standard.calculate_value("שלום").value(), 376);
+    }
+
+    #[test]
+    fn test_shared_cache_keeps_strategies_apart() {
+        struct Scaled(u32);
+
+        impl GematriaCalculation for Scaled {
+            fn calculate_value(&self, letter_index: u32) -> u32 {
+                self.0 * std_gematria_value(&letter_index)
+            }
+
+            fn method_type(&self) -> GematriaMethod {
+                GematriaMethod::Custom
+            }
+        }
+
+        let cache = SharedCache::new();
+        let doubled = GematriaBuilder::new()
+            .with_strategy(Box::new(Scaled(2)))
+            .with_shared_cache(cache.clone());
+        let tripled = GematriaBuilder::new()
+            .with_strategy(Box::new(Scaled(3)))
+            .with_shared_cache(cache.clone())
+            .init_gematria();
+        let first = doubled.clone().init_gematria();
+
+        assert_eq!(first.calculate_value("אב").value(), 6);
+        assert_eq!(tripled.calculate_value("אב").value(), 9);
+        assert_eq!(first.calculate_value("אב").value(), 6);
+        // Contexts built from the same builder share the values of their strategy
+        let second = doubled.init_gematria();
+        assert_eq!(second.calculate_value("אב").value(), 6);
+        assert_eq!(cache.stats().entries(), 2);
+        assert_eq!(cache.stats().hits(), 2);
+
+        let mut replaced = GematriaBuilder::new()
+            .with_shared_cache(cache.clone())
+            .init_gematria();
+        replaced.set_strategy(Box::new(Scaled(4)));
+        assert_eq!(replaced.calculate_value("אב").value(), 12);
+        assert_eq!(tripled.calculate_value("אב").value(), 9);
+    }
+
+    #[test]
+    fn test_char_values_are_cached_apart() {
+        let gmctx = GematriaBuilder::new()
+            .with_method(GematriaMethod::MisparHaMerubahHaKlali)
+            .with_cache(true)
+            .init_gematria();
+        assert_eq!(gmctx.calculate_value("ב").value(), 4);
+        assert_eq!(gmctx.calculate_char_value('ב'), 2);
+        assert_eq!(gmctx.calculate_value("ב").value(), 4);
+
+        let gmctx = GematriaBuilder::new()
+            .with_kolel(Kolel::One)
+            .with_cache(true)
+            .init_gematria();
+        assert_eq!(gmctx.calculate_char_value('א'), 1);
+        assert_eq!(gmctx.calculate_value("א").value(), 2);
+        assert_eq!(gmctx.calculate_char_value('א'), 1);
+        assert_eq!(gmctx.cache_stats().unwrap().entries(), 2);
+
+        // Only the values of texts are persisted
+        let mut bytes = Vec::new();
+        gmctx.write_cache_to(&mut bytes).unwrap();
+        let reloaded = GematriaBuilder::new()
+            .with_kolel(Kolel::One)
+            .with_cache(true)
+            .init_gematria();
+        assert_eq!(reloaded.read_cache_from(&mut bytes.as_slice()).unwrap(), 1);
+    }
+
+    #[test]
+    fn test_cache_round_trip() {
+        let gmctx = GematriaBuilder::new()
+            .with_cache_capacity(2)
+            .init_gematria();
+        for word in ["שלום", "a\tb\\", "אמת"] {
+            gmctx.calculate_value(word);
+        }
+        let mut bytes = Vec::new();
+        gmctx.write_cache_to(&mut bytes).unwrap();
+
+        let reloaded = GematriaBuilder::new().with_cache(true).init_gematria();
+        assert_eq!(reloaded.read_cache_from(&mut bytes.as_slice()).unwrap(), 2);
+        // The most recently used values were kept, so אמת is read from the cache
+        assert_eq!(
+            reloaded.calculate_value("אמת").value(),
+            gmctx.calculate_value("אמת").value()
+        );
+        assert_eq!(reloaded.cache.as_ref().unwrap().lock().len(), 2);
+
+        // Values calculated with other settings are refused
+        let other = GematriaBuilder::new()
+            .with_cache(true)
+            .with_method(GematriaMethod::MisparGadol)
+            .init_gematria();
+        let error = other.read_cache_from(&mut bytes.as_slice()).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+        assert_eq!(escape("a\tb\\\n"), "a\\tb\\\\\\n");
+        assert_eq!(unescape(&escape("a\tb\\\n")).as_deref(), Some("a\tb\\\n"));
+    }
+}
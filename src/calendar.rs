@@ -0,0 +1,493 @@
+// Hebrew calendar dates, converted from Gregorian dates and valued with gematria.
+
+use std::{
+    fmt,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use crate::{format_hebrew_year, to_hebrew_numeral, GematriaContext, GematriaResult};
+
+/// The fixed day number (days since 1 January of year 1, Gregorian) of the Hebrew calendar epoch.
+const HEBREW_EPOCH: i64 = -1_373_427;
+
+/// The fixed day number of 1 January 1970.
+const UNIX_EPOCH_DAY: i64 = 719_163;
+
+/// Enumerates the months of the Hebrew calendar, with both Adars of leap years.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum HebrewMonth {
+    Tishrei,
+    Cheshvan,
+    Kislev,
+    Tevet,
+    Shevat,
+    /// Adar of a common year.
+    Adar,
+    /// The first Adar of a leap year.
+    AdarI,
+    /// The second Adar of a leap year.
+    AdarII,
+    Nisan,
+    Iyar,
+    Sivan,
+    Tammuz,
+    Av,
+    Elul,
+}
+
+impl HebrewMonth {
+    /// Gets the transliterated name of the month.
+    pub fn name(&self) -> &'static str {
+        match self {
+            HebrewMonth::Tishrei => "Tishrei",
+            HebrewMonth::Cheshvan => "Cheshvan",
+            HebrewMonth::Kislev => "Kislev",
+            HebrewMonth::Tevet => "Tevet",
+            HebrewMonth::Shevat => "Shevat",
+            HebrewMonth::Adar => "Adar",
+            HebrewMonth::AdarI => "Adar I",
+            HebrewMonth::AdarII => "Adar II",
+            HebrewMonth::Nisan => "Nisan",
+            HebrewMonth::Iyar => "Iyar",
+            HebrewMonth::Sivan => "Sivan",
+            HebrewMonth::Tammuz => "Tammuz",
+            HebrewMonth::Av => "Av",
+            HebrewMonth::Elul => "Elul",
+        }
+    }
+
+    /// Gets the name of the month in Hebrew letters.
+    pub fn hebrew_name(&self) -> &'static str {
+        match self {
+            HebrewMonth::Tishrei => "תשרי",
+            HebrewMonth::Cheshvan => "חשון",
+            HebrewMonth::Kislev => "כסלו",
+            HebrewMonth::Tevet => "טבת",
+            HebrewMonth::Shevat => "שבט",
+            HebrewMonth::Adar => "אדר",
+            HebrewMonth::AdarI => "אדר א׳",
+            HebrewMonth::AdarII => "אדר ב׳",
+            HebrewMonth::Nisan => "ניסן",
+            HebrewMonth::Iyar => "אייר",
+            HebrewMonth::Sivan => "סיון",
+            HebrewMonth::Tammuz => "תמוז",
+            HebrewMonth::Av => "אב",
+            HebrewMonth::Elul => "אלול",
+        }
+    }
+
+    // Util function to get the month of its number, counted from Nisan (Adar II is 13).
+    fn from_number(month: i64, leap: bool) -> Self {
+        match month {
+            1 => HebrewMonth::Nisan,
+            2 => HebrewMonth::Iyar,
+            3 => HebrewMonth::Sivan,
+            4 => HebrewMonth::Tammuz,
+            5 => HebrewMonth::Av,
+            6 => HebrewMonth::Elul,
+            7 => HebrewMonth::Tishrei,
+            8 => HebrewMonth::Cheshvan,
+            9 => HebrewMonth::Kislev,
+            10 => HebrewMonth::Tevet,
+            11 => HebrewMonth::Shevat,
+            12 if leap => HebrewMonth::AdarI,
+            12 => HebrewMonth::Adar,
+            _ => HebrewMonth::AdarII,
+        }
+    }
+}
+
+impl fmt::Display for HebrewMonth {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+/// `GregorianDate` is a date of the (proleptic) Gregorian calendar.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct GregorianDate {
+    // The year (astronomical numbering, 0 is 1 BCE).
+    year: i32,
+
+    // The month (1-12).
+    month: u32,
+
+    // The day of the month (1 based).
+    day: u32,
+}
+
+impl GregorianDate {
+    /// Creates a date, returning `None` when the month or day are out of range.
+    pub fn new(year: i32, month: u32, day: u32) -> Option<Self> {
+        if !(1..=12).contains(&month) || day == 0 || day > days_in_gregorian_month(year, month) {
+            return None;
+        }
+        Some(GregorianDate { year, month, day })
+    }
+
+    /// Gets the current date, in UTC.
+    pub fn today() -> Self {
+        let seconds = match SystemTime::now().duration_since(UNIX_EPOCH) {
+            Ok(elapsed) => elapsed.as_secs() as i64,
+            Err(before) => -(before.duration().as_secs() as i64),
+        };
+        Self::from_fixed(UNIX_EPOCH_DAY + seconds.div_euclid(86_400))
+    }
+
+    /// Gets the year.
+    pub fn year(&self) -> i32 {
+        self.year
+    }
+
+    /// Gets the month (1-12).
+    pub fn month(&self) -> u32 {
+        self.month
+    }
+
+    /// Gets the day of the month.
+    pub fn day(&self) -> u32 {
+        self.day
+    }
+
+    // Util function to get the fixed day number of the date.
+    fn fixed(&self) -> i64 {
+        let year = self.year as i64 - 1;
+        let month = self.month as i64;
+        let correction = match month {
+            1 | 2 => 0,
+            _ if is_gregorian_leap_year(self.year) => -1,
+            _ => -2,
+        };
+        365 * year + year.div_euclid(4) - year.div_euclid(100)
+            + year.div_euclid(400)
+            + (367 * month - 362).div_euclid(12)
+            + correction
+            + self.day as i64
+    }
+
+    // Util function to get the date of a fixed day number.
+    fn from_fixed(fixed: i64) -> Self {
+        // Days from 1 March of year 0, in 400 years eras of 146097 days
+        let days = fixed - 1 + 306;
+        let era = days.div_euclid(146_097);
+        let day_of_era = days.rem_euclid(146_097);
+        let year_of_era =
+            (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+        let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+        let shifted_month = (5 * day_of_year + 2) / 153;
+        let day = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+        let month = if shifted_month < 10 {
+            shifted_month + 3
+        } else {
+            shifted_month - 9
+        };
+        let year = era * 400 + year_of_era + i64::from(month <= 2);
+
+        GregorianDate {
+            year: year as i32,
+            month: month as u32,
+            day: day as u32,
+        }
+    }
+}
+
+impl fmt::Display for GregorianDate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:04}-{:02}-{:02}", self.year, self.month, self.day)
+    }
+}
+
+// Util function to check whether a Gregorian year is a leap year.
+fn is_gregorian_leap_year(year: i32) -> bool {
+    year % 4 == 0 && (year % 100 != 0 || year % 400 == 0)
+}
+
+// Util function to get the number of days of a Gregorian month.
+fn days_in_gregorian_month(year: i32, month: u32) -> u32 {
+    match month {
+        2 if is_gregorian_leap_year(year) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+/// `HebrewDate` is a date of the Hebrew calendar.
+///
+/// Dates are converted from civil dates, so the evening (after sunset) belonging to the
+/// next Hebrew day is not accounted for.
+///
+/// # Examples
+///
+/// ```
+/// use gematria_rs::{GregorianDate, HebrewDate, HebrewMonth};
+///
+/// let date = HebrewDate::from_gregorian(&GregorianDate::new(2024, 10, 3).unwrap());
+/// assert_eq!((date.day(), date.month(), date.year()), (1, HebrewMonth::Tishrei, 5785));
+/// assert_eq!(date.to_string(), "א׳ תשרי תשפ״ה");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct HebrewDate {
+    // The year (anno mundi).
+    year: u32,
+
+    // The month.
+    month: HebrewMonth,
+
+    // The day of the month (1 based).
+    day: u32,
+}
+
+impl HebrewDate {
+    /// Converts a Gregorian date to its Hebrew date.
+    pub fn from_gregorian(date: &GregorianDate) -> Self {
+        let fixed = date.fixed();
+        let approx = ((fixed - HEBREW_EPOCH) as f64 / (35_975_351.0 / 98_496.0)).floor() as i64 + 1;
+        let year = (approx - 1..)
+            .take_while(|&year| hebrew_new_year(year) <= fixed)
+            .last()
+            .unwrap_or(approx - 1);
+
+        let first = if fixed < fixed_from_hebrew(year, 1, 1) {
+            7
+        } else {
+            1
+        };
+        let month = (first..)
+            .find(|&month| fixed <= fixed_from_hebrew(year, month, last_day_of_month(year, month)))
+            .unwrap_or(first);
+        let day = fixed - fixed_from_hebrew(year, month, 1) + 1;
+
+        HebrewDate {
+            year: year as u32,
+            month: HebrewMonth::from_number(month, is_hebrew_leap_year(year)),
+            day: day as u32,
+        }
+    }
+
+    /// Gets the Hebrew date of the current (UTC) day.
+    pub fn today() -> Self {
+        Self::from_gregorian(&GregorianDate::today())
+    }
+
+    /// Gets the year.
+    pub fn year(&self) -> u32 {
+        self.year
+    }
+
+    /// Gets the month.
+    pub fn month(&self) -> HebrewMonth {
+        self.month
+    }
+
+    /// Gets the day of the month.
+    pub fn day(&self) -> u32 {
+        self.day
+    }
+
+    /// Gets the day of the month as a Hebrew numeral (e.g. כ״ז).
+    pub fn day_numeral(&self) -> String {
+        to_hebrew_numeral(self.day)
+    }
+
+    /// Gets the year as a Hebrew numeral, without the implicit thousands (e.g. תשפ״ה).
+    pub fn year_numeral(&self) -> String {
+        format_hebrew_year(self.year)
+    }
+}
+
+impl fmt::Display for HebrewDate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} {} {}",
+            self.day_numeral(),
+            self.month.hebrew_name(),
+            self.year_numeral()
+        )
+    }
+}
+
+// Util function to check whether a Hebrew year has 13 months.
+fn is_hebrew_leap_year(year: i64) -> bool {
+    (7 * year + 1).rem_euclid(19) < 7
+}
+
+// Util function to get the days elapsed from the epoch to the molad of Tishrei, with the first postponement.
+fn elapsed_days(year: i64) -> i64 {
+    let months = (235 * year - 234).div_euclid(19);
+    let parts = 12_084 + 13_753 * months;
+    let days = 29 * months + parts.div_euclid(25_920);
+    if (3 * (days + 1)).rem_euclid(7) < 3 {
+        days + 1
+    } else {
+        days
+    }
+}
+
+// Util function to get the fixed day number of Rosh Hashana, with the remaining postponements.
+fn hebrew_new_year(year: i64) -> i64 {
+    let previous = elapsed_days(year - 1);
+    let current = elapsed_days(year);
+    let next = elapsed_days(year + 1);
+    let correction = if next - current == 356 {
+        2
+    } else if current - previous == 382 {
+        1
+    } else {
+        0
+    };
+    HEBREW_EPOCH + current + correction
+}
+
+// Util function to get the number of days of a Hebrew year.
+fn days_in_hebrew_year(year: i64) -> i64 {
+    hebrew_new_year(year + 1) - hebrew_new_year(year)
+}
+
+// Util function to get the last day of a month, counted from Nisan (Adar II is 13).
+fn last_day_of_month(year: i64, month: i64) -> i64 {
+    let short = match month {
+        2 | 4 | 6 | 10 | 13 => true,
+        12 => !is_hebrew_leap_year(year),
+        8 => !matches!(days_in_hebrew_year(year), 355 | 385),
+        9 => matches!(days_in_hebrew_year(year), 353 | 383),
+        _ => false,
+    };
+    if short {
+        29
+    } else {
+        30
+    }
+}
+
+// Util function to get the fixed day number of a Hebrew date, with months counted from Nisan.
+fn fixed_from_hebrew(year: i64, month: i64, day: i64) -> i64 {
+    let last_month = if is_hebrew_leap_year(year) { 13 } else { 12 };
+    let months: i64 = if month < 7 {
+        (7..=last_month)
+            .chain(1..month)
+            .map(|m| last_day_of_month(year, m))
+            .sum()
+    } else {
+        (7..month).map(|m| last_day_of_month(year, m)).sum()
+    };
+    hebrew_new_year(year) + months + day - 1
+}
+
+/// `DateGematria` holds the gematria values of the components of a Hebrew date.
+#[derive(Debug, Clone)]
+pub struct DateGematria {
+    // The value of the day numeral.
+    day: GematriaResult,
+
+    // The value of the month name.
+    month: GematriaResult,
+
+    // The value of the year numeral.
+    year: GematriaResult,
+}
+
+impl DateGematria {
+    /// Gets the value of the day numeral.
+    pub fn day(&self) -> &GematriaResult {
+        &self.day
+    }
+
+    /// Gets the value of the month name.
+    pub fn month(&self) -> &GematriaResult {
+        &self.month
+    }
+
+    /// Gets the value of the year numeral.
+    pub fn year(&self) -> &GematriaResult {
+        &self.year
+    }
+
+    /// Gets the total value of the date.
+    pub fn total(&self) -> u64 {
+        self.day.value() + self.month.value() + self.year.value()
+    }
+}
+
+impl GematriaContext {
+    /// Calculates the gematria values of the day numeral, month name and year numeral of a Hebrew date.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gematria_rs::{GematriaContext, GregorianDate, HebrewDate};
+    ///
+    /// let gmctx = GematriaContext::default();
+    /// let date = HebrewDate::from_gregorian(&GregorianDate::new(2024, 10, 3).unwrap());
+    /// let values = gmctx.calculate_date(&date);
+    ///
+    /// assert_eq!(values.month().value(), 910);
+    /// assert_eq!(values.total(), 1 + 910 + 785);
+    /// ```
+    pub fn calculate_date(&self, date: &HebrewDate) -> DateGematria {
+        DateGematria {
+            day: self.calculate_value(&date.day_numeral()),
+            month: self.calculate_value(date.month.hebrew_name()),
+            year: self.calculate_value(&date.year_numeral()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hebrew_date(year: i32, month: u32, day: u32) -> HebrewDate {
+        HebrewDate::from_gregorian(&GregorianDate::new(year, month, day).unwrap())
+    }
+
+    #[test]
+    fn test_from_gregorian() {
+        let cases = [
+            ((1970, 1, 1), (5730, HebrewMonth::Tevet, 23)),
+            ((2000, 1, 1), (5760, HebrewMonth::Tevet, 23)),
+            ((2023, 9, 16), (5784, HebrewMonth::Tishrei, 1)),
+            ((2024, 3, 24), (5784, HebrewMonth::AdarII, 14)),
+            ((2024, 2, 23), (5784, HebrewMonth::AdarI, 14)),
+            ((2025, 3, 14), (5785, HebrewMonth::Adar, 14)),
+            ((2024, 12, 26), (5785, HebrewMonth::Kislev, 25)),
+        ];
+        for ((year, month, day), expected) in cases {
+            let date = hebrew_date(year, month, day);
+            assert_eq!((date.year(), date.month(), date.day()), expected);
+        }
+    }
+
+    #[test]
+    fn test_gregorian_date() {
+        assert_eq!(GregorianDate::new(2023, 2, 29), None);
+        assert!(GregorianDate::new(2024, 2, 29).is_some());
+        for fixed in 700_000..740_000 {
+            assert_eq!(GregorianDate::from_fixed(fixed).fixed(), fixed);
+        }
+        // Consecutive days never skip or repeat a Hebrew date
+        for fixed in 730_000..740_000 {
+            let today = HebrewDate::from_gregorian(&GregorianDate::from_fixed(fixed));
+            let tomorrow = HebrewDate::from_gregorian(&GregorianDate::from_fixed(fixed + 1));
+            assert!(tomorrow.day() == today.day() + 1 || tomorrow.day() == 1);
+        }
+        assert_eq!(
+            GregorianDate::from_fixed(UNIX_EPOCH_DAY).to_string(),
+            "1970-01-01"
+        );
+    }
+
+    #[test]
+    fn test_calculate_date() {
+        let gmctx = GematriaContext::default();
+        let date = hebrew_date(2024, 3, 24);
+        assert_eq!(date.to_string(), "י״ד אדר ב׳ תשפ״ד");
+
+        let values = gmctx.calculate_date(&date);
+        assert_eq!(values.day().value(), 14);
+        assert_eq!(values.month().value(), 207);
+        assert_eq!(values.year().value(), 784);
+        assert_eq!(values.total(), 1005);
+    }
+}
@@ -0,0 +1,265 @@
+// Defines the temurah (letter substitution) ciphers of the Gematria-rs library.
+
+use crate::{
+    methods::base_letter_index, std_gematria_value, GematriaCalculation, GematriaMethod,
+    MisparGadol, HEBREW_LETTERS,
+};
+
+/// A trait defining a temurah cipher, substituting every letter of the alphabet with another.
+///
+/// Letter indices are 1 based, where 1-22 are the regular letters and 23-27 the final forms.
+/// Ciphers valuate a letter as the standard value of its substitute.
+pub trait Cipher {
+    /// Substitutes a letter index with the index of its cipher letter.
+    fn substitute(&self, letter_index: u32) -> u32;
+
+    /// Encodes a text by substituting every Hebrew letter, leaving other characters untouched.
+    fn encode(&self, text: &str) -> String {
+        text.chars()
+            .map(|c| match HEBREW_LETTERS.iter().position(|&l| l == c) {
+                Some(i) => HEBREW_LETTERS[self.substitute(i as u32 + 1) as usize - 1],
+                None => c,
+            })
+            .collect()
+    }
+}
+
+/// Represents the Achbi cipher (א״ך ב״י), where each half of the alphabet is reversed within itself.
+///
+/// The letters א-כ are exchanged with each other (א↔כ, ב↔י, ...) as are the letters ל-ת (ל↔ת, מ↔ש, ...),
+/// where ו and פ, being in the middle of their halves, stay in place.
+/// Final forms are substituted as their regular letters.
+#[derive(Clone)]
+pub struct Achbi;
+
+impl Achbi {
+    /// Gets the value of a letter index, in constant expressions too.
+    pub const fn letter_value(&self, letter_index: u32) -> u32 {
+        std_gematria_value(&achbi_substitute(letter_index))
+    }
+}
+
+// Util function to substitute a letter index under Achbi.
+const fn achbi_substitute(letter_index: u32) -> u32 {
+    match base_letter_index(letter_index) {
+        index @ 1..=11 => 12 - index,
+        index => 34 - index,
+    }
+}
+
+impl Cipher for Achbi {
+    fn substitute(&self, letter_index: u32) -> u32 {
+        achbi_substitute(letter_index)
+    }
+}
+
+impl GematriaCalculation for Achbi {
+    fn calculate_value(&self, letter_index: u32) -> u32 {
+        self.letter_value(letter_index)
+    }
+
+    fn method_type(&self) -> GematriaMethod {
+        GematriaMethod::Achbi
+    }
+}
+
+/// Represents the Avgad cipher (א״ב ג״ד), where each letter is replaced by the letter following it.
+///
+/// The shift is configurable, so a shift of 1 is the classic Avgad (א→ב, ..., ת→א),
+/// -1 is the reverse Avgad (ב→א, ..., א→ת) and larger values rotate the alphabet further.
+/// Final forms are shifted as their regular letters.
+#[derive(Clone)]
+pub struct Avgad {
+    shift: i32,
+}
+
+impl Avgad {
+    /// The shift of the classic Avgad cipher.
+    pub const DEFAULT_SHIFT: i32 = 1;
+
+    pub const fn new(shift: i32) -> Self {
+        Self { shift }
+    }
+
+    /// Gets the letter shift of this cipher.
+    pub fn shift(&self) -> i32 {
+        self.shift
+    }
+
+    /// Gets the value of a letter index, in constant expressions too.
+    pub const fn letter_value(&self, letter_index: u32) -> u32 {
+        std_gematria_value(&self.substitute_index(letter_index))
+    }
+
+    // Util function to substitute a letter index, shifting it within the alphabet.
+    const fn substitute_index(&self, letter_index: u32) -> u32 {
+        // The shift is reduced first, so large shifts do not overflow
+        let index = base_letter_index(letter_index) as i32 - 1;
+        (index + self.shift.rem_euclid(22)).rem_euclid(22) as u32 + 1
+    }
+}
+
+impl Default for Avgad {
+    fn default() -> Self {
+        Self::new(Self::DEFAULT_SHIFT)
+    }
+}
+
+impl Cipher for Avgad {
+    fn substitute(&self, letter_index: u32) -> u32 {
+        self.substitute_index(letter_index)
+    }
+}
+
+impl GematriaCalculation for Avgad {
+    fn calculate_value(&self, letter_index: u32) -> u32 {
+        self.letter_value(letter_index)
+    }
+
+    fn method_type(&self) -> GematriaMethod {
+        GematriaMethod::Avgad
+    }
+}
+
+/// Represents the Ayak Bachar cipher (אי״ק בכ״ר), also known as the "Qabalah of Nine Chambers".
+///
+/// The letters are arranged in nine chambers holding the units, tens and hundreds sharing the same digit
+/// (א-י-ק, ב-כ-ר, ..., ט-צ-ץ), and each letter is cyclically exchanged with the next letter of its chamber:
+/// units become tens, tens become hundreds and hundreds become units (א→י→ק→א).
+///
+/// # Final forms policy
+///
+/// Following the traditional chambers, the final forms complete the hundreds (ך=500, ם=600, ן=700, ף=800, ץ=900),
+/// so ה→נ→ך→ה, and letters are valued as in [`GematriaMethod::MisparGadol`].
+/// This means a final form is not exchanged as its regular letter (e.g. ן→ז while נ→ך).
+#[derive(Clone)]
+pub struct AyakBachar;
+
+impl AyakBachar {
+    /// Gets the value of a letter index, in constant expressions too.
+    pub const fn letter_value(&self, letter_index: u32) -> u32 {
+        MisparGadol.letter_value(ayak_bachar_substitute(letter_index))
+    }
+}
+
+// Util function to substitute a letter index under Ayak Bachar.
+const fn ayak_bachar_substitute(letter_index: u32) -> u32 {
+    // Split the index into its digit and magnitude (units, tens or hundreds)
+    let (digit, magnitude) = match letter_index {
+        1..=9 => (letter_index, 0),
+        10..=18 => (letter_index - 9, 1),
+        index => (index - 18, 2),
+    };
+
+    match (magnitude + 1) % 3 {
+        0 => digit,
+        1 => digit + 9,
+        _ => digit + 18,
+    }
+}
+
+impl Cipher for AyakBachar {
+    fn substitute(&self, letter_index: u32) -> u32 {
+        ayak_bachar_substitute(letter_index)
+    }
+}
+
+impl GematriaCalculation for AyakBachar {
+    fn calculate_value(&self, letter_index: u32) -> u32 {
+        self.letter_value(letter_index)
+    }
+
+    fn method_type(&self) -> GematriaMethod {
+        GematriaMethod::AyakBachar
+    }
+}
+
+/// Represents the Achas Beta cipher (א״ח ב״ט), where the alphabet is split into three groups of seven
+/// letters and each letter is replaced by the letter at the same position in the next group.
+///
+/// The groups are א-ז, ח-נ and ס-ש, so א→ח→ס→א, ב→ט→ע→ב, ..., ז→נ→ש→ז,
+/// while ת, the twenty-second letter left outside the groups, stays in place.
+/// Final forms are substituted as their regular letters.
+#[derive(Clone)]
+pub struct AchasBeta;
+
+impl AchasBeta {
+    /// Gets the value of a letter index, in constant expressions too.
+    pub const fn letter_value(&self, letter_index: u32) -> u32 {
+        std_gematria_value(&achas_beta_substitute(letter_index))
+    }
+}
+
+// Util function to substitute a letter index under Achas Beta.
+const fn achas_beta_substitute(letter_index: u32) -> u32 {
+    match base_letter_index(letter_index) {
+        22 => 22,
+        index => (index - 1 + 7) % 21 + 1,
+    }
+}
+
+impl Cipher for AchasBeta {
+    fn substitute(&self, letter_index: u32) -> u32 {
+        achas_beta_substitute(letter_index)
+    }
+}
+
+impl GematriaCalculation for AchasBeta {
+    fn calculate_value(&self, letter_index: u32) -> u32 {
+        self.letter_value(letter_index)
+    }
+
+    fn method_type(&self) -> GematriaMethod {
+        GematriaMethod::AchasBeta
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_achbi_encode() {
+        assert_eq!(Achbi.encode("אבגדהו"), "כיטחזו");
+        assert_eq!(Achbi.encode("למנסעפ"), "תשרקצפ");
+        // Final forms are substituted as their regular letters
+        assert_eq!(Achbi.encode("ךם"), "אש");
+        // Encoding twice restores the original text
+        assert_eq!(Achbi.encode(&Achbi.encode("בראשית")), "בראשית");
+    }
+
+    #[test]
+    fn test_avgad_encode() {
+        assert_eq!(Avgad::default().encode("אבגת"), "בגדא");
+        assert_eq!(Avgad::new(-1).encode("אבגת"), "תאבש");
+        assert_eq!(Avgad::new(22).encode("שלום"), "שלומ");
+        // i32::MAX is 1 more than a multiple of 22, and i32::MIN 2 less
+        assert_eq!(Avgad::new(i32::MAX).encode("אבגת"), "בגדא");
+        assert_eq!(Avgad::new(i32::MIN).encode("אבגת"), "שתאר");
+    }
+
+    #[test]
+    fn test_ayak_bachar_encode() {
+        assert_eq!(AyakBachar.encode("איק"), "יקא");
+        assert_eq!(AyakBachar.encode("הנך"), "נךה");
+        assert_eq!(AyakBachar.encode("דמת"), "מתד");
+        assert_eq!(AyakBachar.encode("טצץ"), "צץט");
+        // Three substitutions restore the original text
+        let encoded = AyakBachar.encode(&AyakBachar.encode(&AyakBachar.encode("בראשית")));
+        assert_eq!(encoded, "בראשית");
+    }
+
+    #[test]
+    fn test_achas_beta_encode() {
+        assert_eq!(AchasBeta.encode("אבגדהוז"), "חטיכלמנ");
+        assert_eq!(AchasBeta.encode("חטיכלמנ"), "סעפצקרש");
+        assert_eq!(AchasBeta.encode("סעפצקרש"), "אבגדהוז");
+        assert_eq!(AchasBeta.encode("ת"), "ת");
+        // Final forms are substituted as their regular letters
+        assert_eq!(AchasBeta.encode("ךםןףץ"), "צרשגד");
+        // Every letter is substituted by a distinct letter
+        let mut substitutes: Vec<u32> = (1..=22).map(|i| AchasBeta.substitute(i)).collect();
+        substitutes.sort();
+        assert_eq!(substitutes, (1..=22).collect::<Vec<u32>>());
+    }
+}
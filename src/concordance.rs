@@ -0,0 +1,134 @@
+// Concordance (keyword in context) of words or gematria values within a text.
+
+use std::ops::Range;
+
+use crate::{GematriaContext, GematriaValue, Match};
+
+/// `ConcordanceLine` is an occurrence of a word along with the words surrounding it,
+/// a single line of a keyword in context (KWIC) concordance.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConcordanceLine {
+    // The matched word.
+    matched: Match,
+
+    // The original text preceding the word, within the context window.
+    before: String,
+
+    // The original text following the word, within the context window.
+    after: String,
+}
+
+impl ConcordanceLine {
+    /// Gets the matched word, with its value and position.
+    pub fn matched(&self) -> &Match {
+        &self.matched
+    }
+
+    /// Gets the original text preceding the word, whitespace collapsed to single spaces.
+    pub fn before(&self) -> &str {
+        &self.before
+    }
+
+    /// Gets the original text following the word, whitespace collapsed to single spaces.
+    pub fn after(&self) -> &str {
+        &self.after
+    }
+}
+
+impl GematriaContext {
+    /// Builds the concordance of a word: its occurrences with up to `window` words on each side.
+    ///
+    /// Occurrences are matched after the vowel handling of the context.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gematria_rs::GematriaContext;
+    ///
+    /// let gmctx = GematriaContext::default();
+    /// let lines = gmctx.concordance_of_word("יין", "נכנס יין יצא סוד", 1);
+    ///
+    /// assert_eq!(lines.len(), 1);
+    /// assert_eq!(
+    ///     (lines[0].before(), lines[0].matched().word(), lines[0].after()),
+    ///     ("נכנס", "יין", "יצא")
+    /// );
+    /// ```
+    pub fn concordance_of_word(
+        &self,
+        word: &str,
+        text: &str,
+        window: usize,
+    ) -> Vec<ConcordanceLine> {
+        let key = self.handle_vowels(word);
+        let matches = self.search_matching_filter(text, move |_, w| w == key);
+        self.concordance(text, matches, window)
+    }
+
+    /// Builds the concordance of a value: the occurrences of the words with that value,
+    /// with up to `window` words on each side.
+    pub fn concordance_of_value(
+        &self,
+        value: &GematriaValue,
+        text: &str,
+        window: usize,
+    ) -> Vec<ConcordanceLine> {
+        let value = *value;
+        let matches = self.search_matching_filter(text, move |v, _| v == value);
+        self.concordance(text, matches, window)
+    }
+
+    fn concordance<I: Iterator<Item = Match>>(
+        &self,
+        text: &str,
+        matches: I,
+        window: usize,
+    ) -> Vec<ConcordanceLine> {
+        let ranges: Vec<Range<usize>> = self
+            .tokenizer
+            .tokenize_with_ranges(text)
+            .map(|(range, _)| range)
+            .collect();
+        matches
+            .map(|matched| {
+                let index = matched.index();
+                let first = &ranges[index.saturating_sub(window)];
+                let last = &ranges[(index + window).min(ranges.len() - 1)];
+                let range = matched.byte_range();
+                ConcordanceLine {
+                    before: collapse_whitespace(&text[first.start..range.start]),
+                    after: collapse_whitespace(&text[range.end..last.end]),
+                    matched,
+                }
+            })
+            .collect()
+    }
+}
+
+// Util function to collapse runs of whitespace into single spaces, trimming the ends.
+fn collapse_whitespace(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_concordance_windows() {
+        let gmctx = GematriaContext::default();
+        let text = "נכנס יין\nיצא סוד אֶת־הָאוֹר";
+        let lines = gmctx.concordance_of_value(&70, text, 2);
+
+        let kwic: Vec<(&str, &str, &str)> = lines
+            .iter()
+            .map(|l| (l.before(), l.matched().word(), l.after()))
+            .collect();
+        assert_eq!(
+            kwic,
+            vec![("נכנס", "יין", "יצא סוד"), ("יין יצא", "סוד", "אֶת־הָאוֹר")]
+        );
+        assert_eq!(lines[1].matched().index(), 3);
+        assert!(gmctx.concordance_of_word("שלום", text, 2).is_empty());
+    }
+}
@@ -0,0 +1,357 @@
+// Snapshot of the effective configuration of a gematria context.
+
+use std::hash::{DefaultHasher, Hash, Hasher};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "normalization")]
+use crate::Normalization;
+use crate::{
+    default_alphabet, GematriaBuilder, GematriaContext, GematriaMethod, Kolel, MarkStripping,
+    Modifier, SofitPolicy, Tokenizer, Transliteration,
+};
+
+/// `GematriaConfig` is the fully resolved configuration of a [`GematriaContext`],
+/// describing exactly how its values are computed.
+///
+/// A config can be turned back into a [`GematriaBuilder`] to reproduce the same context,
+/// and is serializable when the `serde` feature is enabled.
+/// The config of a [`GematriaMethod::Custom`] strategy does not hold the strategy itself,
+/// which has to be injected again with [`GematriaBuilder::with_strategy`].
+///
+/// # Examples
+///
+/// ```
+/// use gematria_rs::{GematriaBuilder, GematriaMethod, Modifier};
+///
+/// let gmctx = GematriaBuilder::new()
+///     .with_method(GematriaMethod::MisparGadol)
+///     .with_modifier(Modifier::PlusKolel)
+///     .init_gematria();
+///
+/// let config = gmctx.config();
+/// assert_eq!(config.method(), GematriaMethod::MisparGadol);
+/// assert_eq!(config.modifiers(), &[Modifier::PlusKolel]);
+///
+/// let reproduced = GematriaBuilder::from(config).init_gematria();
+/// assert_eq!(
+///     reproduced.calculate_value("שלום").value(),
+///     gmctx.calculate_value("שלום").value()
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct GematriaConfig {
+    // The calculation method.
+    method: GematriaMethod,
+
+    // Whether values are cached.
+    cache_enabled: bool,
+
+    // The maximum number of cached values, `None` when unbounded.
+    #[cfg_attr(feature = "serde", serde(default))]
+    cache_capacity: Option<usize>,
+
+    // Whether vowels are preserved on results.
+    preserve_vowels: bool,
+
+    // The letter shift used by shift ciphers.
+    shift: i32,
+
+    // The modifiers applied on the total value of words, in order.
+    modifiers: Vec<Modifier>,
+
+    // The letter spellings overriding the default milui spellings, by letter.
+    milui_spellings: Vec<(char, String)>,
+
+    // The kolel adjustment added to the value of texts.
+    kolel: Option<Kolel>,
+
+    // The character to index assignments, when they differ from the standard alphabet.
+    char_map: Option<Vec<(char, u32)>>,
+
+    // Whether words marked as Hebrew numerals are recognized.
+    #[cfg_attr(feature = "serde", serde(default))]
+    recognize_numerals: bool,
+
+    // Whether the gershayim marks of abbreviations are stripped.
+    #[cfg_attr(feature = "serde", serde(default))]
+    strip_abbreviations: bool,
+
+    // The tokenizer splitting texts into words.
+    #[cfg_attr(feature = "serde", serde(default))]
+    tokenizer: Tokenizer,
+
+    // The Unicode normalization applied on texts before calculation.
+    #[cfg(feature = "normalization")]
+    #[cfg_attr(feature = "serde", serde(default))]
+    normalization: Option<Normalization>,
+
+    // The classes of marks removed from the words of results.
+    #[cfg_attr(feature = "serde", serde(default))]
+    mark_stripping: MarkStripping,
+
+    // The policy valuing the final forms.
+    #[cfg_attr(feature = "serde", serde(default))]
+    sofit_policy: SofitPolicy,
+
+    // The transliteration of Latin words applied before calculation.
+    #[cfg_attr(feature = "serde", serde(default))]
+    transliteration: Option<Transliteration>,
+}
+
+impl GematriaConfig {
+    /// Gets the calculation method.
+    pub fn method(&self) -> GematriaMethod {
+        self.method
+    }
+
+    /// Gets whether values are cached.
+    pub fn cache_enabled(&self) -> bool {
+        self.cache_enabled
+    }
+
+    /// Gets the maximum number of cached values, `None` when the cache is unbounded or disabled.
+    pub fn cache_capacity(&self) -> Option<usize> {
+        self.cache_capacity
+    }
+
+    /// Gets whether vowels are preserved on results.
+    pub fn preserve_vowels(&self) -> bool {
+        self.preserve_vowels
+    }
+
+    /// Gets the letter shift used by shift ciphers ([`GematriaMethod::Avgad`]).
+    pub fn shift(&self) -> i32 {
+        self.shift
+    }
+
+    /// Gets the modifiers applied on the total value of words, in order.
+    pub fn modifiers(&self) -> &[Modifier] {
+        &self.modifiers
+    }
+
+    /// Gets the letter spellings overriding the default milui spellings, sorted by letter.
+    pub fn milui_spellings(&self) -> &[(char, String)] {
+        &self.milui_spellings
+    }
+
+    /// Gets the kolel adjustment added to the value of texts.
+    pub fn kolel(&self) -> Option<Kolel> {
+        self.kolel
+    }
+
+    /// Gets the character to index assignments sorted by character,
+    /// `None` when the letters of the alphabet of the method are used.
+    pub fn char_map(&self) -> Option<&[(char, u32)]> {
+        self.char_map.as_deref()
+    }
+
+    /// Gets whether words marked as Hebrew numerals are recognized.
+    pub fn recognize_numerals(&self) -> bool {
+        self.recognize_numerals
+    }
+
+    /// Gets whether the gershayim marks of abbreviations are stripped.
+    pub fn strip_abbreviations(&self) -> bool {
+        self.strip_abbreviations
+    }
+
+    /// Gets the tokenizer splitting texts into words.
+    pub fn tokenizer(&self) -> &Tokenizer {
+        &self.tokenizer
+    }
+
+    /// Gets the classes of marks removed from the words of results, unless vowels are preserved.
+    pub fn mark_stripping(&self) -> MarkStripping {
+        self.mark_stripping
+    }
+
+    /// Gets the policy valuing the final forms.
+    pub fn sofit_policy(&self) -> SofitPolicy {
+        self.sofit_policy
+    }
+
+    /// Gets the transliteration of Latin words applied before calculation.
+    pub fn transliteration(&self) -> Option<&Transliteration> {
+        self.transliteration.as_ref()
+    }
+
+    /// Gets the Unicode normalization applied on texts before calculation.
+    #[cfg(feature = "normalization")]
+    pub fn normalization(&self) -> Option<Normalization> {
+        self.normalization
+    }
+}
+
+impl GematriaConfig {
+    /// Describes the settings the values depend on on a single line, leaving out the caching settings.
+    pub(crate) fn fingerprint(&self) -> String {
+        let config = GematriaConfig {
+            cache_enabled: false,
+            cache_capacity: None,
+            ..self.clone()
+        };
+        format!("{:?}", config)
+    }
+
+    /// Hashes the fingerprint of the settings apart from the method, keying the cached values
+    /// of the contexts sharing a cache.
+    pub(crate) fn settings_hash(&self) -> u64 {
+        let config = GematriaConfig {
+            method: GematriaMethod::MisparHechrechi,
+            ..self.clone()
+        };
+        let mut hasher = DefaultHasher::new();
+        config.fingerprint().hash(&mut hasher);
+        hasher.finish()
+    }
+}
+
+impl From<GematriaConfig> for GematriaBuilder {
+    fn from(config: GematriaConfig) -> Self {
+        let mut builder = config.modifiers.into_iter().fold(
+            GematriaBuilder::new()
+                .with_method(config.method)
+                .with_cache(config.cache_enabled)
+                .with_vowels(config.preserve_vowels)
+                .with_shift(config.shift)
+                .with_numerals(config.recognize_numerals)
+                .with_abbreviations(config.strip_abbreviations)
+                .with_tokenizer(config.tokenizer)
+                .with_mark_stripping(config.mark_stripping)
+                .with_sofit_policy(config.sofit_policy),
+            GematriaBuilder::with_modifier,
+        );
+        if let Some(capacity) = config.cache_capacity {
+            builder = builder.with_cache_capacity(capacity);
+        }
+        if let Some(kolel) = config.kolel {
+            builder = builder.with_kolel(kolel);
+        }
+        if let Some(transliteration) = config.transliteration {
+            builder = builder.with_transliteration(transliteration);
+        }
+        if let Some(char_map) = config.char_map {
+            builder = builder.with_char_map(char_map.into_iter().collect());
+        }
+        #[cfg(feature = "normalization")]
+        if let Some(normalization) = config.normalization {
+            builder = builder.with_normalization(normalization);
+        }
+        config
+            .milui_spellings
+            .iter()
+            .fold(builder, |builder, (letter, spelling)| {
+                builder.with_milui_spelling(*letter, spelling)
+            })
+    }
+}
+
+impl GematriaContext {
+    /// Gets the fully resolved configuration of the context.
+    pub fn config(&self) -> GematriaConfig {
+        let mut milui_spellings: Vec<(char, String)> = self
+            .milui_spellings
+            .iter()
+            .map(|(&letter, spelling)| (letter, spelling.iter().collect()))
+            .collect();
+        milui_spellings.sort();
+
+        // The letters of the alphabet of the method are the standard alphabet
+        let letters = default_alphabet(self.get_current_method()).letters();
+        let char_map = if *self.character_map.char_map() == letters {
+            None
+        } else {
+            let mut char_map: Vec<(char, u32)> = self
+                .character_map
+                .char_map()
+                .iter()
+                .map(|(&c, &index)| (c, index))
+                .collect();
+            char_map.sort();
+            Some(char_map)
+        };
+
+        GematriaConfig {
+            method: self.get_current_method(),
+            cache_enabled: self.cache.is_some(),
+            cache_capacity: self.cache_capacity(),
+            preserve_vowels: self.preserve_vowels,
+            shift: self.shift,
+            modifiers: self.modifiers.clone(),
+            milui_spellings,
+            kolel: self.kolel,
+            char_map,
+            recognize_numerals: self.recognize_numerals,
+            strip_abbreviations: self.strip_abbreviations,
+            tokenizer: self.tokenizer.clone(),
+            #[cfg(feature = "normalization")]
+            normalization: self.normalization,
+            mark_stripping: self.mark_stripping,
+            sofit_policy: self.sofit_policy,
+            transliteration: self.transliteration.clone(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_config_defaults() {
+        let config = GematriaContext::default().config();
+
+        assert_eq!(config.method(), GematriaMethod::MisparHechrechi);
+        assert!(!config.cache_enabled());
+        assert_eq!(config.cache_capacity(), None);
+        assert!(!config.preserve_vowels());
+        assert_eq!(config.shift(), 1);
+        assert!(config.modifiers().is_empty());
+        assert!(config.milui_spellings().is_empty());
+        assert_eq!(config.kolel(), None);
+        assert_eq!(config.char_map(), None);
+        assert!(!config.recognize_numerals());
+        assert!(!config.strip_abbreviations());
+        assert_eq!(config.tokenizer(), &Tokenizer::default());
+        assert_eq!(config.mark_stripping(), MarkStripping::default());
+        assert_eq!(config.sofit_policy(), SofitPolicy::Method);
+        assert_eq!(config.transliteration(), None);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_config_round_trip() {
+        let config = GematriaBuilder::new()
+            .with_method(GematriaMethod::Avgad)
+            .with_cache_capacity(64)
+            .with_shift(-2)
+            .with_modifier(Modifier::Modulo(9))
+            .with_milui_tradition(crate::MiluiTradition::Av)
+            .with_kolel(Kolel::Words)
+            .with_char_map(crate::CharMap::from([('א', 2), ('ב', 1)]))
+            .with_numerals(true)
+            .with_abbreviations(true)
+            .with_tokenizer(
+                Tokenizer::new()
+                    .with_maqaf_split(false)
+                    .with_punctuation(crate::Punctuation::Split)
+                    .with_delimiters(['/']),
+            )
+            .with_mark_stripping(MarkStripping::new().with_cantillation(false))
+            .with_sofit_policy(SofitPolicy::Extended)
+            .with_transliteration(Transliteration::new().with_rule("x", "כס"))
+            .init_gematria()
+            .config();
+
+        let json = serde_json::to_string(&config).unwrap();
+        let parsed: GematriaConfig = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed, config);
+        assert_eq!(
+            GematriaBuilder::from(parsed).init_gematria().config(),
+            config
+        );
+    }
+}
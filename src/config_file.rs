@@ -0,0 +1,235 @@
+// Loading gematria systems (alphabet, values, milui spellings and defaults) from TOML or JSON files.
+
+use std::{collections::BTreeMap, fs, io, path::Path};
+
+use serde::Deserialize;
+
+use crate::{
+    CharMap, GematriaBuilder, GematriaCalculation, GematriaMethod, HebrewCharacterMap, Kolel,
+    Modifier,
+};
+
+/// `ConfigFile` describes a user-defined gematria system, loaded from a TOML or JSON file.
+///
+/// All the entries are optional:
+/// - `method`, `cache`, `cache_capacity`, `preserve_vowels`, `shift`, `modifiers` and `kolel`: the defaults of the context.
+/// - `alphabet`: the character to index assignments (1-22 regular letters, 23-27 final forms),
+///   overriding the standard alphabet.
+/// - `values`: custom letter values, used instead of the method (only the listed characters are valued).
+///   Valued characters missing from the alphabet are appended to it, the standard alphabet is not used.
+/// - `milui`: the full spelling of letters used by the milui methods.
+///
+/// Files mapping a character to an index outside of 1 to 27, or two Hebrew letters to the same index,
+/// are refused (see [`HebrewCharacterMap::try_new`]).
+///
+/// # Examples
+///
+/// ```
+/// use gematria_rs::ConfigFile;
+///
+/// let file = ConfigFile::from_toml_str(r#"
+///     method = "OtiyotBeMilui"
+///
+///     [milui]
+///     "ה" = "הי"
+///     "ו" = "ויו"
+/// "#)?;
+///
+/// let gmctx = file.into_builder().init_gematria();
+/// assert_eq!(gmctx.calculate_value("יהוה").value(), 72);
+/// # Ok::<(), std::io::Error>(())
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ConfigFile {
+    // The default calculation method.
+    method: Option<GematriaMethod>,
+
+    // Whether values are cached.
+    cache: bool,
+
+    // The maximum number of cached values, enabling the cache.
+    cache_capacity: Option<usize>,
+
+    // Whether vowels are preserved on results.
+    preserve_vowels: bool,
+
+    // The letter shift used by shift ciphers.
+    shift: Option<i32>,
+
+    // The modifiers applied on the total value of words, in order.
+    modifiers: Vec<Modifier>,
+
+    // The kolel adjustment added to the value of texts.
+    kolel: Option<Kolel>,
+
+    // The character to index assignments.
+    alphabet: BTreeMap<char, u32>,
+
+    // Custom letter values, by character.
+    values: BTreeMap<char, u32>,
+
+    // The full spelling of letters, by letter.
+    milui: BTreeMap<char, String>,
+}
+
+impl ConfigFile {
+    /// Parses a TOML config.
+    pub fn from_toml_str(content: &str) -> io::Result<Self> {
+        let file: Self =
+            toml::from_str(content).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        file.validate()
+    }
+
+    /// Parses a JSON config.
+    pub fn from_json_str(content: &str) -> io::Result<Self> {
+        let file: Self = serde_json::from_str(content)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        file.validate()
+    }
+
+    // Util function to check the letter indices of a parsed config.
+    fn validate(self) -> io::Result<Self> {
+        HebrewCharacterMap::try_new(self.char_map())
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        Ok(self)
+    }
+
+    // Util function to get the character to index assignments of the config,
+    // along with the valued characters missing from the alphabet.
+    fn char_map(&self) -> CharMap {
+        let mut char_map: CharMap = match (self.alphabet.is_empty(), self.values.is_empty()) {
+            (true, true) => HebrewCharacterMap::default().char_map().clone(),
+            _ => self
+                .alphabet
+                .iter()
+                .map(|(&c, &index)| (c, index))
+                .collect(),
+        };
+        // Valued characters missing from the alphabet are appended to it
+        for &c in self.values.keys() {
+            let next = char_map.values().max().copied().unwrap_or_default() + 1;
+            char_map.entry(c).or_insert(next);
+        }
+        char_map
+    }
+
+    /// Loads a config file, parsed as JSON for a `.json` extension and as TOML otherwise.
+    pub fn load<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        let path = path.as_ref();
+        let content = fs::read_to_string(path)?;
+        match path.extension().and_then(|e| e.to_str()) {
+            Some("json") => Self::from_json_str(&content),
+            _ => Self::from_toml_str(&content),
+        }
+    }
+
+    /// Creates a builder configured by the file.
+    pub fn into_builder(self) -> GematriaBuilder {
+        let char_map = self.char_map();
+        let mut builder = GematriaBuilder::new()
+            .with_cache(self.cache)
+            .with_vowels(self.preserve_vowels);
+        if let Some(capacity) = self.cache_capacity {
+            builder = builder.with_cache_capacity(capacity);
+        }
+        if let Some(method) = self.method {
+            builder = builder.with_method(method);
+        }
+        if let Some(shift) = self.shift {
+            builder = builder.with_shift(shift);
+        }
+        for modifier in self.modifiers {
+            builder = builder.with_modifier(modifier);
+        }
+        if let Some(kolel) = self.kolel {
+            builder = builder.with_kolel(kolel);
+        }
+        for (letter, spelling) in &self.milui {
+            builder = builder.with_milui_spelling(*letter, spelling);
+        }
+
+        if !self.values.is_empty() {
+            let values = self
+                .values
+                .iter()
+                .map(|(c, &value)| (char_map[c], value))
+                .collect();
+            builder = builder.with_strategy(Box::new(LetterValues { values }));
+        }
+
+        builder.with_char_map(char_map)
+    }
+}
+
+// Values letters from a table of letter index to value, for systems loaded from files.
+struct LetterValues {
+    values: BTreeMap<u32, u32>,
+}
+
+impl GematriaCalculation for LetterValues {
+    fn calculate_value(&self, letter_index: u32) -> u32 {
+        self.values.get(&letter_index).copied().unwrap_or_default()
+    }
+
+    fn method_type(&self) -> GematriaMethod {
+        GematriaMethod::Custom
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_json_values() {
+        let file = ConfigFile::from_json_str(
+            r#"{"values": {"a": 1, "b": 2, "א": 100}, "kolel": "One", "preserve_vowels": true}"#,
+        )
+        .unwrap();
+        let gmctx = file.into_builder().init_gematria();
+
+        assert_eq!(gmctx.get_current_method(), GematriaMethod::Custom);
+        assert_eq!(gmctx.calculate_value("abא").value(), 104);
+        // Letters without a value are not valued
+        assert_eq!(gmctx.calculate_value("ב").value(), 1);
+    }
+
+    #[test]
+    fn test_invalid_files() {
+        let error = ConfigFile::from_toml_str("colour = 1").unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+        assert!(ConfigFile::from_toml_str("method = \"Unknown\"").is_err());
+        assert!(ConfigFile::load("does-not-exist.toml").is_err());
+
+        // Indices outside of the alphabet, and Hebrew letters sharing an index
+        for content in ["[alphabet]\n\"א\" = 0", "[alphabet]\n\"א\" = 28"] {
+            let error = ConfigFile::from_toml_str(content).unwrap_err();
+            assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+        }
+        let error = ConfigFile::from_json_str(r#"{"alphabet": {"מ": 13, "ם": 13}}"#).unwrap_err();
+        assert!(error.to_string().contains("letter index 13"));
+        let values: String = ('a'..='z').map(|c| format!("\"{}\": 1, ", c)).collect();
+        let content = format!(r#"{{"values": {{{}"α": 1, "β": 2}}}}"#, values);
+        assert!(ConfigFile::from_json_str(&content).is_err());
+    }
+
+    #[test]
+    fn test_toml_alphabet() {
+        let file = ConfigFile::from_toml_str(
+            r#"
+            method = "MisparGadol"
+            modifiers = ["SquareTotal", { Modulo = 1000 }]
+
+            [alphabet]
+            "ם" = 13
+            "ש" = 21
+            "#,
+        )
+        .unwrap();
+        let gmctx = file.into_builder().init_gematria();
+
+        // (300 + 40)² % 1000
+        assert_eq!(gmctx.calculate_value("שלום").value(), 600);
+    }
+}
@@ -0,0 +1,287 @@
+// Corpus checksum utilities: letter and word counts by book and chapter.
+
+use std::collections::BTreeMap;
+
+use crate::{tokenize, HEBREW_LETTERS};
+
+/// `TextCounts` holds the number of letters and words of a text.
+///
+/// Only Hebrew letters are counted (vowels, cantillation marks and punctuation are ignored),
+/// and words are split on whitespace and on the maqaf.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct TextCounts {
+    // The number of Hebrew letters.
+    letters: u64,
+
+    // The number of words.
+    words: u64,
+}
+
+impl TextCounts {
+    /// Creates a new counts object.
+    pub fn new(letters: u64, words: u64) -> Self {
+        TextCounts { letters, words }
+    }
+
+    /// Counts the letters and words of a text.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gematria_rs::TextCounts;
+    ///
+    /// let counts = TextCounts::of("בְּרֵאשִׁ֖ית בָּרָ֣א אֱלֹהִ֑ים אֵ֥ת הַשָּׁמַ֖יִם וְאֵ֥ת הָאָֽרֶץ");
+    /// assert_eq!(counts, TextCounts::new(28, 7));
+    /// ```
+    pub fn of(text: &str) -> Self {
+        let mut counts = TextCounts::default();
+        for word in tokenize(text) {
+            let letters = word.chars().filter(|c| HEBREW_LETTERS.contains(c)).count() as u64;
+            if letters > 0 {
+                counts.letters += letters;
+                counts.words += 1;
+            }
+        }
+
+        counts
+    }
+
+    /// Gets the number of letters.
+    pub fn letters(&self) -> u64 {
+        self.letters
+    }
+
+    /// Gets the number of words.
+    pub fn words(&self) -> u64 {
+        self.words
+    }
+}
+
+impl std::ops::AddAssign for TextCounts {
+    fn add_assign(&mut self, other: Self) {
+        self.letters += other.letters;
+        self.words += other.words;
+    }
+}
+
+/// `ExpectedCounts` is a reference count of a whole book, or of a single chapter of a book.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExpectedCounts {
+    // The book name.
+    book: String,
+
+    // The chapter number, `None` for the whole book.
+    chapter: Option<u32>,
+
+    // The expected counts.
+    counts: TextCounts,
+}
+
+impl ExpectedCounts {
+    /// Creates the reference count of a whole book.
+    pub fn book(book: &str, letters: u64, words: u64) -> Self {
+        ExpectedCounts {
+            book: book.to_string(),
+            chapter: None,
+            counts: TextCounts::new(letters, words),
+        }
+    }
+
+    /// Creates the reference count of a single chapter of a book.
+    pub fn chapter(book: &str, chapter: u32, letters: u64, words: u64) -> Self {
+        ExpectedCounts {
+            book: book.to_string(),
+            chapter: Some(chapter),
+            counts: TextCounts::new(letters, words),
+        }
+    }
+
+    /// Gets the book name.
+    pub fn book_name(&self) -> &str {
+        &self.book
+    }
+
+    /// Gets the chapter number, `None` for a whole book reference.
+    pub fn chapter_number(&self) -> Option<u32> {
+        self.chapter
+    }
+
+    /// Gets the expected counts.
+    pub fn counts(&self) -> TextCounts {
+        self.counts
+    }
+}
+
+/// `CountMismatch` describes a reference count which the corpus does not match.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CountMismatch {
+    // The reference count.
+    expected: ExpectedCounts,
+
+    // The actual counts, `None` when the book or chapter is missing from the corpus.
+    actual: Option<TextCounts>,
+}
+
+impl CountMismatch {
+    /// Gets the reference count.
+    pub fn expected(&self) -> &ExpectedCounts {
+        &self.expected
+    }
+
+    /// Gets the actual counts, `None` when the book or chapter is missing from the corpus.
+    pub fn actual(&self) -> Option<TextCounts> {
+        self.actual
+    }
+}
+
+/// `CorpusCounts` holds the letter and word counts of a corpus by book and chapter,
+/// used to validate that a loaded text is complete before running analyses.
+///
+/// # Examples
+///
+/// ```
+/// use gematria_rs::{CorpusCounts, ExpectedCounts, TextCounts};
+///
+/// let corpus = CorpusCounts::from_verses([
+///     ("Genesis", 1, "בראשית ברא אלהים"),
+///     ("Genesis", 1, "והארץ היתה תהו ובהו"),
+///     ("Genesis", 2, "ויכלו השמים"),
+/// ]);
+///
+/// assert_eq!(corpus.chapter("Genesis", 1), Some(TextCounts::new(30, 7)));
+/// assert_eq!(corpus.book("Genesis"), Some(TextCounts::new(40, 9)));
+///
+/// let mismatches = corpus.verify(&[
+///     ExpectedCounts::chapter("Genesis", 2, 10, 2),
+///     ExpectedCounts::book("Exodus", 63529, 16723),
+/// ]);
+/// assert_eq!(mismatches.len(), 1);
+/// assert_eq!(mismatches[0].expected().book_name(), "Exodus");
+/// assert_eq!(mismatches[0].actual(), None);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CorpusCounts {
+    // The books, in the order they were first added.
+    books: Vec<String>,
+
+    // The counts of every chapter, by book and chapter number.
+    chapters: BTreeMap<(String, u32), TextCounts>,
+}
+
+impl CorpusCounts {
+    /// Creates empty corpus counts.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Counts a corpus of `(book, chapter, verse text)` items.
+    pub fn from_verses<I, B, T>(verses: I) -> Self
+    where
+        I: IntoIterator<Item = (B, u32, T)>,
+        B: AsRef<str>,
+        T: AsRef<str>,
+    {
+        let mut corpus = Self::new();
+        for (book, chapter, text) in verses {
+            corpus.add_verse(book.as_ref(), chapter, text.as_ref());
+        }
+
+        corpus
+    }
+
+    /// Adds the counts of a verse to its book and chapter.
+    pub fn add_verse(&mut self, book: &str, chapter: u32, text: &str) {
+        if !self.books.iter().any(|b| b == book) {
+            self.books.push(book.to_string());
+        }
+        *self
+            .chapters
+            .entry((book.to_string(), chapter))
+            .or_default() += TextCounts::of(text);
+    }
+
+    /// Gets the books of the corpus, in the order they were first added.
+    pub fn books(&self) -> &[String] {
+        &self.books
+    }
+
+    /// Gets the counts of a whole book.
+    pub fn book(&self, book: &str) -> Option<TextCounts> {
+        let mut chapters = self
+            .chapters
+            .iter()
+            .filter(|((b, _), _)| b == book)
+            .peekable();
+        chapters.peek()?;
+
+        Some(
+            chapters.fold(TextCounts::default(), |mut total, (_, &counts)| {
+                total += counts;
+                total
+            }),
+        )
+    }
+
+    /// Gets the counts of a single chapter of a book.
+    pub fn chapter(&self, book: &str, chapter: u32) -> Option<TextCounts> {
+        self.chapters.get(&(book.to_string(), chapter)).copied()
+    }
+
+    /// Gets the counts of the whole corpus.
+    pub fn total(&self) -> TextCounts {
+        self.chapters
+            .values()
+            .fold(TextCounts::default(), |mut total, &counts| {
+                total += counts;
+                total
+            })
+    }
+
+    /// Compares the corpus against reference counts, returning the references which do not match.
+    pub fn verify(&self, expected: &[ExpectedCounts]) -> Vec<CountMismatch> {
+        expected
+            .iter()
+            .filter_map(|reference| {
+                let actual = match reference.chapter {
+                    Some(chapter) => self.chapter(&reference.book, chapter),
+                    None => self.book(&reference.book),
+                };
+                if actual == Some(reference.counts) {
+                    None
+                } else {
+                    Some(CountMismatch {
+                        expected: reference.clone(),
+                        actual,
+                    })
+                }
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_text_counts_ignore_marks() {
+        // Maqaf joined words are counted separately, punctuation alone is not a word
+        assert_eq!(TextCounts::of("וַֽיְהִי־אֹֽור ׃"), TextCounts::new(7, 2));
+        assert_eq!(TextCounts::of(""), TextCounts::default());
+    }
+
+    #[test]
+    fn test_verify_reports_wrong_counts() {
+        let corpus = CorpusCounts::from_verses(vec![("Genesis", 1, "בראשית ברא")]);
+        let mismatches = corpus.verify(&[
+            ExpectedCounts::chapter("Genesis", 1, 9, 2),
+            ExpectedCounts::book("Genesis", 10, 2),
+        ]);
+
+        assert_eq!(mismatches.len(), 1);
+        assert_eq!(mismatches[0].expected().chapter_number(), None);
+        assert_eq!(mismatches[0].actual(), Some(TextCounts::new(9, 2)));
+        assert_eq!(corpus.total(), TextCounts::new(9, 2));
+        assert_eq!(corpus.books(), ["Genesis".to_string()]);
+    }
+}
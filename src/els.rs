@@ -0,0 +1,499 @@
+// Equidistant letter sequences (ELS) and their display as letter grids.
+
+use crate::{
+    limits::LimitGuard, methods::base_letter_index, tokenize, CancellationToken, GematriaError,
+    Limited, NoProgress, ProgressSink, SearchLimits, HEBREW_LETTERS,
+};
+
+/// `ElsHit` describes an equidistant letter sequence within the letter stream of a text,
+/// the letters of the text without spaces, punctuation and vowels.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ElsHit {
+    // The position of the first letter in the letter stream (0 based).
+    start: usize,
+
+    // The distance between the letters, negative for sequences read backwards.
+    skip: i64,
+
+    // The number of letters in the sequence.
+    length: usize,
+}
+
+impl ElsHit {
+    /// Creates a new hit object.
+    pub fn new(start: usize, skip: i64, length: usize) -> Self {
+        ElsHit {
+            start,
+            skip,
+            length,
+        }
+    }
+
+    /// Gets the position of the first letter in the letter stream.
+    pub fn start(&self) -> usize {
+        self.start
+    }
+
+    /// Gets the distance between the letters of the sequence.
+    pub fn skip(&self) -> i64 {
+        self.skip
+    }
+
+    /// Gets the number of letters in the sequence.
+    pub fn length(&self) -> usize {
+        self.length
+    }
+
+    /// Gets the positions of the letters of the sequence in the letter stream, in reading order.
+    ///
+    /// Positions falling before the start of the stream are omitted.
+    pub fn positions(&self) -> Vec<usize> {
+        (0..self.length as i64)
+            .filter_map(|i| usize::try_from(self.start as i64 + i * self.skip).ok())
+            .collect()
+    }
+}
+
+/// `ElsSearch` searches the letter stream of a text for a word spelled at equidistant skips.
+///
+/// Skips range from `min_skip` to `max_skip` (by default from 1 to the largest skip fitting the text),
+/// and sequences are read forwards, backwards (negative skips) or both. Final forms match their
+/// regular letters, and targets of less than two letters have no hits.
+///
+/// Hits are ordered by skip, then by reading direction (forwards first) and then by start position.
+///
+/// # Examples
+///
+/// ```
+/// use gematria_rs::{ElsHit, ElsSearch};
+///
+/// let found = ElsSearch::new()
+///     .with_min_skip(2)
+///     .search("אבגדהוגזא", "אגה");
+///
+/// assert_eq!(found.items(), [ElsHit::new(0, 2, 3), ElsHit::new(8, -2, 3)]);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ElsSearch {
+    // The smallest searched skip.
+    min_skip: usize,
+
+    // The largest searched skip, `None` for the largest skip fitting the text.
+    max_skip: Option<usize>,
+
+    // Whether sequences are read forwards.
+    forward: bool,
+
+    // Whether sequences are read backwards.
+    backward: bool,
+
+    // The limits bounding the search.
+    limits: SearchLimits,
+}
+
+impl Default for ElsSearch {
+    fn default() -> Self {
+        ElsSearch {
+            min_skip: 1,
+            max_skip: None,
+            forward: true,
+            backward: true,
+            limits: SearchLimits::default(),
+        }
+    }
+}
+
+impl ElsSearch {
+    /// Creates a search of every skip, in both directions.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the smallest searched skip, a skip of 0 is treated as 1.
+    pub fn with_min_skip(mut self, min_skip: usize) -> Self {
+        self.min_skip = min_skip.max(1);
+        self
+    }
+
+    /// Sets the largest searched skip.
+    pub fn with_max_skip(mut self, max_skip: usize) -> Self {
+        self.max_skip = Some(max_skip);
+        self
+    }
+
+    /// Sets whether sequences are read forwards.
+    pub fn with_forward(mut self, forward: bool) -> Self {
+        self.forward = forward;
+        self
+    }
+
+    /// Sets whether sequences are read backwards.
+    pub fn with_backward(mut self, backward: bool) -> Self {
+        self.backward = backward;
+        self
+    }
+
+    /// Sets the limits bounding the search, the results are then flagged as truncated.
+    pub fn with_limits(mut self, limits: SearchLimits) -> Self {
+        self.limits = limits;
+        self
+    }
+
+    /// Searches a text for the target word, returning the hits found within the limits.
+    pub fn search(&self, text: &str, target: &str) -> Limited<ElsHit> {
+        self.search_with_progress(text, target, &mut NoProgress, &CancellationToken::new())
+            .expect("a search without cancellation")
+    }
+
+    /// Same as [`ElsSearch::search`], reporting progress (in searched skips) to a [`ProgressSink`]
+    /// and aborting with a [`GematriaError::Cancelled`] error once the [`CancellationToken`] is cancelled.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gematria_rs::{CancellationToken, ElsSearch};
+    ///
+    /// let mut processed = (0, 0);
+    /// let found = ElsSearch::new().with_max_skip(3).search_with_progress(
+    ///     "אבגדהוגזא",
+    ///     "אגה",
+    ///     &mut |skips, total| processed = (skips, total),
+    ///     &CancellationToken::new(),
+    /// )?;
+    ///
+    /// assert_eq!(processed, (3, 3));
+    /// assert_eq!(found.items().len(), 2);
+    /// # Ok::<(), gematria_rs::Error>(())
+    /// ```
+    pub fn search_with_progress(
+        &self,
+        text: &str,
+        target: &str,
+        progress: &mut dyn ProgressSink,
+        cancel: &CancellationToken,
+    ) -> Result<Limited<ElsHit>, GematriaError> {
+        let stream: Vec<u32> = letter_stream(text)
+            .iter()
+            .filter_map(|&(c, ..)| base_letter(c))
+            .collect();
+        let target: Vec<u32> = target.chars().filter_map(base_letter).collect();
+        let mut hits = Vec::new();
+        if target.len() < 2 || stream.len() < target.len() {
+            return Ok(Limited::new(hits, false));
+        }
+
+        let span = target.len() - 1;
+        let largest = (stream.len() - 1) / span;
+        let max_skip = self.max_skip.map_or(largest, |max| max.min(largest));
+        let reversed: Vec<u32> = target.iter().rev().copied().collect();
+        let guard = LimitGuard::start(&self.limits);
+        let total = (max_skip + 1).saturating_sub(self.min_skip);
+        for (searched, skip) in (self.min_skip..=max_skip).enumerate() {
+            if cancel.is_cancelled() {
+                return Err(GematriaError::Cancelled);
+            }
+            let directions = [(self.forward, &target, 1), (self.backward, &reversed, -1)];
+            for (enabled, letters, sign) in directions {
+                if !enabled {
+                    continue;
+                }
+                for first in 0..stream.len() - span * skip {
+                    if guard.exceeded(hits.len()) {
+                        return Ok(Limited::new(hits, true));
+                    }
+                    if !letters
+                        .iter()
+                        .enumerate()
+                        .all(|(i, &letter)| stream[first + i * skip] == letter)
+                    {
+                        continue;
+                    }
+                    // Backward sequences start at their last position in the stream
+                    let hit = if sign > 0 {
+                        ElsHit::new(first, skip as i64, target.len())
+                    } else {
+                        ElsHit::new(first + span * skip, -(skip as i64), target.len())
+                    };
+                    hits.push(hit);
+                }
+            }
+            progress.on_progress(searched + 1, total);
+        }
+
+        Ok(Limited::new(hits, false))
+    }
+}
+
+// Util function to get the regular letter index (1-22) of a Hebrew letter, final forms included.
+fn base_letter(c: char) -> Option<u32> {
+    HEBREW_LETTERS
+        .iter()
+        .position(|&l| l == c)
+        .map(|i| base_letter_index(i as u32 + 1))
+}
+
+/// A single letter of a [`LetterGrid`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GridCell {
+    // The letter.
+    letter: char,
+
+    // The position of the letter in the letter stream.
+    position: usize,
+
+    // The index of the word the letter belongs to.
+    word_index: usize,
+
+    // Whether the letter starts its word.
+    word_start: bool,
+
+    // Whether the letter is part of the hit.
+    marked: bool,
+}
+
+impl GridCell {
+    /// Gets the letter.
+    pub fn letter(&self) -> char {
+        self.letter
+    }
+
+    /// Gets the position of the letter in the letter stream.
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    /// Gets the index of the word (within the text) the letter belongs to.
+    pub fn word_index(&self) -> usize {
+        self.word_index
+    }
+
+    /// Whether the letter is the first letter of its word.
+    pub fn is_word_start(&self) -> bool {
+        self.word_start
+    }
+
+    /// Whether the letter is part of the displayed hit.
+    pub fn is_marked(&self) -> bool {
+        self.marked
+    }
+}
+
+/// `LetterGrid` lays the letter stream surrounding an [`ElsHit`] out in rows of a fixed width,
+/// marking the letters of the hit, which is how ELS results are conventionally presented.
+///
+/// Rows are aligned to the start of the letter stream, so a row width equal to the skip
+/// displays the hit as a column. Every cell keeps the word it belongs to, so word boundaries
+/// of the original text remain visible.
+///
+/// # Examples
+///
+/// ```
+/// use gematria_rs::{ElsHit, LetterGrid};
+///
+/// let hit = ElsHit::new(0, 3, 3);
+/// let grid = LetterGrid::new("אבג דהו זחט", &hit, 3, 0);
+///
+/// assert_eq!(grid.render_text(), "[א] ב  ג\n[ד] ה  ו\n[ז] ח  ט\n");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LetterGrid {
+    // The number of letters in every row.
+    width: usize,
+
+    // The displayed letters, row by row.
+    cells: Vec<GridCell>,
+}
+
+impl LetterGrid {
+    /// Creates the grid of the letters surrounding a hit, with `margin_rows` extra rows
+    /// above and below the hit. A width of 0 is treated as 1.
+    pub fn new(text: &str, hit: &ElsHit, width: usize, margin_rows: usize) -> Self {
+        let width = width.max(1);
+        let letters = letter_stream(text);
+        let positions = hit.positions();
+
+        let first_row = positions
+            .iter()
+            .min()
+            .map_or(0, |p| (p / width).saturating_sub(margin_rows));
+        let last_row = positions
+            .iter()
+            .max()
+            .map_or(0, |p| p / width + margin_rows);
+        let end = ((last_row + 1) * width).min(letters.len());
+
+        let cells = letters
+            .get(first_row * width..end)
+            .unwrap_or_default()
+            .iter()
+            .map(|&(letter, position, word_index, word_start)| GridCell {
+                letter,
+                position,
+                word_index,
+                word_start,
+                marked: positions.contains(&position),
+            })
+            .collect();
+
+        LetterGrid { width, cells }
+    }
+
+    /// Gets the number of letters in every row.
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Gets the rows of the grid, the last row may be shorter.
+    pub fn rows(&self) -> impl Iterator<Item = &[GridCell]> {
+        self.cells.chunks(self.width)
+    }
+
+    /// Renders the grid for a terminal, marking the letters of the hit with brackets.
+    pub fn render_text(&self) -> String {
+        self.rows()
+            .map(|row| {
+                let line: String = row
+                    .iter()
+                    .map(|cell| {
+                        if cell.marked {
+                            format!("[{}]", cell.letter)
+                        } else {
+                            format!(" {} ", cell.letter)
+                        }
+                    })
+                    .collect();
+                format!("{}\n", line.trim_end())
+            })
+            .collect()
+    }
+
+    /// Renders the grid as an HTML table, the letters of the hit have the `hit` class
+    /// and the first letters of words the `word-start` class.
+    pub fn render_html(&self) -> String {
+        let mut output = String::from("<table class=\"els-grid\">\n");
+        for row in self.rows() {
+            output.push_str("<tr>");
+            for cell in row {
+                let classes: Vec<&str> = [(cell.marked, "hit"), (cell.word_start, "word-start")]
+                    .iter()
+                    .filter(|(applies, _)| *applies)
+                    .map(|(_, class)| *class)
+                    .collect();
+                if classes.is_empty() {
+                    output.push_str(&format!("<td>{}</td>", cell.letter));
+                } else {
+                    output.push_str(&format!(
+                        "<td class=\"{}\">{}</td>",
+                        classes.join(" "),
+                        cell.letter
+                    ));
+                }
+            }
+            output.push_str("</tr>\n");
+        }
+        output.push_str("</table>\n");
+
+        output
+    }
+}
+
+// Util function to get the Hebrew letters of a text, with their position, word index and
+// whether they start their word.
+fn letter_stream(text: &str) -> Vec<(char, usize, usize, bool)> {
+    let mut letters = Vec::new();
+    for (word_index, word) in tokenize(text).enumerate() {
+        let mut word_start = true;
+        for c in word.chars().filter(|c| HEBREW_LETTERS.contains(c)) {
+            letters.push((c, letters.len(), word_index, word_start));
+            word_start = false;
+        }
+    }
+
+    letters
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hit_positions() {
+        assert_eq!(ElsHit::new(2, 5, 3).positions(), vec![2, 7, 12]);
+        assert_eq!(ElsHit::new(10, -4, 3).positions(), vec![10, 6, 2]);
+    }
+
+    #[test]
+    fn test_els_search() {
+        // Letters: בראשית ברא אלהים
+        let text = "בְּרֵאשִׁית בָּרָא אֱלֹהִים";
+        let found = ElsSearch::new().search(text, "יבא");
+        assert_eq!(found.items(), [ElsHit::new(4, 2, 3)]);
+
+        // Final forms match regular letters, backward hits start from their first read letter
+        let found = ElsSearch::new().with_forward(false).search("מ א כ", "ךאם");
+        assert_eq!(found.items(), [ElsHit::new(2, -1, 3)]);
+        assert_eq!(found.items()[0].positions(), vec![2, 1, 0]);
+
+        let found = ElsSearch::new().with_max_skip(1).search("אבאבאב", "אב");
+        assert_eq!(found.items().len(), 5);
+        let limited = ElsSearch::new()
+            .with_limits(SearchLimits::new().with_max_results(2))
+            .search("אבאבאב", "אב");
+        assert_eq!(limited.items().len(), 2);
+        assert!(limited.is_truncated());
+        assert!(ElsSearch::new().search(text, "א").items().is_empty());
+    }
+
+    #[test]
+    fn test_els_search_cancelled_midway() {
+        let text = "אבגדהוזחטי".repeat(20);
+        let cancel = CancellationToken::new();
+        let mut reports = Vec::new();
+        let mut sink = |skips, total| {
+            reports.push((skips, total));
+            if skips == 2 {
+                cancel.cancel();
+            }
+        };
+        let search = ElsSearch::new().with_max_skip(10);
+        let result = search.search_with_progress(&text, "אג", &mut sink, &cancel);
+
+        assert_eq!(result, Err(GematriaError::Cancelled));
+        assert_eq!(reports, [(1, 10), (2, 10)]);
+        let found = search
+            .search_with_progress(&text, "אג", &mut NoProgress, &CancellationToken::new())
+            .unwrap();
+        assert_eq!(found, search.search(&text, "אג"));
+    }
+
+    #[test]
+    fn test_grid_margins_and_words() {
+        // Letters: בראשית ברא אלהים
+        let text = "בְּרֵאשִׁית בָּרָא אֱלֹהִים";
+        let hit = ElsHit::new(6, -2, 2);
+        let grid = LetterGrid::new(text, &hit, 4, 1);
+
+        let rows: Vec<String> = grid
+            .rows()
+            .map(|row| row.iter().map(GridCell::letter).collect())
+            .collect();
+        assert_eq!(rows, vec!["בראש", "יתבר", "אאלה"]);
+        assert_eq!(
+            grid.render_text(),
+            " ב  ר  א  ש\n[י] ת [ב] ר\n א  א  ל  ה\n"
+        );
+
+        let cell = grid.rows().nth(1).unwrap()[2];
+        assert!(cell.is_word_start() && cell.is_marked());
+        assert_eq!(cell.word_index(), 1);
+    }
+
+    #[test]
+    fn test_grid_html() {
+        let grid = LetterGrid::new("אב גד", &ElsHit::new(1, 1, 2), 2, 0);
+        assert_eq!(
+            grid.render_html(),
+            "<table class=\"els-grid\">\n<tr><td class=\"word-start\">א</td><td class=\"hit\">ב</td></tr>\n<tr><td class=\"hit word-start\">ג</td><td>ד</td></tr>\n</table>\n"
+        );
+    }
+}
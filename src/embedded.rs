@@ -0,0 +1,166 @@
+//! Table driven, allocation free gematria calculations, for hot paths and memory constrained processes.
+//!
+//! Everything in this module is backed by `const` tables: no `HashMap`, no heap allocation and no
+//! `String` returning APIs, so the module itself only uses `core`. The rest of the crate requires `std`,
+//! so it does not build for `no_std` targets such as microcontrollers.
+//! It is enabled by the `embedded` feature, use the `embedded` cargo profile
+//! (`cargo build --lib --profile embedded --no-default-features --features embedded`) for a size optimized
+//! build without the command line interface.
+//!
+//! # Memory budget
+//!
+//! | item                        | size      |
+//! |-----------------------------|-----------|
+//! | [`HECHRECHI`] table         | 54 bytes  |
+//! | [`GADOL`] table             | 54 bytes  |
+//! | [`KATAN`] table             | 27 bytes  |
+//! | heap usage                  | 0 bytes   |
+//! | stack usage of [`word_value`] | O(1)    |
+//!
+//! The supported methods are [`GematriaMethod::MisparHechrechi`], [`GematriaMethod::MisparGadol`]
+//! and [`GematriaMethod::MisparKatan`], other methods return `None`.
+//!
+//! # Examples
+//!
+//! ```
+//! use gematria_rs::{embedded, GematriaMethod};
+//!
+//! let value = embedded::word_value("שלום", GematriaMethod::MisparHechrechi).unwrap();
+//!
+//! let mut buf = [0u8; embedded::MAX_VALUE_DIGITS];
+//! assert_eq!(embedded::format_value(value, &mut buf), "376");
+//! ```
+
+use crate::GematriaMethod;
+
+/// Standard (Mispar Hechrechi) values, by letter index - 1 (final forms are 23-27).
+pub const HECHRECHI: [u16; 27] = [
+    1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 20, 30, 40, 50, 60, 70, 80, 90, 100, 200, 300,
+    400, // Final forms
+    20, 40, 50, 80, 90,
+];
+
+/// Mispar Gadol values, by letter index - 1 (final forms are 23-27).
+pub const GADOL: [u16; 27] = [
+    1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 20, 30, 40, 50, 60, 70, 80, 90, 100, 200, 300,
+    400, // Final forms
+    500, 600, 700, 800, 900,
+];
+
+/// Mispar Katan values, by letter index - 1 (final forms are 23-27).
+pub const KATAN: [u8; 27] = [
+    1, 2, 3, 4, 5, 6, 7, 8, 9, 1, 2, 3, 4, 5, 6, 7, 8, 9, 1, 2, 3, 4, // Final forms
+    5, 6, 7, 8, 9,
+];
+
+/// The maximum number of digits of a `u64` value, used to size [`format_value`] buffers.
+pub const MAX_VALUE_DIGITS: usize = 20;
+
+/// Gets the 1 based letter index of a Hebrew letter (final forms are 23-27).
+pub const fn letter_index(c: char) -> Option<u8> {
+    let index = match c {
+        'א' => 1,
+        'ב' => 2,
+        'ג' => 3,
+        'ד' => 4,
+        'ה' => 5,
+        'ו' => 6,
+        'ז' => 7,
+        'ח' => 8,
+        'ט' => 9,
+        'י' => 10,
+        'כ' => 11,
+        'ל' => 12,
+        'מ' => 13,
+        'נ' => 14,
+        'ס' => 15,
+        'ע' => 16,
+        'פ' => 17,
+        'צ' => 18,
+        'ק' => 19,
+        'ר' => 20,
+        'ש' => 21,
+        'ת' => 22,
+        'ך' => 23,
+        'ם' => 24,
+        'ן' => 25,
+        'ף' => 26,
+        'ץ' => 27,
+        _ => return None,
+    };
+    Some(index)
+}
+
+/// Gets the value of a single letter, `Some(0)` for non Hebrew characters and `None` for unsupported methods.
+pub const fn letter_value(c: char, method: GematriaMethod) -> Option<u32> {
+    let index = match letter_index(c) {
+        Some(index) => index as usize - 1,
+        None => return Some(0),
+    };
+    match method {
+        GematriaMethod::MisparHechrechi => Some(HECHRECHI[index] as u32),
+        GematriaMethod::MisparGadol => Some(GADOL[index] as u32),
+        GematriaMethod::MisparKatan => Some(KATAN[index] as u32),
+        _ => None,
+    }
+}
+
+/// Calculates the value of a word without allocating, ignoring non Hebrew characters.
+pub fn word_value(word: &str, method: GematriaMethod) -> Option<u64> {
+    word.chars().try_fold(0u64, |total, c| {
+        Some(total + letter_value(c, method)? as u64)
+    })
+}
+
+/// Formats a value into the given buffer without allocating, returning the written digits.
+pub fn format_value(mut value: u64, buf: &mut [u8; MAX_VALUE_DIGITS]) -> &str {
+    let mut start = MAX_VALUE_DIGITS;
+    loop {
+        start -= 1;
+        buf[start] = b'0' + (value % 10) as u8;
+        value /= 10;
+        if value == 0 {
+            break;
+        }
+    }
+
+    // Only ASCII digits were written
+    core::str::from_utf8(&buf[start..]).unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::GematriaBuilder;
+
+    #[test]
+    fn test_tables_match_context() {
+        for method in [
+            GematriaMethod::MisparHechrechi,
+            GematriaMethod::MisparGadol,
+            GematriaMethod::MisparKatan,
+        ] {
+            let gmctx = GematriaBuilder::new().with_method(method).init_gematria();
+            for c in crate::HEBREW_LETTERS {
+                assert_eq!(
+                    letter_value(c, method).unwrap() as u64,
+                    gmctx.calculate_char_value(c)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_word_value() {
+        assert_eq!(
+            word_value("בעזרת השם", GematriaMethod::MisparHechrechi),
+            Some(1024)
+        );
+        assert_eq!(word_value("שלום", GematriaMethod::OtiyotBeMilui), None);
+        assert_eq!(format_value(0, &mut [0; MAX_VALUE_DIGITS]), "0");
+        assert_eq!(
+            format_value(u64::MAX, &mut [0; MAX_VALUE_DIGITS]),
+            "18446744073709551615"
+        );
+    }
+}
@@ -0,0 +1,154 @@
+// English gematria: the Latin alphabet and its ordinal, English Qabalah and Agrippa values.
+
+use crate::alphabet::is_combining_mark;
+use crate::{AlphabetSystem, CharMap, GematriaCalculation, GematriaMethod};
+
+/// The values of the letters A to Z under English Qabalah (the ALW cipher),
+/// where every eleventh letter of the alphabet is valued in turn (A = 1, L = 2, W = 3...).
+const ENGLISH_QABALAH: [u32; 26] = [
+    1, 20, 13, 6, 25, 18, 11, 4, 23, 16, 9, 2, 21, 14, 7, 26, 19, 12, 5, 24, 17, 10, 3, 22, 15, 8,
+];
+
+/// The values of the letters A to Z under the Agrippa key, following the Latin isopsephy of Agrippa
+/// (A = 1 to T = 100) and valuing J, V and W as 600, 700 and 900.
+const AGRIPPA: [u32; 26] = [
+    1, 2, 3, 4, 5, 6, 7, 8, 9, 600, 10, 20, 30, 40, 50, 60, 70, 80, 90, 100, 200, 700, 900, 300,
+    400, 500,
+];
+
+/// Builds the character map of the English alphabet, mapping the letters A to Z
+/// (in upper and lower case) to their index (1 based).
+///
+/// These are the letters of the [`EnglishAlphabet`].
+///
+/// # Examples
+///
+/// ```
+/// use gematria_rs::{english_char_map, GematriaBuilder, GematriaMethod};
+///
+/// assert_eq!(english_char_map()[&'c'], 3);
+///
+/// let gmctx = GematriaBuilder::new()
+///     .with_method(GematriaMethod::EnglishOrdinal)
+///     .init_gematria();
+/// assert_eq!(gmctx.calculate_value("Gematria").value(), 74);
+/// ```
+pub fn english_char_map() -> CharMap {
+    ('A'..='Z')
+        .zip('a'..='z')
+        .zip(1..)
+        .flat_map(|((upper, lower), index)| [(upper, index), (lower, index)])
+        .collect()
+}
+
+/// The English (Latin) alphabet, the letters A to Z in upper and lower case (indices 1 to 26).
+///
+/// Contexts built with an English method ([`GematriaMethod::EnglishOrdinal`], [`GematriaMethod::EnglishQabalah`]
+/// or [`GematriaMethod::Agrippa`]) use this alphabet unless another alphabet or character map is given.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct EnglishAlphabet;
+
+impl AlphabetSystem for EnglishAlphabet {
+    fn name(&self) -> &str {
+        "English"
+    }
+
+    fn letters(&self) -> CharMap {
+        english_char_map()
+    }
+
+    fn is_mark(&self, c: char) -> bool {
+        is_combining_mark(c)
+    }
+}
+
+// Util function to get the value of a letter index from a table of the letters A to Z.
+fn table_value(table: &[u32; 26], letter_index: u32) -> u32 {
+    match letter_index {
+        1..=26 => table[letter_index as usize - 1],
+        _ => 0,
+    }
+}
+
+/// Represents the English ordinal method, where the letters are valued by their position
+/// in the alphabet (A = 1 to Z = 26).
+#[derive(Clone)]
+pub struct EnglishOrdinal;
+
+impl GematriaCalculation for EnglishOrdinal {
+    fn calculate_value(&self, letter_index: u32) -> u32 {
+        match letter_index {
+            1..=26 => letter_index,
+            _ => 0,
+        }
+    }
+
+    fn method_type(&self) -> GematriaMethod {
+        GematriaMethod::EnglishOrdinal
+    }
+}
+
+/// Represents the English Qabalah (ALW cipher) method, where the letters are valued 1 to 26
+/// stepping by eleven letters through the alphabet (A = 1, L = 2, W = 3, H = 4...).
+#[derive(Clone)]
+pub struct EnglishQabalah;
+
+impl GematriaCalculation for EnglishQabalah {
+    fn calculate_value(&self, letter_index: u32) -> u32 {
+        table_value(&ENGLISH_QABALAH, letter_index)
+    }
+
+    fn method_type(&self) -> GematriaMethod {
+        GematriaMethod::EnglishQabalah
+    }
+}
+
+/// Represents the Agrippa method, where the letters are valued as units, tens and hundreds
+/// after Agrippa's Latin key (A = 1 to I = 9, K = 10 to S = 90, T = 100 to Z = 500),
+/// with J = 600, V = 700 and W = 900.
+#[derive(Clone)]
+pub struct Agrippa;
+
+impl GematriaCalculation for Agrippa {
+    fn calculate_value(&self, letter_index: u32) -> u32 {
+        table_value(&AGRIPPA, letter_index)
+    }
+
+    fn method_type(&self) -> GematriaMethod {
+        GematriaMethod::Agrippa
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{GematriaBuilder, IntoGematriaVal};
+
+    #[test]
+    fn test_english_methods() {
+        assert_eq!("Gematria".gematria_val(&GematriaMethod::EnglishOrdinal), 74);
+        assert_eq!(
+            "Gematria".gematria_val(&GematriaMethod::EnglishQabalah),
+            118
+        );
+        assert_eq!("Gematria".gematria_val(&GematriaMethod::Agrippa), 233);
+        assert_eq!('w'.gematria_val(&GematriaMethod::EnglishQabalah), 3);
+
+        // The ALW cipher values every letter once
+        let mut values = ENGLISH_QABALAH;
+        values.sort_unstable();
+        assert_eq!(values.to_vec(), (1..=26).collect::<Vec<u32>>());
+    }
+
+    #[test]
+    fn test_mixed_text() {
+        let gmctx = GematriaBuilder::new()
+            .with_method(GematriaMethod::EnglishOrdinal)
+            .init_gematria();
+        // Hebrew letters are not part of the English alphabet
+        assert_eq!(gmctx.calculate_value("abc שלום").value(), 6);
+        let matches = gmctx.search_matching_values(&6, "abc fa cab שלום");
+        let words: Vec<&str> = matches.iter().map(|m| m.word()).collect();
+        assert_eq!(words, ["abc", "cab"]);
+    }
+}
@@ -0,0 +1,79 @@
+// The error type of fallible gematria operations.
+
+use std::{fmt, io};
+
+use crate::GematriaMethod;
+
+/// Enumerates the errors returned by fallible gematria operations.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GematriaError {
+    /// The text contains characters which are not Hebrew, listed with their position
+    /// (0 based, in characters) within the text.
+    InvalidCharacters(Vec<(usize, char)>),
+    /// The operation was aborted through its [`crate::CancellationToken`].
+    Cancelled,
+    /// The method has no built-in implementation, custom strategies are injected with
+    /// [`crate::GematriaBuilder::with_strategy`] instead.
+    UnsupportedMethod(GematriaMethod),
+    /// The value overflows a `u64`, see [`crate::GematriaContext::calculate_value_checked`].
+    Overflow,
+    /// A character is mapped to an index outside of 1 to 27, see [`crate::HebrewCharacterMap::try_new`].
+    InvalidLetterIndex(char, u32),
+    /// Two Hebrew letters are mapped to the same index, see [`crate::HebrewCharacterMap::try_new`].
+    DuplicateLetterIndex(char, char, u32),
+}
+
+/// The error type of the crate, an alias of [`GematriaError`].
+pub type Error = GematriaError;
+
+impl fmt::Display for GematriaError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GematriaError::InvalidCharacters(characters) => {
+                let listed: Vec<String> = characters
+                    .iter()
+                    .map(|(position, c)| format!("'{}' at {}", c, position))
+                    .collect();
+                write!(f, "invalid characters: {}", listed.join(", "))
+            }
+            GematriaError::Cancelled => f.write_str("the operation was cancelled"),
+            GematriaError::UnsupportedMethod(GematriaMethod::Custom) => f.write_str(
+                "custom strategies are injected with `GematriaBuilder::with_strategy` or `GematriaContext::set_strategy`",
+            ),
+            GematriaError::UnsupportedMethod(method) => write!(
+                f,
+                "{} is not yet implemented to calculate gematria values",
+                method
+            ),
+            GematriaError::Overflow => f.write_str("the value overflows a 64-bit total"),
+            GematriaError::InvalidLetterIndex(c, index) => write!(
+                f,
+                "'{}' is mapped to the letter index {}, outside of 1 to 27",
+                c, index
+            ),
+            GematriaError::DuplicateLetterIndex(first, second, index) => write!(
+                f,
+                "'{}' and '{}' are both mapped to the letter index {}",
+                first, second, index
+            ),
+        }
+    }
+}
+
+impl std::error::Error for GematriaError {}
+
+// Keeps the deprecated `io::Result` APIs working on top of the crate error.
+impl From<GematriaError> for io::Error {
+    fn from(error: GematriaError) -> Self {
+        let kind = match error {
+            GematriaError::InvalidCharacters(_) => io::ErrorKind::InvalidData,
+            GematriaError::Cancelled => io::ErrorKind::Interrupted,
+            GematriaError::UnsupportedMethod(_) => io::ErrorKind::Unsupported,
+            GematriaError::Overflow => io::ErrorKind::InvalidData,
+            GematriaError::InvalidLetterIndex(..) | GematriaError::DuplicateLetterIndex(..) => {
+                io::ErrorKind::InvalidInput
+            }
+        };
+        io::Error::new(kind, error)
+    }
+}
@@ -0,0 +1,289 @@
+// Extraction of the Hebrew words of multilingual texts, reporting the skipped foreign content.
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::ops::Range;
+
+use crate::paleo::is_paleo_letter;
+use crate::presentation::is_presentation_form;
+use crate::{GematriaContext, Match};
+
+/// Enumerates the kinds of foreign (non Hebrew) content skipped when extracting Hebrew words.
+///
+/// Punctuation and other symbols are not foreign content, and are skipped silently.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum ForeignKind {
+    /// Latin letters, including accented letters.
+    Latin,
+    /// Digits of any script.
+    Digit,
+    /// Emoji and pictographs.
+    Emoji,
+    /// Letters of other scripts, such as Greek, Cyrillic or Arabic.
+    OtherScript,
+}
+
+impl ForeignKind {
+    /// Gets the kind of a foreign character, `None` for Hebrew characters, whitespace and punctuation.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gematria_rs::ForeignKind;
+    ///
+    /// assert_eq!(ForeignKind::of('é'), Some(ForeignKind::Latin));
+    /// assert_eq!(ForeignKind::of('7'), Some(ForeignKind::Digit));
+    /// assert_eq!(ForeignKind::of('🙂'), Some(ForeignKind::Emoji));
+    /// assert_eq!(ForeignKind::of('λ'), Some(ForeignKind::OtherScript));
+    /// assert_eq!(ForeignKind::of('א'), None);
+    /// assert_eq!(ForeignKind::of(','), None);
+    /// ```
+    pub fn of(c: char) -> Option<Self> {
+        match c {
+            _ if is_hebrew_char(c) => None,
+            '\u{1F000}'..='\u{1FAFF}' | '\u{2600}'..='\u{27BF}' | '\u{200D}' | '\u{FE0F}' => {
+                Some(ForeignKind::Emoji)
+            }
+            _ if c.is_numeric() => Some(ForeignKind::Digit),
+            'A'..='Z' | 'a'..='z' | '\u{00C0}'..='\u{024F}' | '\u{1E00}'..='\u{1EFF}'
+                if c.is_alphabetic() =>
+            {
+                Some(ForeignKind::Latin)
+            }
+            _ if c.is_alphabetic() => Some(ForeignKind::OtherScript),
+            _ => None,
+        }
+    }
+
+    /// Gets the name of the kind.
+    pub fn name(&self) -> &'static str {
+        match self {
+            ForeignKind::Latin => "latin",
+            ForeignKind::Digit => "digit",
+            ForeignKind::Emoji => "emoji",
+            ForeignKind::OtherScript => "other script",
+        }
+    }
+}
+
+impl fmt::Display for ForeignKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// `SkippedContent` reports the foreign content of a kind skipped when extracting Hebrew words.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SkippedContent {
+    // The kind of the skipped content.
+    kind: ForeignKind,
+
+    // The number of skipped characters.
+    characters: usize,
+
+    // The number of skipped fragments (runs of characters of the kind).
+    fragments: usize,
+
+    // The first distinct skipped fragments, in order of appearance.
+    samples: Vec<String>,
+}
+
+impl SkippedContent {
+    /// Gets the kind of the skipped content.
+    pub fn kind(&self) -> ForeignKind {
+        self.kind
+    }
+
+    /// Gets the number of skipped characters.
+    pub fn characters(&self) -> usize {
+        self.characters
+    }
+
+    /// Gets the number of skipped fragments, runs of characters of the kind such as a Latin word.
+    pub fn fragments(&self) -> usize {
+        self.fragments
+    }
+
+    /// Gets the first distinct skipped fragments, in order of appearance.
+    pub fn samples(&self) -> &[String] {
+        &self.samples
+    }
+}
+
+/// `HebrewExtraction` holds the Hebrew words extracted from a multilingual text,
+/// along with a report of the skipped foreign content.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HebrewExtraction {
+    // The extracted Hebrew words, with their values and byte ranges.
+    words: Vec<Match>,
+
+    // The skipped foreign content, by kind.
+    skipped: Vec<SkippedContent>,
+}
+
+impl HebrewExtraction {
+    /// Gets the extracted Hebrew words, indexed in order of appearance,
+    /// with their values and byte ranges within the text.
+    pub fn words(&self) -> &[Match] {
+        &self.words
+    }
+
+    /// Gets the skipped foreign content, a report for every kind found, ordered by kind.
+    pub fn skipped(&self) -> &[SkippedContent] {
+        &self.skipped
+    }
+
+    /// Gets the skipped content of a kind, if any was found.
+    pub fn skipped_of(&self, kind: ForeignKind) -> Option<&SkippedContent> {
+        self.skipped.iter().find(|skipped| skipped.kind == kind)
+    }
+
+    /// Gets the total number of skipped foreign characters.
+    pub fn skipped_characters(&self) -> usize {
+        self.skipped.iter().map(SkippedContent::characters).sum()
+    }
+}
+
+// Util function to check whether a character belongs to the Hebrew block, is a Hebrew presentation form
+// or a Paleo-Hebrew letter.
+fn is_hebrew_char(c: char) -> bool {
+    matches!(c, '\u{0590}'..='\u{05FF}') || is_presentation_form(c) || is_paleo_letter(c)
+}
+
+// Util function to split a word into its Hebrew and non Hebrew runs, along with their byte ranges.
+fn split_runs(word: &str) -> Vec<(bool, Range<usize>)> {
+    let mut runs: Vec<(bool, Range<usize>)> = Vec::new();
+    for (i, c) in word.char_indices() {
+        let hebrew = is_hebrew_char(c);
+        let end = i + c.len_utf8();
+        match runs.last_mut() {
+            Some((last, range)) if *last == hebrew => range.end = end,
+            _ => runs.push((hebrew, i..end)),
+        }
+    }
+    runs
+}
+
+// Util function to split a foreign run into runs of a single kind, skipping punctuation and symbols.
+fn foreign_runs(fragment: &str) -> Vec<(ForeignKind, &str)> {
+    let mut runs: Vec<(ForeignKind, Range<usize>)> = Vec::new();
+    for (i, c) in fragment.char_indices() {
+        let Some(kind) = ForeignKind::of(c) else {
+            continue;
+        };
+        let end = i + c.len_utf8();
+        match runs.last_mut() {
+            Some((last, range)) if *last == kind && range.end == i => range.end = end,
+            _ => runs.push((kind, i..end)),
+        }
+    }
+    runs.into_iter()
+        .map(|(kind, range)| (kind, &fragment[range]))
+        .collect()
+}
+
+impl GematriaContext {
+    /// Extracts the Hebrew words of a multilingual text and calculates their values,
+    /// reporting the skipped foreign content (Latin letters, digits, emoji and other scripts)
+    /// with up to `max_samples` samples of every kind.
+    ///
+    /// Words mixing Hebrew and foreign characters (such as `שלום123`) keep their Hebrew part.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gematria_rs::{ForeignKind, GematriaContext};
+    ///
+    /// let gmctx = GematriaContext::default();
+    /// let extraction = gmctx.extract_hebrew("Hello שלום world, 2024 עולם🙂", 5);
+    ///
+    /// let words: Vec<&str> = extraction.words().iter().map(|m| m.word()).collect();
+    /// assert_eq!(words, ["שלום", "עולם"]);
+    ///
+    /// let latin = extraction.skipped_of(ForeignKind::Latin).unwrap();
+    /// assert_eq!((latin.characters(), latin.fragments()), (10, 2));
+    /// assert_eq!(latin.samples(), ["Hello", "world"]);
+    /// assert_eq!(extraction.skipped_characters(), 15);
+    /// ```
+    pub fn extract_hebrew(&self, text: &str, max_samples: usize) -> HebrewExtraction {
+        let mut words = Vec::new();
+        let mut skipped: BTreeMap<ForeignKind, SkippedContent> = BTreeMap::new();
+
+        for (range, word) in self.tokenizer.tokenize_with_ranges(text) {
+            for (hebrew, run) in split_runs(word) {
+                let start = range.start + run.start;
+                let fragment = &word[run];
+                if hebrew {
+                    if self
+                        .get_indices_for_word(&self.normalize(fragment))
+                        .is_empty()
+                    {
+                        continue;
+                    }
+                    let result = self.calculate_value(fragment);
+                    words.push(
+                        Match::new(result.word().to_string(), result.value(), words.len())
+                            .with_byte_range(start..start + fragment.len()),
+                    );
+                    continue;
+                }
+
+                // Foreign runs are split further into runs of a single kind
+                for (kind, run) in foreign_runs(fragment) {
+                    let report = skipped.entry(kind).or_insert_with(|| SkippedContent {
+                        kind,
+                        characters: 0,
+                        fragments: 0,
+                        samples: Vec::new(),
+                    });
+                    report.characters += run.chars().count();
+                    report.fragments += 1;
+                    if report.samples.len() < max_samples
+                        && !report.samples.iter().any(|s| s == run)
+                    {
+                        report.samples.push(run.to_string());
+                    }
+                }
+            }
+        }
+
+        HebrewExtraction {
+            words,
+            skipped: skipped.into_values().collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mixed_words() {
+        let gmctx = GematriaContext::default();
+        let text = "שלום123 abc-אב Ωμέγα 👍🏽 ...";
+        let extraction = gmctx.extract_hebrew(text, 1);
+
+        let words: Vec<(&str, u64, &str)> = extraction
+            .words()
+            .iter()
+            .map(|m| (m.word(), m.value(), &text[m.byte_range()]))
+            .collect();
+        assert_eq!(words, [("שלום", 376, "שלום"), ("אב", 3, "אב")]);
+
+        let report: Vec<(ForeignKind, usize, usize, &[String])> = extraction
+            .skipped()
+            .iter()
+            .map(|s| (s.kind(), s.characters(), s.fragments(), s.samples()))
+            .collect();
+        assert_eq!(
+            report,
+            [
+                (ForeignKind::Latin, 3, 1, &["abc".to_string()][..]),
+                (ForeignKind::Digit, 3, 1, &["123".to_string()][..]),
+                (ForeignKind::Emoji, 2, 1, &["👍🏽".to_string()][..]),
+                (ForeignKind::OtherScript, 5, 1, &["Ωμέγα".to_string()][..]),
+            ]
+        );
+    }
+}
@@ -0,0 +1,159 @@
+// Letter-pair ("two-letter gates") analysis, following the 231 gates of Sefer Yetzirah.
+
+use std::collections::HashMap;
+
+use crate::{methods::base_letter_index, GematriaContext, HEBREW_LETTERS};
+
+/// `Gate` is one of the 231 two-letter gates: an unordered pair of two distinct letters.
+///
+/// Gates are normalized, the letters are kept in alphabet order and final forms are
+/// treated as their regular letters, so `Gate::new('ב', 'א')` and `Gate::new('א', 'ב')` are the same gate.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Gate {
+    // The letter indices (1-22) of the gate, in alphabet order.
+    first: u32,
+    second: u32,
+}
+
+impl Gate {
+    /// Creates a gate from two letters, returning `None` for non Hebrew letters or a repeated letter.
+    pub fn new(a: char, b: char) -> Option<Self> {
+        let a = letter_index(a)?;
+        let b = letter_index(b)?;
+        match a.cmp(&b) {
+            std::cmp::Ordering::Less => Some(Gate {
+                first: a,
+                second: b,
+            }),
+            std::cmp::Ordering::Greater => Some(Gate {
+                first: b,
+                second: a,
+            }),
+            std::cmp::Ordering::Equal => None,
+        }
+    }
+
+    /// Gets the first letter of the gate (in alphabet order).
+    pub fn first(&self) -> char {
+        HEBREW_LETTERS[self.first as usize - 1]
+    }
+
+    /// Gets the second letter of the gate (in alphabet order).
+    pub fn second(&self) -> char {
+        HEBREW_LETTERS[self.second as usize - 1]
+    }
+
+    /// Gets the gate as a two-letter word.
+    pub fn letters(&self) -> String {
+        [self.first(), self.second()].iter().collect()
+    }
+}
+
+// Util function to get the regular letter index (1-22) of a Hebrew letter.
+fn letter_index(c: char) -> Option<u32> {
+    HEBREW_LETTERS
+        .iter()
+        .position(|&l| l == c)
+        .map(|i| base_letter_index(i as u32 + 1))
+}
+
+/// Enumerates all the 231 two-letter gates, in alphabet order (אב, אג, ..., שת).
+///
+/// # Examples
+///
+/// ```
+/// use gematria_rs::all_gates;
+///
+/// let gates = all_gates();
+/// assert_eq!(gates.len(), 231);
+/// assert_eq!(gates[0].letters(), "אב");
+/// assert_eq!(gates[230].letters(), "שת");
+/// ```
+pub fn all_gates() -> Vec<Gate> {
+    (1..=22)
+        .flat_map(|first| ((first + 1)..=22).map(move |second| Gate { first, second }))
+        .collect()
+}
+
+impl GematriaContext {
+    /// Calculates the gematria value of all the 231 gates, using the current method.
+    pub fn gate_values(&self) -> Vec<(Gate, u64)> {
+        all_gates()
+            .into_iter()
+            .map(|gate| (gate, self.calculate_value(&gate.letters()).value()))
+            .collect()
+    }
+
+    /// Scans a text for adjacent letter pairs within words, counting the occurrences of every gate.
+    ///
+    /// Gates are sorted by their number of occurrences (descending) and then in alphabet order.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// use gematria_rs::{GematriaContext, Gate};
+    ///
+    /// let gmctx = GematriaContext::default();
+    /// let gates = gmctx.scan_gates("אב בא");
+    ///
+    /// assert_eq!(gates, vec![(Gate::new('א', 'ב').unwrap(), 2)]);
+    /// ```
+    pub fn scan_gates(&self, text: &str) -> Vec<(Gate, usize)> {
+        let mut counts: HashMap<Gate, usize> = HashMap::new();
+        for word in self.tokenizer.tokenize(text) {
+            let letters: Vec<char> = word
+                .chars()
+                .filter(|c| self.get_character_index(c).is_some())
+                .collect();
+            for pair in letters.windows(2) {
+                if let Some(gate) = Gate::new(pair[0], pair[1]) {
+                    *counts.entry(gate).or_default() += 1;
+                }
+            }
+        }
+
+        let mut gates: Vec<(Gate, usize)> = counts.into_iter().collect();
+        gates.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+        gates
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_gate_normalization() {
+        assert_eq!(Gate::new('ב', 'א'), Gate::new('א', 'ב'));
+        assert_eq!(Gate::new('ך', 'א'), Gate::new('א', 'כ'));
+        assert_eq!(Gate::new('מ', 'ם'), None);
+        assert_eq!(Gate::new('a', 'ב'), None);
+    }
+
+    #[test]
+    fn test_gate_values() {
+        let gmctx = GematriaContext::default();
+        let values = gmctx.gate_values();
+
+        assert_eq!(values.len(), 231);
+        assert_eq!(values[0], (Gate::new('א', 'ב').unwrap(), 3));
+        assert_eq!(values[230], (Gate::new('ש', 'ת').unwrap(), 700));
+    }
+
+    #[test]
+    fn test_scan_gates() {
+        let gmctx = GematriaContext::default();
+        let gates = gmctx.scan_gates("שָׁלוֹם עולם");
+
+        assert_eq!(
+            gates,
+            vec![
+                (Gate::new('ל', 'ו').unwrap(), 2),
+                (Gate::new('ו', 'ם').unwrap(), 1),
+                (Gate::new('ע', 'ו').unwrap(), 1),
+                (Gate::new('ל', 'ם').unwrap(), 1),
+                (Gate::new('ש', 'ל').unwrap(), 1),
+            ]
+        );
+    }
+}
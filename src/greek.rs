@@ -0,0 +1,250 @@
+// Greek isopsephy: the Greek alphabet with its archaic numerals, and its letter values.
+
+use crate::alphabet::is_combining_mark;
+use crate::{std_gematria_value, AlphabetSystem, CharMap, GematriaCalculation, GematriaMethod};
+
+/// The Greek letters in the order of their values (α = 1 to ϡ = 900), along with their variant forms:
+/// capitals, accented (monotonic) letters, final sigma and symbol forms.
+const GREEK_LETTERS: [&[char]; 27] = [
+    &['α', 'Α', 'ά', 'Ά'],
+    &['β', 'Β', 'ϐ'],
+    &['γ', 'Γ'],
+    &['δ', 'Δ'],
+    &['ε', 'Ε', 'έ', 'Έ', 'ϵ'],
+    // Stigma, and the digamma it stands for
+    &['ϛ', 'Ϛ', 'ϝ', 'Ϝ'],
+    &['ζ', 'Ζ'],
+    &['η', 'Η', 'ή', 'Ή'],
+    &['θ', 'Θ', 'ϑ'],
+    &['ι', 'Ι', 'ί', 'Ί', 'ϊ', 'Ϊ', 'ΐ'],
+    &['κ', 'Κ', 'ϰ'],
+    &['λ', 'Λ'],
+    &['μ', 'Μ'],
+    &['ν', 'Ν'],
+    &['ξ', 'Ξ'],
+    &['ο', 'Ο', 'ό', 'Ό'],
+    &['π', 'Π', 'ϖ'],
+    // Koppa, archaic and numeral forms
+    &['ϙ', 'Ϙ', 'ϟ', 'Ϟ'],
+    &['ρ', 'Ρ', 'ϱ'],
+    &['σ', 'Σ', 'ς', 'ϲ', 'Ϲ'],
+    &['τ', 'Τ'],
+    &['υ', 'Υ', 'ύ', 'Ύ', 'ϋ', 'Ϋ', 'ΰ'],
+    &['φ', 'Φ', 'ϕ'],
+    &['χ', 'Χ'],
+    &['ψ', 'Ψ'],
+    &['ω', 'Ω', 'ώ', 'Ώ'],
+    // Sampi, and the archaic sampi
+    &['ϡ', 'Ϡ', 'ͳ', 'Ͳ'],
+];
+
+/// The indices of the archaic numerals stigma, koppa and sampi.
+const STIGMA: u32 = 6;
+const KOPPA: u32 = 18;
+const SAMPI: u32 = 27;
+
+/// Gets the plain letter of a polytonic letter (Greek Extended block, U+1F00 to U+1FFF),
+/// as found in classical and koine texts.
+fn polytonic_letter(c: char) -> Option<char> {
+    let letter = match c {
+        '\u{1F00}'..='\u{1F0F}' | '\u{1F70}' | '\u{1F71}' | '\u{1F80}'..='\u{1F8F}' => 'α',
+        '\u{1FB0}'..='\u{1FB4}' | '\u{1FB6}'..='\u{1FBC}' => 'α',
+        '\u{1F10}'..='\u{1F15}' | '\u{1F18}'..='\u{1F1D}' | '\u{1F72}' | '\u{1F73}' => 'ε',
+        '\u{1FC8}' | '\u{1FC9}' => 'ε',
+        '\u{1F20}'..='\u{1F2F}' | '\u{1F74}' | '\u{1F75}' | '\u{1F90}'..='\u{1F9F}' => 'η',
+        '\u{1FC2}'..='\u{1FC4}' | '\u{1FC6}' | '\u{1FC7}' | '\u{1FCA}'..='\u{1FCC}' => 'η',
+        '\u{1F30}'..='\u{1F3F}' | '\u{1F76}' | '\u{1F77}' => 'ι',
+        '\u{1FD0}'..='\u{1FD3}' | '\u{1FD6}'..='\u{1FDB}' => 'ι',
+        '\u{1F40}'..='\u{1F45}' | '\u{1F48}'..='\u{1F4D}' | '\u{1F78}' | '\u{1F79}' => 'ο',
+        '\u{1FF8}' | '\u{1FF9}' => 'ο',
+        '\u{1F50}'..='\u{1F57}' | '\u{1F59}' | '\u{1F5B}' | '\u{1F5D}' | '\u{1F5F}' => 'υ',
+        '\u{1F7A}' | '\u{1F7B}' | '\u{1FE0}'..='\u{1FE3}' | '\u{1FE6}'..='\u{1FEB}' => 'υ',
+        '\u{1F60}'..='\u{1F6F}' | '\u{1F7C}' | '\u{1F7D}' | '\u{1FA0}'..='\u{1FAF}' => 'ω',
+        '\u{1FF2}'..='\u{1FF4}' | '\u{1FF6}' | '\u{1FF7}' | '\u{1FFA}'..='\u{1FFC}' => 'ω',
+        '\u{1FE4}' | '\u{1FE5}' | '\u{1FEC}' => 'ρ',
+        _ => return None,
+    };
+    Some(letter)
+}
+
+/// `GreekAlphabet` describes the Greek letters valued by [`GematriaMethod::Isopsephy`],
+/// see [`crate::GematriaBuilder::with_greek_alphabet`].
+///
+/// The 24 letters are always valued, in any case and with any accent or breathing (monotonic or polytonic),
+/// and final sigma (ς) is valued as σ. The archaic numerals stigma (ϛ = 6, also written as the digamma ϝ),
+/// koppa (ϙ = 90) and sampi (ϡ = 900) are valued by default, and may be left out.
+///
+/// # Examples
+///
+/// ```
+/// use gematria_rs::{GematriaBuilder, GematriaMethod, GreekAlphabet};
+///
+/// let gmctx = GematriaBuilder::new()
+///     .with_method(GematriaMethod::Isopsephy)
+///     .init_gematria();
+/// assert_eq!(gmctx.calculate_value("λόγος").value(), 373);
+/// assert_eq!(gmctx.calculate_value("Ἰησοῦς").value(), 888);
+/// assert_eq!(gmctx.calculate_value("χξϛ").value(), 666);
+///
+/// let gmctx = GematriaBuilder::new()
+///     .with_method(GematriaMethod::Isopsephy)
+///     .with_greek_alphabet(GreekAlphabet::new().with_stigma(false))
+///     .init_gematria();
+/// assert_eq!(gmctx.calculate_value("χξϛ").value(), 660);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct GreekAlphabet {
+    // Whether the stigma (and digamma) is valued as 6.
+    stigma: bool,
+
+    // Whether the koppa is valued as 90.
+    koppa: bool,
+
+    // Whether the sampi is valued as 900.
+    sampi: bool,
+}
+
+impl Default for GreekAlphabet {
+    fn default() -> Self {
+        GreekAlphabet {
+            stigma: true,
+            koppa: true,
+            sampi: true,
+        }
+    }
+}
+
+impl GreekAlphabet {
+    /// Creates the alphabet valuing the archaic numerals.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets whether the stigma (ϛ, and the digamma ϝ) is valued as 6.
+    pub fn with_stigma(mut self, stigma: bool) -> Self {
+        self.stigma = stigma;
+        self
+    }
+
+    /// Sets whether the koppa (ϙ) is valued as 90.
+    pub fn with_koppa(mut self, koppa: bool) -> Self {
+        self.koppa = koppa;
+        self
+    }
+
+    /// Sets whether the sampi (ϡ) is valued as 900.
+    pub fn with_sampi(mut self, sampi: bool) -> Self {
+        self.sampi = sampi;
+        self
+    }
+
+    /// Gets whether the stigma is valued.
+    pub fn stigma(&self) -> bool {
+        self.stigma
+    }
+
+    /// Gets whether the koppa is valued.
+    pub fn koppa(&self) -> bool {
+        self.koppa
+    }
+
+    /// Gets whether the sampi is valued.
+    pub fn sampi(&self) -> bool {
+        self.sampi
+    }
+
+    /// Builds the character map of the alphabet, mapping every form of a letter to its index (1 based).
+    pub fn char_map(&self) -> CharMap {
+        let mut map: CharMap = (1..)
+            .zip(GREEK_LETTERS)
+            .filter(|&(index, _)| match index {
+                STIGMA => self.stigma,
+                KOPPA => self.koppa,
+                SAMPI => self.sampi,
+                _ => true,
+            })
+            .flat_map(|(index, forms)| forms.iter().map(move |&form| (form, index)))
+            .collect();
+
+        let polytonic: Vec<(char, u32)> = ('\u{1F00}'..='\u{1FFF}')
+            .filter_map(|c| Some((c, *map.get(&polytonic_letter(c)?)?)))
+            .collect();
+        map.extend(polytonic);
+        map
+    }
+}
+
+impl AlphabetSystem for GreekAlphabet {
+    fn name(&self) -> &str {
+        "Greek"
+    }
+
+    fn letters(&self) -> CharMap {
+        self.char_map()
+    }
+
+    fn is_mark(&self, c: char) -> bool {
+        is_combining_mark(c)
+    }
+}
+
+/// Represents the Greek isopsephy method, where the letters of the Greek alphabet are valued
+/// from α = 1 to ω = 800 (with the archaic ϛ = 6, ϙ = 90 and ϡ = 900), see [`GreekAlphabet`].
+///
+/// Contexts built with this method use the Greek alphabet unless another alphabet or character map is given,
+/// with the Hebrew alphabet it values the letters as [`GematriaMethod::MisparGadol`].
+#[derive(Clone)]
+pub struct Isopsephy;
+
+impl GematriaCalculation for Isopsephy {
+    fn calculate_value(&self, letter_index: u32) -> u32 {
+        match letter_index {
+            1..=27 => std_gematria_value(&letter_index),
+            _ => 0,
+        }
+    }
+
+    fn method_type(&self) -> GematriaMethod {
+        GematriaMethod::Isopsephy
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::GematriaBuilder;
+
+    #[test]
+    fn test_letter_values() {
+        let gmctx = GematriaBuilder::new()
+            .with_method(GematriaMethod::Isopsephy)
+            .init_gematria();
+        let values: Vec<u64> = "αβγδεϛζηθικλμνξοπϙρστυφχψωϡ"
+            .chars()
+            .map(|c| gmctx.calculate_char_value(c))
+            .collect();
+        let expected: Vec<u64> = (1..=27).map(|i| std_gematria_value(&i) as u64).collect();
+        assert_eq!(values, expected);
+
+        // Capitals, accents, breathings and final sigma are valued as their letters
+        assert_eq!(
+            gmctx.calculate_value("ΑΓΑΠΗ").value(),
+            gmctx.calculate_value("ἀγάπη").value()
+        );
+        assert_eq!(gmctx.calculate_value("ς").value(), 200);
+    }
+
+    #[test]
+    fn test_grouping_greek_text() {
+        let gmctx = GematriaBuilder::new()
+            .with_method(GematriaMethod::Isopsephy)
+            .init_gematria();
+        // αβ and γ are both 3
+        let groups = gmctx.group_words("αβ γ δ γ").unwrap();
+        assert_eq!(groups, [(3, vec!["αβ".to_string(), "γ".to_string()])]);
+
+        let matches = gmctx.search_matching_values(&4, "αβ γ δ");
+        assert_eq!(matches.len(), 1);
+        assert_eq!(matches[0].word(), "δ");
+    }
+}
@@ -0,0 +1,637 @@
+// Corpus index, ingesting a text once to answer many queries without recalculating it.
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    ops::Range,
+};
+#[cfg(feature = "index-files")]
+use std::{
+    fs::File,
+    io::{self, BufReader, BufWriter, Read, Write},
+    path::Path,
+};
+
+#[cfg(feature = "index-files")]
+use crate::IndexHeader;
+
+use crate::{
+    parallel, process_method_dyn, CancellationToken, GematriaCalculation, GematriaContext,
+    GematriaError, GematriaMethod, GematriaValue, Match, NoProgress, ProgressSink,
+};
+
+/// The number of words ingested between the progress reports (and cancellation checks) of indexing.
+const PROGRESS_CHUNK: usize = 1024;
+
+/// `GematriaIndex` ingests a corpus once, storing its distinct words, their positions and their values
+/// under every indexed method, then answers matching, grouping and reverse lookup queries without
+/// recalculating the text.
+///
+/// Words are indexed as found in the results of the context building the index, and valued as that
+/// context values them (normalization, transliteration, abbreviations, shift, milui spellings, modifiers
+/// and kolel), so the index answers as the searches of the context. The spellings of a word differing
+/// by their removed marks (such as vowels) are valued apart, for the methods valuing the marks ([`GematriaMethod::Nikud`]).
+///
+/// # Examples
+///
+/// ```
+/// use gematria_rs::{GematriaContext, GematriaMethod};
+///
+/// let gmctx = GematriaContext::default();
+/// let index = gmctx.build_index(
+///     "נכנס יין יצא סוד",
+///     &[GematriaMethod::MisparHechrechi, GematriaMethod::MisparGadol],
+/// )?;
+///
+/// assert_eq!(index.words_with_value(GematriaMethod::MisparHechrechi, 70), ["יין", "סוד"]);
+/// assert_eq!(index.words_with_value(GematriaMethod::MisparGadol, 720), ["יין"]);
+/// assert_eq!(index.value_of(GematriaMethod::MisparGadol, "סוד"), Some(70));
+/// # Ok::<(), gematria_rs::Error>(())
+/// ```
+///
+/// With the `index-files` feature, an index can be saved once and loaded back with [`GematriaIndex::save`]
+/// and [`GematriaIndex::load`], instead of indexing a large corpus again.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GematriaIndex {
+    // The distinct words of the corpus, in order of first appearance.
+    words: Vec<String>,
+
+    // Word to its position in `words`, rebuilt when loading.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    word_ids: HashMap<String, usize>,
+
+    // The word of every distinct spelling of the corpus (a text as valued), in order of first appearance.
+    spellings: Vec<usize>,
+
+    // The spelling and byte range of every token of the corpus, in order.
+    tokens: Vec<(usize, Range<usize>)>,
+
+    // The token indices of every word.
+    occurrences: Vec<Vec<usize>>,
+
+    // The values of the spellings under every indexed method, keyed by method name when serialized.
+    #[cfg_attr(feature = "serde", serde(with = "method_names"))]
+    methods: BTreeMap<GematriaMethod, MethodIndex>,
+}
+
+// A method and its strategy, `None` standing for the custom strategy of the context.
+pub(crate) type MethodStrategy = (GematriaMethod, Option<Box<dyn GematriaCalculation>>);
+
+// The values of the spellings of a corpus under a single method.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+struct MethodIndex {
+    // The value of every spelling, by spelling position.
+    values: Vec<GematriaValue>,
+
+    // Value to the positions of the spellings having it, in order of first appearance.
+    by_value: BTreeMap<GematriaValue, Vec<usize>>,
+}
+
+impl GematriaIndex {
+    /// Gets the indexed methods.
+    pub fn methods(&self) -> impl Iterator<Item = GematriaMethod> + '_ {
+        self.methods.keys().copied()
+    }
+
+    /// Gets the number of words (tokens) of the corpus.
+    pub fn token_count(&self) -> usize {
+        self.tokens.len()
+    }
+
+    /// Gets the distinct words of the corpus, in order of first appearance.
+    pub fn words(&self) -> &[String] {
+        &self.words
+    }
+
+    /// Gets the value of an indexed word, `None` if the word or the method were not indexed.
+    ///
+    /// The value is the value of the first spelling of the word, under the methods valuing marks
+    /// its other spellings may have other values.
+    pub fn value_of(&self, method: GematriaMethod, word: &str) -> Option<GematriaValue> {
+        let id = *self.word_ids.get(word)?;
+        let (spelling, _) = &self.tokens[*self.occurrences[id].first()?];
+        Some(self.methods.get(&method)?.values[*spelling])
+    }
+
+    /// Gets the token indices of an indexed word within the corpus.
+    pub fn positions(&self, word: &str) -> &[usize] {
+        self.word_ids
+            .get(word)
+            .map(|&id| self.occurrences[id].as_slice())
+            .unwrap_or_default()
+    }
+
+    /// Gets the distinct words with the given value, in order of first appearance.
+    pub fn words_with_value(&self, method: GematriaMethod, value: GematriaValue) -> Vec<&str> {
+        self.ids_with_value(method, value)
+            .into_iter()
+            .map(|id| self.words[id].as_str())
+            .collect()
+    }
+
+    /// Gets every occurrence of the words with the given value, in order of appearance in the corpus.
+    pub fn search_matching_values(
+        &self,
+        method: GematriaMethod,
+        value: GematriaValue,
+    ) -> Vec<Match> {
+        let Some(values) = self.methods.get(&method).map(|index| &index.values) else {
+            return Vec::new();
+        };
+        // The occurrences of the words spelled with the value
+        let mut indices: Vec<usize> = self
+            .ids_with_value(method, value)
+            .into_iter()
+            .flat_map(|id| self.occurrences[id].iter().copied())
+            .filter(|&index| values[self.tokens[index].0] == value)
+            .collect();
+        indices.sort_unstable();
+
+        indices
+            .into_iter()
+            .map(|index| {
+                let (spelling, range) = &self.tokens[index];
+                let word = &self.words[self.spellings[*spelling]];
+                Match::new(word.clone(), value, index).with_byte_range(range.clone())
+            })
+            .collect()
+    }
+
+    /// Groups the distinct words with matching values, as [`GematriaContext::group_words`] does.
+    ///
+    /// Groups of a single word are left out, groups are sorted by their number of words (descending)
+    /// and then by value. Returns an empty grouping for a method which was not indexed.
+    pub fn group_words(&self, method: GematriaMethod) -> Vec<(GematriaValue, Vec<String>)> {
+        let mut groups: Vec<(GematriaValue, Vec<String>)> = self
+            .methods
+            .get(&method)
+            .map(|index| {
+                index
+                    .by_value
+                    .iter()
+                    .map(|(&value, spellings)| (value, self.spelled_words(spellings)))
+                    .filter(|(_, ids)| ids.len() > 1)
+                    .map(|(value, ids)| {
+                        (
+                            value,
+                            ids.into_iter().map(|id| self.words[id].clone()).collect(),
+                        )
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+        // The sort is stable and groups are ordered by value
+        groups.sort_by_key(|(_, words)| std::cmp::Reverse(words.len()));
+        groups
+    }
+
+    // Util function to get the distinct words spelled with the given value, in order of first appearance.
+    fn ids_with_value(&self, method: GematriaMethod, value: GematriaValue) -> Vec<usize> {
+        let spellings = self
+            .methods
+            .get(&method)
+            .and_then(|index| index.by_value.get(&value))
+            .map(Vec::as_slice)
+            .unwrap_or_default();
+        self.spelled_words(spellings)
+    }
+
+    // Util function to get the distinct words of spellings, in order of first appearance.
+    fn spelled_words(&self, spellings: &[usize]) -> Vec<usize> {
+        let mut ids: Vec<usize> = Vec::with_capacity(spellings.len());
+        for &spelling in spellings {
+            let id = self.spellings[spelling];
+            if !ids.contains(&id) {
+                ids.push(id);
+            }
+        }
+        ids
+    }
+}
+
+#[cfg(feature = "index-files")]
+impl GematriaIndex {
+    /// Writes the index to the given writer, behind an [`IndexHeader`].
+    pub fn write_to<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        IndexHeader::current().write_to(writer)?;
+        bincode::serialize_into(writer, self).map_err(|err| bincode_error(*err))
+    }
+
+    /// Reads an index written by [`GematriaIndex::write_to`] from the given reader.
+    ///
+    /// Fails with [`io::ErrorKind::Unsupported`] for an unsupported format version
+    /// and with [`io::ErrorKind::InvalidData`] for a corrupted index.
+    pub fn read_from<R: Read>(reader: &mut R) -> io::Result<Self> {
+        IndexHeader::read_from(reader)?;
+        let mut index: Self =
+            bincode::deserialize_from(reader).map_err(|err| bincode_error(*err))?;
+        if !index.is_consistent() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "corrupted gematria index",
+            ));
+        }
+
+        index.word_ids = index
+            .words
+            .iter()
+            .enumerate()
+            .map(|(id, word)| (word.clone(), id))
+            .collect();
+        Ok(index)
+    }
+
+    /// Saves the index to a file.
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        let mut writer = BufWriter::new(File::create(path)?);
+        self.write_to(&mut writer)?;
+        writer.flush()
+    }
+
+    /// Loads an index saved by [`GematriaIndex::save`] from a file.
+    pub fn load<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        Self::read_from(&mut BufReader::new(File::open(path)?))
+    }
+
+    // Checks every word, spelling and token position of the index is in bounds, so queries never panic.
+    fn is_consistent(&self) -> bool {
+        let words = self.words.len();
+        let spellings = self.spellings.len();
+        self.occurrences.len() == words
+            && self.spellings.iter().all(|&id| id < words)
+            && self
+                .tokens
+                .iter()
+                .all(|(spelling, _)| *spelling < spellings)
+            && self
+                .occurrences
+                .iter()
+                .flatten()
+                .all(|&token| token < self.tokens.len())
+            && self.methods.values().all(|index| {
+                index.values.len() == spellings
+                    && index.by_value.values().flatten().all(|&id| id < spellings)
+            })
+    }
+}
+
+// Serializes the methods of an index by name, so persisted indexes do not depend on the variant order.
+#[cfg(feature = "serde")]
+mod method_names {
+    use std::collections::BTreeMap;
+
+    use serde::{de::Error, Deserialize, Deserializer, Serializer};
+
+    use super::MethodIndex;
+    use crate::GematriaMethod;
+
+    pub(super) fn serialize<S: Serializer>(
+        methods: &BTreeMap<GematriaMethod, MethodIndex>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.collect_map(methods.iter().map(|(method, index)| (method.name(), index)))
+    }
+
+    pub(super) fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<BTreeMap<GematriaMethod, MethodIndex>, D::Error> {
+        BTreeMap::<String, MethodIndex>::deserialize(deserializer)?
+            .into_iter()
+            .map(|(name, index)| {
+                GematriaMethod::all()
+                    .chain(std::iter::once(GematriaMethod::Custom))
+                    .find(|method| method.name() == name)
+                    .map(|method| (method, index))
+                    .ok_or_else(|| D::Error::custom(format!("unknown gematria method '{}'", name)))
+            })
+            .collect()
+    }
+}
+
+// Util function to convert a bincode error, keeping I/O errors as they are.
+#[cfg(feature = "index-files")]
+fn bincode_error(err: bincode::ErrorKind) -> io::Error {
+    match err {
+        bincode::ErrorKind::Io(err) => err,
+        err => io::Error::new(io::ErrorKind::InvalidData, err),
+    }
+}
+
+impl GematriaContext {
+    /// Builds a [`GematriaIndex`] of a corpus under the given methods.
+    ///
+    /// [`GematriaMethod::Custom`] indexes the custom strategy of the context, if any.
+    /// Fails with [`GematriaError::UnsupportedMethod`] for a method without an implementation.
+    /// With the `parallel` feature, the distinct words are valued in parallel.
+    pub fn build_index(
+        &self,
+        text: &str,
+        methods: &[GematriaMethod],
+    ) -> Result<GematriaIndex, GematriaError> {
+        self.build_index_with_progress(text, methods, &mut NoProgress, &CancellationToken::new())
+    }
+
+    /// Same as [`GematriaContext::build_index`], reporting progress (in words) to a [`ProgressSink`]
+    /// and aborting with a [`GematriaError::Cancelled`] error once the [`CancellationToken`] is cancelled.
+    ///
+    /// Words are ingested and valued by chunks of 1024 words, progress is reported after every chunk.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gematria_rs::{CancellationToken, GematriaContext, GematriaMethod};
+    ///
+    /// let gmctx = GematriaContext::default();
+    /// let mut processed = 0;
+    /// let index = gmctx.build_index_with_progress(
+    ///     "נכנס יין יצא סוד",
+    ///     &[GematriaMethod::MisparHechrechi],
+    ///     &mut |done, _total| processed = done,
+    ///     &CancellationToken::new(),
+    /// )?;
+    ///
+    /// assert_eq!(processed, 4);
+    /// assert_eq!(index.words_with_value(GematriaMethod::MisparHechrechi, 70), ["יין", "סוד"]);
+    /// # Ok::<(), gematria_rs::Error>(())
+    /// ```
+    pub fn build_index_with_progress(
+        &self,
+        text: &str,
+        methods: &[GematriaMethod],
+        progress: &mut dyn ProgressSink,
+        cancel: &CancellationToken,
+    ) -> Result<GematriaIndex, GematriaError> {
+        let strategies = self.method_strategies(methods)?;
+        let mut method_indices = vec![MethodIndex::default(); strategies.len()];
+        let mut index = GematriaIndex::default();
+        // Normalized text to its spelling
+        let mut spelling_ids: HashMap<String, usize> = HashMap::new();
+        let tokens: Vec<(Range<usize>, &str)> = self.tokenizer.tokenize_with_ranges(text).collect();
+        let mut processed = 0;
+        for chunk in tokens.chunks(PROGRESS_CHUNK) {
+            if cancel.is_cancelled() {
+                return Err(GematriaError::Cancelled);
+            }
+
+            // The text valued for every spelling first found in the chunk
+            let mut texts: Vec<String> = Vec::new();
+            for (range, raw) in chunk {
+                let normalized = self.normalize(raw);
+                let spelling = match spelling_ids.get(normalized.as_ref()) {
+                    Some(&spelling) => spelling,
+                    None => {
+                        let (_, abbreviation) = self.numeral_or_abbreviation(&normalized);
+                        let valued = abbreviation.unwrap_or_else(|| normalized.to_string());
+                        let word = self.handle_vowels(&valued);
+                        let id = match index.word_ids.get(&word) {
+                            Some(&id) => id,
+                            None => {
+                                let id = index.words.len();
+                                index.word_ids.insert(word.clone(), id);
+                                index.words.push(word);
+                                index.occurrences.push(Vec::new());
+                                id
+                            }
+                        };
+                        let spelling = index.spellings.len();
+                        spelling_ids.insert(normalized.into_owned(), spelling);
+                        index.spellings.push(id);
+                        texts.push(valued);
+                        spelling
+                    }
+                };
+                index.occurrences[index.spellings[spelling]].push(index.tokens.len());
+                index.tokens.push((spelling, range.clone()));
+            }
+
+            // With the `parallel` feature, the new spellings are valued in parallel
+            let first = index.spellings.len() - texts.len();
+            let letters = parallel::map(&texts, |text| self.get_word_letters(text));
+            let words: Vec<(&str, Vec<Vec<u32>>)> =
+                texts.iter().map(String::as_str).zip(letters).collect();
+            for ((_, built), method_index) in strategies.iter().zip(&mut method_indices) {
+                let strategy = built
+                    .as_deref()
+                    .unwrap_or(self.calculation_strategy.as_ref());
+                let values = parallel::map(&words, |(word, letters)| {
+                    self.calculate_letters_value(strategy, letters, word)
+                });
+                for (offset, value) in values.into_iter().enumerate() {
+                    method_index.values.push(value);
+                    method_index
+                        .by_value
+                        .entry(value)
+                        .or_default()
+                        .push(first + offset);
+                }
+            }
+
+            processed += chunk.len();
+            progress.on_progress(processed, tokens.len());
+        }
+        index.methods = strategies
+            .into_iter()
+            .map(|(method, _)| method)
+            .zip(method_indices)
+            .collect();
+
+        Ok(index)
+    }
+
+    // Util function to build the strategies of the given methods, `None` standing for the custom strategy of the context.
+    pub(crate) fn method_strategies(
+        &self,
+        methods: &[GematriaMethod],
+    ) -> Result<Vec<MethodStrategy>, GematriaError> {
+        methods
+            .iter()
+            .map(|&method| {
+                if method == GematriaMethod::Custom && self.get_current_method() == method {
+                    return Ok((method, None));
+                }
+                let strategy = process_method_dyn(
+                    method,
+                    self.character_map.clone(),
+                    self.shift,
+                    &self.milui_spellings,
+                    self.sofit_policy,
+                    &self.alphabet,
+                )?;
+                Ok((method, Some(strategy)))
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{GematriaBuilder, Transliteration};
+
+    #[test]
+    fn test_index_matches_context() {
+        let text = "נכנס יין יצא סוד\nיין שָׁלוֹם־עולם שלום";
+        let gmctx = GematriaContext::default();
+        let index = gmctx
+            .build_index(text, &[GematriaMethod::MisparHechrechi])
+            .unwrap();
+
+        assert_eq!(index.token_count(), 8);
+        assert_eq!(index.words().len(), 6);
+        assert_eq!(index.positions("יין"), [1, 4]);
+        assert_eq!(
+            index.search_matching_values(GematriaMethod::MisparHechrechi, 70),
+            gmctx.search_matching_values(&70, text)
+        );
+        assert_eq!(
+            index.search_matching_values(GematriaMethod::MisparHechrechi, 376),
+            gmctx.search_matching_values(&376, text)
+        );
+        assert_eq!(
+            index.group_words(GematriaMethod::MisparHechrechi),
+            gmctx.group_words(text).unwrap()
+        );
+        assert!(index.group_words(GematriaMethod::MisparGadol).is_empty());
+    }
+
+    #[test]
+    fn test_index_values_as_the_context() {
+        // Abbreviations, numerals and transliterated words are valued as the context values them
+        let text = "צה״ל צהל תשפ״ד shalom שלום";
+        let gmctx = GematriaBuilder::new()
+            .with_abbreviations(true)
+            .with_numerals(true)
+            .with_transliteration(Transliteration::new())
+            .init_gematria();
+        let index = gmctx
+            .build_index(text, &[GematriaMethod::MisparHechrechi])
+            .unwrap();
+        for word in text.split(' ') {
+            let value = gmctx.calculate_value(word).value();
+            assert_eq!(
+                index.search_matching_values(GematriaMethod::MisparHechrechi, value),
+                gmctx.search_matching_values(&value, text),
+                "{}",
+                word
+            );
+        }
+        assert_eq!(index.words(), ["צהל", "תשפ״ד", "שלום"]);
+        assert_eq!(
+            index.group_words(GematriaMethod::MisparHechrechi),
+            gmctx.group_words(text).unwrap()
+        );
+
+        // Differently pointed spellings of a word are valued apart under Nikud
+        let text = "שָׁלוֹם שלום שְׁלוֹם שָׁלוֹם";
+        let gmctx = GematriaBuilder::new()
+            .with_method(GematriaMethod::Nikud)
+            .init_gematria();
+        let index = gmctx.build_index(text, &[GematriaMethod::Nikud]).unwrap();
+        assert_eq!(index.words(), ["שלום"]);
+        assert_eq!(index.positions("שלום"), [0, 1, 2, 3]);
+        for word in text.split(' ') {
+            let value = gmctx.calculate_value(word).value();
+            assert_eq!(
+                index.search_matching_values(GematriaMethod::Nikud, value),
+                gmctx.search_matching_values(&value, text),
+                "{}",
+                word
+            );
+        }
+        assert_eq!(
+            index.value_of(GematriaMethod::Nikud, "שלום"),
+            Some(gmctx.calculate_value("שָׁלוֹם").value())
+        );
+    }
+
+    #[test]
+    fn test_index_cancelled_midway() {
+        let gmctx = GematriaContext::default();
+        let text = "נכנס יין יצא סוד ".repeat(1000);
+        let methods = [GematriaMethod::MisparHechrechi];
+        let cancel = CancellationToken::new();
+        let mut reports = Vec::new();
+        let mut sink = |processed, total| {
+            reports.push((processed, total));
+            if processed >= 2048 {
+                cancel.cancel();
+            }
+        };
+        let result = gmctx.build_index_with_progress(&text, &methods, &mut sink, &cancel);
+
+        assert_eq!(result, Err(GematriaError::Cancelled));
+        assert_eq!(reports, [(1024, 4000), (2048, 4000)]);
+
+        let mut last = (0, 0);
+        let index = gmctx
+            .build_index_with_progress(
+                &text,
+                &methods,
+                &mut |processed, total| last = (processed, total),
+                &CancellationToken::new(),
+            )
+            .unwrap();
+        assert_eq!(last, (4000, 4000));
+        assert_eq!(index, gmctx.build_index(&text, &methods).unwrap());
+        assert_eq!(index.positions("סוד").len(), 1000);
+    }
+
+    #[test]
+    fn test_index_unsupported_method() {
+        let gmctx = GematriaContext::default();
+
+        assert_eq!(
+            gmctx.build_index("אב", &[GematriaMethod::MisparBoneh]),
+            Err(GematriaError::UnsupportedMethod(
+                GematriaMethod::MisparBoneh
+            ))
+        );
+    }
+
+    #[cfg(feature = "index-files")]
+    #[test]
+    fn test_index_save_and_load() {
+        let gmctx = GematriaContext::default();
+        let index = gmctx
+            .build_index(
+                "נכנס יין יצא סוד",
+                &[GematriaMethod::MisparHechrechi, GematriaMethod::MisparGadol],
+            )
+            .unwrap();
+
+        let mut bytes = Vec::new();
+        index.write_to(&mut bytes).unwrap();
+        let loaded = GematriaIndex::read_from(&mut bytes.as_slice()).unwrap();
+        assert_eq!(loaded, index);
+        assert_eq!(
+            loaded.value_of(GematriaMethod::MisparGadol, "יין"),
+            Some(720)
+        );
+
+        let err = GematriaIndex::read_from(&mut &bytes[..bytes.len() - 1]).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::UnexpectedEof);
+    }
+
+    #[cfg(feature = "index-files")]
+    #[test]
+    fn test_index_methods_saved_by_name() {
+        let gmctx = GematriaContext::default();
+        let index = gmctx
+            .build_index("נכנס יין יצא סוד", &[GematriaMethod::MisparGadol])
+            .unwrap();
+
+        let mut bytes = Vec::new();
+        index.write_to(&mut bytes).unwrap();
+        let name = b"Mispar Gadol";
+        let at = bytes
+            .windows(name.len())
+            .position(|window| window == name)
+            .unwrap();
+
+        // An unknown method name is rejected, rather than read as another method
+        bytes[at..at + name.len()].copy_from_slice(b"Mispar Bogus");
+        let err = GematriaIndex::read_from(&mut bytes.as_slice()).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    }
+}
@@ -0,0 +1,319 @@
+// Dictionary backed reverse lookup, from gematria values back to words.
+
+use std::{
+    collections::{BTreeMap, HashSet},
+    io::{self, BufRead},
+};
+
+use crate::{
+    limits::LimitGuard, GematriaContext, GematriaMethod, GematriaValue, Limited, SearchLimits,
+};
+
+/// The bundled Hebrew word list, one word per line in alphabet order.
+///
+/// It holds the distinct words (without vowels) appearing at least 3 times in the text of the Hebrew Bible,
+/// about 12,700 words. Enabled by the `wordlist` feature.
+#[cfg(feature = "wordlist")]
+pub const WORDLIST: &str = include_str!("../data/wordlist.txt");
+
+/// `Lexicon` is a reverse index of a word list, ingested once to answer value to words queries
+/// without rescanning any text.
+///
+/// Words are indexed once (repetitions are ignored), as returned by the vowel handling of the context,
+/// and the values are calculated with the method of the context building the lexicon.
+///
+/// # Examples
+///
+/// ```
+/// use gematria_rs::GematriaContext;
+///
+/// let gmctx = GematriaContext::default();
+/// let lexicon = gmctx.build_lexicon(["שלום", "יין", "סוד", "יין"]);
+///
+/// assert_eq!(lexicon.words_with_value(70), ["יין", "סוד"]);
+/// assert_eq!(lexicon.words_with_value(376), ["שלום"]);
+/// assert!(lexicon.words_with_value(1).is_empty());
+/// assert_eq!(lexicon.len(), 3);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Lexicon {
+    // The method used to calculate the values of the words.
+    method: GematriaMethod,
+
+    // Value to the words having it, in insertion order.
+    by_value: BTreeMap<GematriaValue, Vec<String>>,
+
+    // The indexed words, to ignore repetitions.
+    words: HashSet<String>,
+}
+
+impl Lexicon {
+    /// Creates an empty lexicon for values calculated with the given method.
+    pub fn new(method: GematriaMethod) -> Self {
+        Self {
+            method,
+            by_value: BTreeMap::new(),
+            words: HashSet::new(),
+        }
+    }
+
+    /// Gets the method used to calculate the values of the words.
+    pub fn method(&self) -> GematriaMethod {
+        self.method
+    }
+
+    /// Gets the words with exactly the given value, in insertion order.
+    pub fn words_with_value(&self, value: GematriaValue) -> &[String] {
+        self.by_value
+            .get(&value)
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
+    /// Iterates over the distinct values of the words, in ascending order.
+    pub fn values(&self) -> impl Iterator<Item = GematriaValue> + '_ {
+        self.by_value.keys().copied()
+    }
+
+    /// Iterates over the values and their words, in ascending order of value.
+    pub fn entries(&self) -> impl Iterator<Item = (GematriaValue, &[String])> + '_ {
+        self.by_value
+            .iter()
+            .map(|(&value, words)| (value, words.as_slice()))
+    }
+
+    /// Whether the word is in the lexicon.
+    pub fn contains(&self, word: &str) -> bool {
+        self.words.contains(word)
+    }
+
+    /// Gets the number of distinct words.
+    pub fn len(&self) -> usize {
+        self.words.len()
+    }
+
+    /// Whether the lexicon has no words.
+    pub fn is_empty(&self) -> bool {
+        self.words.is_empty()
+    }
+
+    /// Enumerates the combinations of distinct words of the lexicon whose values sum to the target value,
+    /// such as phrases equal to a name or a year.
+    ///
+    /// Phrases hold 1 to `max_words` words (bounded by the depth limit too, if lower), in ascending order of value,
+    /// so nothing is found for 0 words. Words without a value are never used. The number of combinations grows
+    /// quickly with the lexicon and the number of words, so the results and time limits bound the enumeration.
+    /// The result is truncated when a limit cut the enumeration, including phrases cut by the number of words.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gematria_rs::{GematriaContext, SearchLimits};
+    ///
+    /// let gmctx = GematriaContext::default();
+    /// let lexicon = gmctx.build_lexicon(["אב", "גד", "יין", "סוד", "ס", "י"]);
+    /// let phrases = lexicon.phrases_with_value(70, 3, &SearchLimits::new());
+    ///
+    /// let joined: Vec<String> = phrases.items().iter().map(|p| p.join(" ")).collect();
+    /// assert_eq!(joined, vec!["אב גד ס", "י ס", "יין", "סוד"]);
+    /// assert!(!phrases.is_truncated());
+    ///
+    /// // אב גד (10) could only reach 70 with a third word
+    /// assert!(lexicon.phrases_with_value(70, 2, &SearchLimits::new()).is_truncated());
+    /// ```
+    pub fn phrases_with_value(
+        &self,
+        target_value: GematriaValue,
+        max_words: usize,
+        limits: &SearchLimits,
+    ) -> Limited<Vec<String>> {
+        let max_words = limits.max_depth().map_or(max_words, |d| d.min(max_words));
+        if max_words == 0 {
+            return Limited::new(Vec::new(), false);
+        }
+        let words: Vec<(GematriaValue, &str)> = self
+            .entries()
+            .filter(|&(value, _)| value > 0)
+            .flat_map(|(value, words)| words.iter().map(move |w| (value, w.as_str())))
+            .collect();
+
+        let mut search = PhraseSearch {
+            words: &words,
+            max_words,
+            guard: LimitGuard::start(limits),
+            phrase: Vec::new(),
+            phrases: Vec::new(),
+            truncated: false,
+            capped: false,
+        };
+        search.extend(0, target_value);
+
+        let mut phrases = search.phrases;
+        phrases.sort();
+        Limited::new(phrases, search.truncated || search.capped)
+    }
+
+    fn insert(&mut self, word: String, value: GematriaValue) {
+        if !word.is_empty() && self.words.insert(word.clone()) {
+            self.by_value.entry(value).or_default().push(word);
+        }
+    }
+}
+
+// The state of a phrase enumeration, as a depth first search over the words sorted by value.
+struct PhraseSearch<'a> {
+    words: &'a [(GematriaValue, &'a str)],
+    max_words: usize,
+    guard: LimitGuard,
+    phrase: Vec<&'a str>,
+    phrases: Vec<Vec<String>>,
+    truncated: bool,
+    // Whether a phrase short of the target could not be extended past the maximum number of words.
+    capped: bool,
+}
+
+impl PhraseSearch<'_> {
+    // Extends the current phrase with the words from `start`, to reach the remaining value.
+    fn extend(&mut self, start: usize, remaining: GematriaValue) {
+        for i in start..self.words.len() {
+            if self.guard.exceeded(self.phrases.len()) {
+                self.truncated = true;
+                return;
+            }
+
+            // Words are sorted by value, so the following ones only exceed the remaining value further
+            let (value, word) = self.words[i];
+            if value > remaining {
+                return;
+            }
+
+            self.phrase.push(word);
+            if value == remaining {
+                self.phrases
+                    .push(self.phrase.iter().map(|w| w.to_string()).collect());
+            } else if self.phrase.len() < self.max_words {
+                self.extend(i + 1, remaining - value);
+            } else if self
+                .words
+                .get(i + 1)
+                .is_some_and(|&(next, _)| next <= remaining - value)
+            {
+                // The following words could still have completed the phrase
+                self.capped = true;
+            }
+            self.phrase.pop();
+            if self.truncated {
+                return;
+            }
+        }
+    }
+}
+
+impl GematriaContext {
+    /// Builds a [`Lexicon`] of a word list, using the current method.
+    ///
+    /// Every entry may hold several words (e.g. lines of a text), which are indexed separately.
+    pub fn build_lexicon<I, W>(&self, words: I) -> Lexicon
+    where
+        I: IntoIterator<Item = W>,
+        W: AsRef<str>,
+    {
+        let mut lexicon = Lexicon::new(self.get_current_method());
+        for entry in words {
+            self.extend_lexicon(&mut lexicon, entry.as_ref());
+        }
+
+        lexicon
+    }
+
+    /// Builds a [`Lexicon`] of the bundled [`WORDLIST`], using the current method.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gematria_rs::GematriaContext;
+    ///
+    /// let lexicon = GematriaContext::default().bundled_lexicon();
+    /// assert!(lexicon.words_with_value(376).contains(&"שלום".to_string()));
+    /// ```
+    #[cfg(feature = "wordlist")]
+    pub fn bundled_lexicon(&self) -> Lexicon {
+        self.build_lexicon(WORDLIST.lines())
+    }
+
+    /// Builds a [`Lexicon`] of a dictionary file (or any reader), reading it line by line.
+    pub fn lexicon_from_reader<R: BufRead>(&self, reader: R) -> io::Result<Lexicon> {
+        let mut lexicon = Lexicon::new(self.get_current_method());
+        for line in reader.lines() {
+            self.extend_lexicon(&mut lexicon, &line?);
+        }
+
+        Ok(lexicon)
+    }
+
+    fn extend_lexicon(&self, lexicon: &mut Lexicon, text: &str) {
+        for word in self.tokenizer.tokenize(text) {
+            let result = self.calculate_value(word);
+            lexicon.insert(result.word().to_string(), result.value());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::GematriaBuilder;
+
+    #[test]
+    fn test_lexicon_from_reader() {
+        let gmctx = GematriaBuilder::new()
+            .with_method(GematriaMethod::MisparGadol)
+            .init_gematria();
+        let dictionary = "שָׁלוֹם\nשלום עולם\n\nאב";
+        let lexicon = gmctx.lexicon_from_reader(dictionary.as_bytes()).unwrap();
+
+        assert_eq!(lexicon.method(), GematriaMethod::MisparGadol);
+        assert_eq!(lexicon.len(), 3);
+        assert!(lexicon.contains("עולם"));
+        assert_eq!(lexicon.words_with_value(936), ["שלום"]);
+        assert_eq!(lexicon.values().collect::<Vec<_>>(), vec![3, 706, 936]);
+    }
+
+    #[test]
+    fn test_phrases_with_value() {
+        let gmctx = GematriaContext::default();
+        let lexicon = gmctx.build_lexicon(["א", "ב", "ג", "ד", "!"]);
+
+        let phrases = lexicon.phrases_with_value(6, 3, &SearchLimits::new());
+        assert!(!phrases.is_truncated());
+        let phrases: Vec<String> = phrases.items().iter().map(|p| p.join(" ")).collect();
+        assert_eq!(phrases, vec!["א ב ג", "ב ד"]);
+
+        // א ב could have reached 6 with ג, past the depth limit
+        let phrases = lexicon.phrases_with_value(6, 3, &SearchLimits::new().with_max_depth(2));
+        assert_eq!(phrases.items(), [vec!["ב".to_string(), "ד".to_string()]]);
+        assert!(phrases.is_truncated());
+
+        let phrases = lexicon.phrases_with_value(1, 0, &SearchLimits::new());
+        assert!(phrases.items().is_empty());
+        assert!(!phrases.is_truncated());
+
+        let phrases = lexicon.phrases_with_value(3, 2, &SearchLimits::new().with_max_results(1));
+        assert_eq!(phrases.items().len(), 1);
+        assert!(phrases.is_truncated());
+        assert!(lexicon
+            .phrases_with_value(100, 4, &SearchLimits::new())
+            .items()
+            .is_empty());
+    }
+
+    #[test]
+    #[cfg(feature = "wordlist")]
+    fn test_bundled_lexicon() {
+        let lexicon = GematriaContext::default().bundled_lexicon();
+
+        assert_eq!(lexicon.len(), WORDLIST.lines().count());
+        assert!(lexicon.contains("בראשית"));
+        assert!(lexicon.words_with_value(26).contains(&"יהוה".to_string()));
+    }
+}
@@ -47,26 +47,168 @@
 //! ```
 //! Author: Amit Shmulevitch
 
+mod acronyms;
+mod aggregate;
+mod alphabet;
+#[cfg(feature = "bigint")]
+mod bigint;
+mod cache;
+#[cfg(feature = "calendar")]
+mod calendar;
+mod ciphers;
+mod concordance;
+mod config;
+#[cfg(feature = "config-files")]
+mod config_file;
+mod corpus;
+mod els;
+#[cfg(feature = "embedded")]
+pub mod embedded;
+mod english;
+mod error;
+mod extraction;
+mod gates;
+mod greek;
+mod index;
+mod lexicon;
+mod limits;
+#[cfg(feature = "mmap")]
+mod mapped;
+mod marks;
 mod methods;
+mod milestones;
+#[cfg(feature = "normalization")]
+mod normalization;
+mod numerals;
+mod paleo;
+mod parallel;
+#[cfg(feature = "tanakh")]
+mod parasha;
+mod persist;
+mod presentation;
+mod progress;
+mod reference;
+mod render;
+#[cfg(feature = "sefaria")]
+mod sefaria;
+mod sofit;
+#[cfg(feature = "sqlite")]
+mod sqlite;
+mod static_dispatch;
+mod stats;
+mod stream;
+#[cfg(feature = "tanakh")]
+mod tanakh;
+mod tokenizer;
+mod transliteration;
+mod yiddish;
+pub use aggregate::{AggregateIndex, AggregatePolicy, DocumentAggregate};
+use alphabet::SharedAlphabet;
+pub use alphabet::{AlphabetSystem, HebrewAlphabet};
+use cache::{CacheKey, CacheKind};
+pub use cache::{CacheStats, SharedCache};
+#[cfg(feature = "calendar")]
+pub use calendar::{DateGematria, GregorianDate, HebrewDate, HebrewMonth};
+pub use ciphers::{AchasBeta, Achbi, Avgad, AyakBachar, Cipher};
+pub use concordance::ConcordanceLine;
+pub use config::GematriaConfig;
+#[cfg(feature = "config-files")]
+pub use config_file::ConfigFile;
+pub use corpus::{CorpusCounts, CountMismatch, ExpectedCounts, TextCounts};
+pub use els::{ElsHit, ElsSearch, GridCell, LetterGrid};
+pub use english::{english_char_map, Agrippa, EnglishAlphabet, EnglishOrdinal, EnglishQabalah};
+pub use error::{Error, GematriaError};
+pub use extraction::{ForeignKind, HebrewExtraction, SkippedContent};
+pub use gates::{all_gates, Gate};
+#[cfg(feature = "macros")]
+pub use gematria_macros::gematria;
+pub use greek::{GreekAlphabet, Isopsephy};
+pub use index::GematriaIndex;
+pub use lexicon::Lexicon;
+#[cfg(feature = "wordlist")]
+pub use lexicon::WORDLIST;
+pub use limits::{Limited, SearchLimits};
+#[cfg(feature = "mmap")]
+pub use mapped::MappedText;
+pub use marks::{HebrewMark, MarkStripping};
 use methods::OtyiotBeMilui;
 pub use methods::{
-    std_gematria_value, GematriaCalculation, GematriaMethod, MisparGadol, MisparHechrechi,
-    MisparKatan,
+    std_gematria_value, GematriaCalculation, GematriaMethod, Kolel, MiluiTradition, MisparGadol,
+    MisparHaAkhor, MisparHaMerubahHaKlali, MisparHechrechi, MisparKatan, MisparKatanMispari,
+    MisparKidmi, MisparNeelam, MisparPerati, Modifier, Nikud, ParseMethodError,
+};
+pub use milestones::{Milestone, MilestoneKind, MilestoneTracker};
+#[cfg(feature = "normalization")]
+pub use normalization::Normalization;
+#[cfg(feature = "bigint")]
+pub use num_bigint::BigUint;
+pub use numerals::{
+    format_hebrew_year, kosher_numeral, parse_hebrew_numeral, parse_hebrew_year, to_hebrew_numeral,
+    to_hebrew_numeral_with, validate_numeral, NumeralIssue, NumeralOptions, ParseNumeralError,
+    Thousands, GERESH, GERSHAYIM, IMPLICIT_YEAR_THOUSANDS,
+};
+pub use paleo::transcribe_paleo_hebrew;
+#[cfg(feature = "tanakh")]
+pub use parasha::{find_parasha, parasha_of, Parasha, PARASHOT};
+pub use persist::{
+    IndexHeader, INDEX_FORMAT_VERSION, INDEX_MAGIC, MIN_SUPPORTED_INDEX_FORMAT_VERSION,
+};
+pub use presentation::decompose_presentation_forms;
+pub use progress::{CancellationToken, NoProgress, ProgressSink};
+pub use reference::{
+    verify_reference_values, verify_reference_values_with, ReferenceMismatch, ReferenceValue,
+    REFERENCE_VALUES,
+};
+pub use render::{
+    Cell, CsvRenderer, HtmlRenderer, JsonRenderer, MarkdownRenderer, OutputFormat, OutputRenderer,
+    PlainRenderer, TableRenderer,
+};
+#[cfg(feature = "sefaria")]
+pub use sefaria::{SefariaClient, SefariaText, SEFARIA_API_URL};
+pub use sofit::SofitPolicy;
+use sofit::SofitStrategy;
+#[cfg(feature = "sqlite")]
+pub use sqlite::{SqliteIndex, SQLITE_SCHEMA_VERSION};
+pub use static_dispatch::StaticGematria;
+pub use stats::ValueStats;
+#[cfg(feature = "tanakh")]
+pub use tanakh::{Tanakh, Verse, VerseRef};
+pub use tokenizer::{Punctuation, Tokenizer};
+pub use transliteration::Transliteration;
+pub use yiddish::decompose_yiddish_ligatures;
+
+use std::{
+    borrow::Cow,
+    collections::{BTreeMap, HashMap},
+    fmt, io,
+    ops::Range,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
 };
 
-use std::{cell::RefCell, collections::HashMap, io};
+/// A group of words with the same gematria value, clustered by anagrams.
+pub type AnagramGroup = (GematriaValue, Vec<Vec<String>>);
 
-type GematriaCtxCache = RefCell<HashMap<(GematriaMethod, String), u32>>;
+type GematriaCtxCache = SharedCache;
 
 /// `GematriaContext` holds the core logic for gematria calculations.
 /// It encapsulates the mapping of Hebrew characters to their numeric values and the chosen calculation strategy.
 /// Optionally, it can cache calculated values for improved performance and handle vowel preservation in input words.
 ///
+/// Contexts are `Send` and `Sync`, so a context may be shared between threads (e.g. behind an [`Arc`]),
+/// its cache being guarded by a lock.
+///
 /// # Fields
 /// - `character_map`: A mapping of Hebrew characters to their numeric values.
 /// - `calculation_strategy`: The current gematria calculation strategy, implemented as a trait object.
-/// - `cache`: An optional cache to store previously calculated gematria values for quick retrieval.
+/// - `cache`: An optional cache to store previously calculated gematria values for quick retrieval, shared by threads.
 /// - `preserve_vowels`: A flag to determine whether to preserve Hebrew vowels in calculation results.
+/// - `shift`: The letter shift used by shift ciphers ([`GematriaMethod::Avgad`]).
+/// - `modifiers`: The [`Modifier`]s applied on the total value of words.
+/// - `milui_spellings`: The letter spellings overriding the default ones of the milui methods.
+/// - `kolel`: The optional [`Kolel`] adjustment added to the value of texts.
 ///
 /// # Examples
 ///
@@ -124,6 +266,49 @@ pub struct GematriaContext {
 
     // Flag to determine whether to preserve vowels in calculations results.
     preserve_vowels: bool,
+
+    // The letter shift used by shift ciphers such as Avgad.
+    shift: i32,
+
+    // Modifiers applied on the total value of words, in order.
+    modifiers: Vec<Modifier>,
+
+    // Letter spellings overriding the default filled letters map.
+    milui_spellings: FullCharMap,
+
+    // Optional "im hakolel" adjustment added to the value of texts.
+    kolel: Option<Kolel>,
+
+    // Flag to recognize words marked as Hebrew numerals.
+    recognize_numerals: bool,
+
+    // Flag to strip the gershayim marks of abbreviations.
+    strip_abbreviations: bool,
+
+    // The tokenizer splitting texts into words.
+    tokenizer: Tokenizer,
+
+    // Optional Unicode normalization applied on texts before calculation.
+    #[cfg(feature = "normalization")]
+    normalization: Option<Normalization>,
+
+    // The classes of marks removed from the words of results, unless vowels are preserved.
+    mark_stripping: MarkStripping,
+
+    // The policy valuing the final forms, overriding the method.
+    sofit_policy: SofitPolicy,
+
+    // Optional transliteration of Latin words into Hebrew letters, applied before calculation.
+    transliteration: Option<Transliteration>,
+
+    // The alphabet of the context, defining its final forms and marks.
+    alphabet: Arc<dyn AlphabetSystem>,
+
+    // The hash of the settings the values depend on apart from the method, keying the cached values.
+    settings: u64,
+
+    // The identity of the custom strategy keying its cached values, 0 for the built-in methods.
+    strategy_id: u64,
 }
 
 impl Default for GematriaContext {
@@ -132,30 +317,209 @@ impl Default for GematriaContext {
     }
 }
 
+/// The type of gematria values (totals) returned by a [`GematriaContext`].
+///
+/// Values used to be `u32`, which overflows for long texts and squaring methods.
+/// Code still expecting `u32` values may use [`GematriaResult::value_u32`] while migrating.
+pub type GematriaValue = u64;
+
 /// Used to alias the standard hebrew alphabet mapping.
 pub type CharMap = HashMap<char, u32>;
 /// Used to alias the "filled letters" hebrew alphabet mapping.
 pub type FullCharMap = HashMap<char, Vec<char>>;
 
+/// The first code point of the Hebrew letters block, alef (א), which ends with tav (ת) after the final forms.
+const HEBREW_BLOCK_START: u32 = 0x05D0;
+const HEBREW_BLOCK_LEN: usize = 27;
+
+/// The highest letter index valued by the calculation methods, the final tsadi (ץ).
+const MAX_LETTER_INDEX: u32 = 27;
+
 /// `HebrewCharacterMap` maps Hebrew characters to their corresponding numeric indices.
-#[derive(Debug, Clone)]
+///
+/// Indices are 1 based, where 1-22 are the regular letters and 23-27 the final forms,
+/// and are valued by the calculation methods. The default map is the standard alphabet.
+///
+/// The indices of the Hebrew letters block (U+05D0 to U+05EA) are also kept in a table indexed by code point,
+/// so Hebrew texts are looked up without hashing.
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct HebrewCharacterMap {
     char_to_index: CharMap,
     // filled_letters: FullCharMap,
+
+    // Indices of the Hebrew letters block, derived from the mapping.
+    hebrew_block: [Option<u32>; HEBREW_BLOCK_LEN],
+}
+
+impl HebrewCharacterMap {
+    /// Creates a character map from a character to index mapping.
+    ///
+    /// The indices are not validated, see [`HebrewCharacterMap::try_new`].
+    pub fn new(char_to_index: CharMap) -> Self {
+        let mut hebrew_block = [None; HEBREW_BLOCK_LEN];
+        for (offset, index) in (HEBREW_BLOCK_START..).zip(hebrew_block.iter_mut()) {
+            *index = char::from_u32(offset).and_then(|c| char_to_index.get(&c).copied());
+        }
+        HebrewCharacterMap {
+            char_to_index,
+            hebrew_block,
+        }
+    }
+
+    /// Creates a character map from a character to index mapping, failing with
+    /// [`GematriaError::InvalidLetterIndex`] for an index outside of 1 to 27, and with
+    /// [`GematriaError::DuplicateLetterIndex`] for two Hebrew letters mapped to the same index.
+    ///
+    /// # Examples
+    /// ```
+    /// use gematria_rs::{CharMap, GematriaError, HebrewCharacterMap};
+    ///
+    /// // Other characters may stand for a Hebrew letter
+    /// let map = HebrewCharacterMap::try_new(CharMap::from([('א', 1), ('a', 1)]));
+    /// assert_eq!(map.unwrap().index_of('a'), Some(1));
+    ///
+    /// let error = HebrewCharacterMap::try_new(CharMap::from([('א', 0)])).unwrap_err();
+    /// assert_eq!(error, GematriaError::InvalidLetterIndex('א', 0));
+    /// let error = HebrewCharacterMap::try_new(CharMap::from([('מ', 13), ('ם', 13)])).unwrap_err();
+    /// assert_eq!(error, GematriaError::DuplicateLetterIndex('ם', 'מ', 13));
+    /// ```
+    pub fn try_new(char_to_index: CharMap) -> Result<Self, GematriaError> {
+        let mut pairs: Vec<(char, u32)> = char_to_index.iter().map(|(&c, &i)| (c, i)).collect();
+        pairs.sort_unstable();
+        // The Hebrew letters by index, as the milui methods look the letter of an index up
+        let mut hebrew_letters = HashMap::new();
+        for (c, index) in pairs {
+            if !(1..=MAX_LETTER_INDEX).contains(&index) {
+                return Err(GematriaError::InvalidLetterIndex(c, index));
+            }
+            if matches!(c, '\u{05D0}'..='\u{05EA}') {
+                if let Some(other) = hebrew_letters.insert(index, c) {
+                    return Err(GematriaError::DuplicateLetterIndex(other, c, index));
+                }
+            }
+        }
+        Ok(Self::new(char_to_index))
+    }
+
+    /// Creates a character map from `(character, index)` pairs.
+    ///
+    /// # Examples
+    /// ```
+    /// use gematria_rs::HebrewCharacterMap;
+    ///
+    /// let map = HebrewCharacterMap::from_pairs([('א', 1), ('ב', 2)]);
+    /// assert_eq!(map.index_of('ב'), Some(2));
+    /// assert_eq!(map.index_of('ג'), None);
+    /// ```
+    pub fn from_pairs<I: IntoIterator<Item = (char, u32)>>(pairs: I) -> Self {
+        Self::new(pairs.into_iter().collect())
+    }
+
+    /// Gets the index of a character, `None` if it is not mapped.
+    pub fn index_of(&self, character: char) -> Option<u32> {
+        self.get(character).copied()
+    }
+
+    // Util function to look a character up, in the Hebrew block table first.
+    fn get(&self, character: char) -> Option<&u32> {
+        match (character as u32).checked_sub(HEBREW_BLOCK_START) {
+            Some(offset) if (offset as usize) < HEBREW_BLOCK_LEN => {
+                self.hebrew_block[offset as usize].as_ref()
+            }
+            _ => self.char_to_index.get(&character),
+        }
+    }
+
+    /// Gets the character to index mapping.
+    pub fn char_map(&self) -> &CharMap {
+        &self.char_to_index
+    }
+}
+
+impl Default for HebrewCharacterMap {
+    fn default() -> Self {
+        Self::new(HebrewAlphabet.letters())
+    }
 }
 
 /// `GematriaResult` represents the result of a gematria calculation,
 /// including the calculated value, the method used, and the original word.
+///
+/// Results are ordered by value, then by word, method and kolel adjustment.
+/// The breakdown is not compared, as it is derived from the other fields.
+///
+/// # Examples
+/// ```
+/// use std::collections::BTreeSet;
+/// use gematria_rs::GematriaContext;
+///
+/// let gmctx = GematriaContext::default();
+/// let results: BTreeSet<_> = ["שלום", "סוד", "יין", "סוד"]
+///     .iter()
+///     .map(|w| gmctx.calculate_value(w))
+///     .collect();
+///
+/// let sorted: Vec<String> = results.iter().map(|r| r.to_string()).collect();
+/// assert_eq!(
+///     sorted,
+///     ["יין = 70 (MisparHechrechi)", "סוד = 70 (MisparHechrechi)", "שלום = 376 (MisparHechrechi)"]
+/// );
+/// ```
 #[derive(Debug, Clone)]
 pub struct GematriaResult {
     // The calculated gematria value.
-    value: u32,
+    value: GematriaValue,
 
     // The gematria calculation method used.
     method: GematriaMethod,
 
-    // The original word for which the gematria value was calculated.
+    // The word for which the gematria value was calculated, vowels are kept according to the context.
+    word: String,
+
+    // The word exactly as given to the calculation.
+    original_word: String,
+
+    // The word without vowel points and cantillation marks.
+    normalized_word: String,
+
+    // The kolel adjustment included in the value, if any.
+    kolel: Option<Kolel>,
+
+    // The characters of the word and their contribution to the value.
+    breakdown: Vec<(char, u32)>,
+
+    // The number denoted by the word, when recognized as a Hebrew numeral.
+    numeral: Option<u32>,
+
+    // Whether the word is an abbreviation whose gershayim marks were stripped.
+    abbreviation: bool,
+}
+
+/// `Match` represents a single word found while searching a text,
+/// including the matched word, its gematria value and its token index within the text.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Match {
+    // The matched word, as returned by the vowel handling of the context.
     word: String,
+
+    // The gematria value of the matched word.
+    value: GematriaValue,
+
+    // The index of the word within the tokenized text (0 based).
+    index: usize,
+
+    // The byte range of the word within the searched text.
+    byte_range: Range<usize>,
+}
+
+/// `MethodComparison` holds the values of two words under a single method, see [`GematriaContext::compare_words`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct MethodComparison {
+    // The value of the first word.
+    value_a: GematriaValue,
+
+    // The value of the second word.
+    value_b: GematriaValue,
 }
 
 /// `GematriaBuilder` provides a builder pattern for constructing [`GematriaContext`].
@@ -188,11 +552,159 @@ pub struct GematriaBuilder {
     // Flag to enable or disable caching, defaulted to false.
     enable_cache: bool,
 
+    // Optional maximum number of cached values, defaulted to unbounded.
+    cache_capacity: Option<usize>,
+
+    // Optional cache shared with other contexts, defaulted to a cache of the context.
+    shared_cache: Option<SharedCache>,
+
     // Flag to preserve or remove vowels in the input, defaulted to false.
     presevre_vowels: bool,
+
+    // Optional letter shift for shift ciphers, defaulted to 1.
+    shift: Option<i32>,
+
+    // Modifiers applied on the total value of words, defaulted to none.
+    modifiers: Vec<Modifier>,
+
+    // Letter spellings overriding the default milui spellings, in order.
+    milui_spellings: Vec<(char, String)>,
+
+    // Optional kolel adjustment, defaulted to none.
+    kolel: Option<Kolel>,
+
+    // Optional custom calculation strategy, overriding the method.
+    strategy: Option<CustomStrategy>,
+
+    // Optional character map, defaulted to the standard alphabet.
+    char_map: Option<CharMap>,
+
+    // Flag to recognize words marked as Hebrew numerals, defaulted to false.
+    recognize_numerals: bool,
+
+    // Flag to strip the gershayim marks of abbreviations, defaulted to false.
+    strip_abbreviations: bool,
+
+    // Optional tokenizer, defaulted to splitting on whitespace and the maqaf, paseq and sof pasuk.
+    tokenizer: Option<Tokenizer>,
+
+    // Optional Unicode normalization, defaulted to none.
+    #[cfg(feature = "normalization")]
+    normalization: Option<Normalization>,
+
+    // Optional classes of marks removed from words, defaulted to cantillation, niqqud and punctuation.
+    mark_stripping: Option<MarkStripping>,
+
+    // Optional policy valuing the final forms, defaulted to the method values.
+    sofit_policy: Option<SofitPolicy>,
+
+    // Optional transliteration of Latin words, defaulted to none.
+    transliteration: Option<Transliteration>,
+
+    // Optional alphabet, defaulted to the alphabet of the method (Hebrew apart from the Greek and English methods).
+    alphabet: Option<SharedAlphabet>,
+}
+
+/// A custom calculation strategy, shared so the builder remains cloneable, and its identity keying its cached values.
+#[derive(Clone)]
+struct CustomStrategy(Arc<dyn GematriaCalculation>, u64);
+
+/// The identity of the next custom strategy, 0 standing for the built-in methods.
+static NEXT_STRATEGY_ID: AtomicU64 = AtomicU64::new(1);
+
+// Utils function to get a new identity for a custom strategy.
+fn next_strategy_id() -> u64 {
+    NEXT_STRATEGY_ID.fetch_add(1, Ordering::Relaxed)
+}
+
+impl fmt::Debug for CustomStrategy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("CustomStrategy")
+            .field(&self.0.method_type())
+            .finish()
+    }
+}
+
+impl GematriaCalculation for CustomStrategy {
+    fn calculate_value(&self, letter_index: u32) -> u32 {
+        self.0.calculate_value(letter_index)
+    }
+
+    fn method_type(&self) -> GematriaMethod {
+        self.0.method_type()
+    }
+
+    fn calculate_positional_value(&self, letter_index: u32, position: usize) -> u64 {
+        self.0.calculate_positional_value(letter_index, position)
+    }
+
+    fn calculate_symbol_value(&self, symbol: char) -> Option<u32> {
+        self.0.calculate_symbol_value(symbol)
+    }
+
+    fn finalize_total(&self, total: u64) -> u64 {
+        self.0.finalize_total(total)
+    }
+
+    fn checked_finalize_total(&self, total: u64) -> Option<u64> {
+        self.0.checked_finalize_total(total)
+    }
+
+    fn calculate_extended_value(&self, letter_index: u32) -> u32 {
+        self.0.calculate_extended_value(letter_index)
+    }
+}
+
+/// The Hebrew punctuation maqaf (־), joining words.
+pub const MAQAF: char = '\u{05BE}';
+
+/// The Hebrew punctuation paseq (׀), a divider between words.
+pub const PASEQ: char = '\u{05C0}';
+
+/// The Hebrew punctuation sof pasuk (׃), ending a verse.
+pub const SOF_PASUK: char = '\u{05C3}';
+
+/// The Paleo-Hebrew (Phoenician) word separator (𐤟), a divider between words in inscriptions.
+pub const PALEO_WORD_SEPARATOR: char = '\u{1091F}';
+
+/// Whether a character separates words: whitespace, maqaf, paseq, sof pasuk or the Paleo-Hebrew word separator.
+fn is_word_separator(c: char) -> bool {
+    c.is_whitespace() || matches!(c, MAQAF | PASEQ | SOF_PASUK | PALEO_WORD_SEPARATOR)
+}
+
+/// Applies a borrowing transformation on a text which may already be owned.
+fn then_apply<'t>(text: Cow<'t, str>, apply: impl Fn(&str) -> Cow<'_, str>) -> Cow<'t, str> {
+    match text {
+        Cow::Borrowed(text) => apply(text),
+        Cow::Owned(text) => Cow::Owned(apply(&text).into_owned()),
+    }
+}
+
+/// Splits a text into words on whitespace and on the hebrew maqaf, paseq and sof pasuk.
+fn tokenize(text: &str) -> impl Iterator<Item = &str> {
+    text.split(is_word_separator).filter(|w| !w.is_empty())
+}
+
+/// Splits a text into verses on sof pasuk and on line breaks, along with the byte range of every verse.
+///
+/// Verses without any word are skipped.
+fn split_verses(text: &str) -> impl Iterator<Item = (Range<usize>, &str)> {
+    text.split([SOF_PASUK, '\n'])
+        .map(move |verse| {
+            // Verses are slices of the text
+            let start = verse.as_ptr() as usize - text.as_ptr() as usize;
+            (start..start + verse.len(), verse)
+        })
+        .filter(|(_, verse)| tokenize(verse).next().is_some())
 }
 
-/// Used to create a hebrew letter filled map, used for [`methods::GematriaMethod::OtiyotBeMilui`] calculations.
+/// Whether a character is a hebrew vowel point, cantillation mark or punctuation (U+0591 to U+05C7).
+fn is_hebrew_vowel(c: char) -> bool {
+    matches!(c, '\u{0591}'..='\u{05C7}')
+}
+
+/// Used to create a hebrew letter filled map, used for [`methods::GematriaMethod::OtiyotBeMilui`]
+/// and [`methods::GematriaMethod::MisparNeelam`] calculations.
 fn create_hebrew_filled_letters_map() -> FullCharMap {
     let full_names = vec![
         ('א', vec!['א', 'ל', 'ף']),
@@ -227,15 +739,16 @@ fn create_hebrew_filled_letters_map() -> FullCharMap {
     full_name_map
 }
 
-fn create_hebrew_index_map() -> CharMap {
-    let letters = vec![
-        'א', 'ב', 'ג', 'ד', 'ה', 'ו', 'ז', 'ח', 'ט', 'י', 'כ', 'ל', 'מ', 'נ', 'ס', 'ע', 'פ', 'צ',
-        'ק', 'ר', 'ש', 'ת', // Final forms
-        'ך', 'ם', 'ן', 'ף', 'ץ',
-    ];
+/// The hebrew alphabet in its index order, followed by the final forms.
+pub(crate) const HEBREW_LETTERS: [char; 27] = [
+    'א', 'ב', 'ג', 'ד', 'ה', 'ו', 'ז', 'ח', 'ט', 'י', 'כ', 'ל', 'מ', 'נ', 'ס', 'ע', 'פ', 'צ', 'ק',
+    'ר', 'ש', 'ת', // Final forms
+    'ך', 'ם', 'ן', 'ף', 'ץ',
+];
 
+fn create_hebrew_index_map() -> CharMap {
     let mut std_index_map = HashMap::new();
-    for (index, letter) in letters.into_iter().enumerate() {
+    for (index, letter) in HEBREW_LETTERS.into_iter().enumerate() {
         std_index_map.insert(letter, (index + 1) as u32);
     }
 
@@ -256,6 +769,34 @@ impl GematriaBuilder {
         self
     }
 
+    /// Enables caching of up to `capacity` values, evicting the least recently used value once full.
+    ///
+    /// The cache is unbounded by default, which may hold a value for every distinct word of a large corpus.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gematria_rs::GematriaBuilder;
+    ///
+    /// let gmctx = GematriaBuilder::new().with_cache_capacity(10_000).init_gematria();
+    /// assert_eq!(gmctx.calculate_value("שלום").value(), 376);
+    /// assert_eq!(gmctx.config().cache_capacity(), Some(10_000));
+    /// ```
+    pub fn with_cache_capacity(mut self, capacity: usize) -> Self {
+        self.enable_cache = true;
+        self.cache_capacity = Some(capacity);
+        self
+    }
+
+    /// Enables caching with a cache shared with other contexts, see [`SharedCache`].
+    ///
+    /// The shared cache takes precedence over [`GematriaBuilder::with_cache_capacity`].
+    pub fn with_shared_cache(mut self, cache: SharedCache) -> Self {
+        self.enable_cache = true;
+        self.shared_cache = Some(cache);
+        self
+    }
+
     /// Sets a specific method to init the [`GematriaContext`], it is defaulted to [`methods::GematriaMethod::MisparHechrechi`].
     pub fn with_method(mut self, method: GematriaMethod) -> Self {
         self.method = Some(method);
@@ -268,441 +809,2738 @@ impl GematriaBuilder {
         self
     }
 
-    /// Initializes the gematria library and returns necessary data structures.
-    pub fn init_gematria(self) -> GematriaContext {
-        let char_to_index = create_hebrew_index_map();
-        let map = HebrewCharacterMap { char_to_index };
-        let method = self.method.unwrap_or(GematriaMethod::MisparHechrechi);
-        GematriaContext::new(map, method, self.enable_cache, self.presevre_vowels)
+    /// Sets the letter shift used by [`GematriaMethod::Avgad`], it is defaulted to 1 (א→ב).
+    ///
+    /// Negative values shift backwards, e.g. `-1` is the reverse Avgad (ב→א).
+    pub fn with_shift(mut self, shift: i32) -> Self {
+        self.shift = Some(shift);
+        self
     }
-}
-
-// Utils function to parse the method of gematria.
-fn process_method_dyn(
-    method: GematriaMethod,
-    char_map: HebrewCharacterMap,
-) -> Box<dyn GematriaCalculation> {
-    let strategy: Box<dyn GematriaCalculation> = match method {
-        GematriaMethod::MisparHechrechi => Box::new(MisparHechrechi),
-        GematriaMethod::MisparGadol => Box::new(MisparGadol),
-        GematriaMethod::MisparKatan => Box::new(MisparKatan),
-        GematriaMethod::OtiyotBeMilui => Box::new(OtyiotBeMilui::new(
-            create_hebrew_filled_letters_map(),
-            char_map.char_to_index,
-        )),
-        _ => unimplemented!(
-            "{:?} is not yet implemented to calculate gematria values.",
-            method
-        ),
-    };
-
-    strategy
-}
-
-impl GematriaContext {
-    pub fn new(
-        char_map: HebrewCharacterMap,
-        method: GematriaMethod,
-        enable_cache: bool,
-        preserve_vowels: bool,
-    ) -> Self {
-        let strategy = process_method_dyn(method, char_map.clone());
-
-        let cache = if enable_cache {
-            Some(RefCell::new(HashMap::new()))
-        } else {
-            None
-        };
 
-        Self {
-            character_map: char_map,
-            calculation_strategy: strategy,
-            cache,
-            preserve_vowels,
-        }
+    /// Adds a [`Modifier`] applied on the total value of words, after the previously added modifiers.
+    pub fn with_modifier(mut self, modifier: Modifier) -> Self {
+        self.modifiers.push(modifier);
+        self
     }
 
-    /// Processing different hebrew vowels, will check against the flags passed to `GematriaContext`.
-    fn handle_vowels(&self, word: &str) -> String {
-        if self.preserve_vowels {
-            word.to_string()
-        } else {
-            self.remove_hebrew_vowels(word)
+    /// Selects the spellings of ה and ו used by the milui methods ([`GematriaMethod::OtiyotBeMilui`],
+    /// [`GematriaMethod::MiluiHaMilui`] and [`GematriaMethod::MisparNeelam`]).
+    ///
+    /// # Examples
+    /// ```
+    /// use gematria_rs::{GematriaBuilder, GematriaMethod, MiluiTradition};
+    ///
+    /// let gmctx = GematriaBuilder::new()
+    ///     .with_method(GematriaMethod::OtiyotBeMilui)
+    ///     .with_milui_tradition(MiluiTradition::Sag)
+    ///     .init_gematria();
+    /// assert_eq!(gmctx.calculate_value("יהוה").value(), 63);
+    /// ```
+    pub fn with_milui_tradition(mut self, tradition: MiluiTradition) -> Self {
+        for (letter, spelling) in tradition.spellings() {
+            self.milui_spellings.push((letter, spelling.to_string()));
         }
+        self
     }
 
-    /// Removes all hebrew vowels from text.
-    fn remove_hebrew_vowels(&self, text: &str) -> String {
-        text.chars().filter(|&c| !self.is_hebrew_vowel(c)).collect()
+    /// Overrides the full spelling of a single letter used by the milui methods,
+    /// after the previously selected tradition and spellings.
+    pub fn with_milui_spelling(mut self, letter: char, spelling: &str) -> Self {
+        self.milui_spellings.push((letter, spelling.to_string()));
+        self
     }
 
-    /// Wheter this [`char`] vowel is included with vowel.
-    fn is_hebrew_vowel(&self, c: char) -> bool {
-        matches!(c, '\u{0591}'..='\u{05C7}')
+    /// Adds a [`Kolel`] adjustment to the value of texts ("im hakolel").
+    ///
+    /// # Examples
+    /// ```
+    /// use gematria_rs::{GematriaBuilder, Kolel};
+    ///
+    /// let gmctx = GematriaBuilder::new().with_kolel(Kolel::Words).init_gematria();
+    /// let result = gmctx.calculate_value("בעזרת השם");
+    ///
+    /// assert_eq!(result.value(), 1026);
+    /// assert_eq!(result.kolel(), Some(Kolel::Words));
+    /// ```
+    pub fn with_kolel(mut self, kolel: Kolel) -> Self {
+        self.kolel = Some(kolel);
+        self
     }
 
-    /// Gets the hebrew char index within the alphabet order (1 based).
-    fn get_indices_for_word(&self, word: &str) -> Vec<u32> {
-        word.chars()
-            .filter_map(|c| self.character_map.char_to_index.get(&c))
-            .cloned()
-            .collect()
+    /// Injects a custom calculation strategy, overriding the method set with [`GematriaBuilder::with_method`].
+    ///
+    /// Custom strategies should report [`GematriaMethod::Custom`] as their method type.
+    ///
+    /// # Examples
+    /// ```
+    /// use gematria_rs::{GematriaBuilder, GematriaCalculation, GematriaMethod};
+    ///
+    /// // Values every letter as 1, counting the letters of a word
+    /// struct LetterCount;
+    ///
+    /// impl GematriaCalculation for LetterCount {
+    ///     fn calculate_value(&self, _letter_index: u32) -> u32 {
+    ///         1
+    ///     }
+    ///
+    ///     fn method_type(&self) -> GematriaMethod {
+    ///         GematriaMethod::Custom
+    ///     }
+    /// }
+    ///
+    /// let gmctx = GematriaBuilder::new()
+    ///     .with_strategy(Box::new(LetterCount))
+    ///     .init_gematria();
+    /// assert_eq!(gmctx.calculate_value("שלום").value(), 4);
+    /// assert_eq!(gmctx.get_current_method(), GematriaMethod::Custom);
+    /// ```
+    pub fn with_strategy(mut self, strategy: Box<dyn GematriaCalculation>) -> Self {
+        self.strategy = Some(CustomStrategy(Arc::from(strategy), next_strategy_id()));
+        self
     }
 
-    /// Util function for calculate gematria value without using cache.
-    fn calculate_value_no_cache(&self, word: &str) -> u32 {
-        self.get_indices_for_word(word)
-            .iter()
-            .map(|&index| self.calculation_strategy.calculate_value(index))
-            .sum()
+    /// Overrides the character to index assignments, e.g. to value other characters as Hebrew letters.
+    ///
+    /// The initialization fails for an index outside of 1 to 27, or for two Hebrew letters mapped
+    /// to the same index, see [`HebrewCharacterMap::try_new`].
+    ///
+    /// # Examples
+    /// ```
+    /// use gematria_rs::{GematriaBuilder, GematriaError, GematriaMethod, HebrewCharacterMap};
+    ///
+    /// // Latin letters stand for ש, ל, ו and ם
+    /// let mut char_map = HebrewCharacterMap::default().char_map().clone();
+    /// char_map.extend([('s', 21), ('l', 12), ('o', 6), ('m', 24)]);
+    ///
+    /// let gmctx = GematriaBuilder::new()
+    ///     .with_method(GematriaMethod::MisparGadol)
+    ///     .with_char_map(char_map.clone())
+    ///     .init_gematria();
+    /// assert_eq!(gmctx.calculate_value("slom").value(), 936);
+    /// assert_eq!(gmctx.calculate_value("שלום").value(), 936);
+    ///
+    /// // Final mem (index 24) may not be mapped as a regular mem (index 13), see `with_sofit_policy`
+    /// char_map.insert('ם', 13);
+    /// let result = GematriaBuilder::new().with_char_map(char_map).try_init_gematria();
+    /// assert!(matches!(result, Err(GematriaError::DuplicateLetterIndex('ם', 'מ', 13))));
+    /// ```
+    pub fn with_char_map(mut self, char_map: CharMap) -> Self {
+        self.char_map = Some(char_map);
+        self
     }
 
-    /// Gets the current method used to calculate Gematria on the current [`GematriaContext`].
-    pub fn get_current_method(&self) -> GematriaMethod {
-        self.calculation_strategy.method_type()
+    /// Recognizes words marked as Hebrew numerals with a gershayim or geresh (such as תשפ״ד or י״ד),
+    /// reporting the number they denote on [`GematriaResult::numeral`] along with their letter sum.
+    ///
+    /// Marked words which are not valid numerals, such as the abbreviation צה״ל, are left as plain words.
+    pub fn with_numerals(mut self, recognize: bool) -> Self {
+        self.recognize_numerals = recognize;
+        self
     }
 
-    /// Calculates the gematria value of a single Hebrew character.
-    pub fn calculate_char_value(&self, character: char) -> u32 {
-        let method = self.get_current_method();
-        let cache_key = (method, character.to_string());
+    /// Strips the geresh and gershayim marks placed inside words (such as צה״ל or רמב״ם), keeping the
+    /// words whole and flagging their results with [`GematriaResult::is_abbreviation`].
+    ///
+    /// Words recognized as numerals (see [`GematriaBuilder::with_numerals`]) are not abbreviations.
+    pub fn with_abbreviations(mut self, strip: bool) -> Self {
+        self.strip_abbreviations = strip;
+        self
+    }
 
-        // Check if value is in cache
-        if let Some(ref cache) = self.cache {
-            let cache = cache.borrow();
-            if let Some(&value) = cache.get(&cache_key) {
-                return value;
-            }
+    /// Sets the [`Tokenizer`] splitting texts into words, e.g. to keep words joined by a maqaf
+    /// or to handle the punctuation of modern texts.
+    ///
+    /// # Examples
+    /// ```
+    /// use gematria_rs::{GematriaBuilder, Punctuation, Tokenizer};
+    ///
+    /// let gmctx = GematriaBuilder::new()
+    ///     .with_tokenizer(Tokenizer::new().with_punctuation(Punctuation::Strip))
+    ///     .init_gematria();
+    ///
+    /// let found = gmctx.search_matching_values(&376, "אמר: שלום, עולם.");
+    /// assert_eq!(found[0].word(), "שלום");
+    /// ```
+    pub fn with_tokenizer(mut self, tokenizer: Tokenizer) -> Self {
+        self.tokenizer = Some(tokenizer);
+        self
+    }
+
+    /// Normalizes texts with a Unicode normalization form before calculation, so equal looking
+    /// strings (decomposed or precomposed) always yield equal values.
+    ///
+    /// # Examples
+    /// ```
+    /// use gematria_rs::{GematriaBuilder, Normalization};
+    ///
+    /// let gmctx = GematriaBuilder::new()
+    ///     .with_normalization(Normalization::Nfkc)
+    ///     .init_gematria();
+    ///
+    /// // The alef-lamed ligature is valued as its two letters
+    /// assert_eq!(gmctx.calculate_value("\u{FB4F}").value(), 31);
+    /// ```
+    #[cfg(feature = "normalization")]
+    pub fn with_normalization(mut self, normalization: Normalization) -> Self {
+        self.normalization = Some(normalization);
+        self
+    }
+
+    /// Selects the classes of Hebrew marks (cantillation, niqqud, punctuation, geresh and gershayim)
+    /// removed from the words of results, see [`MarkStripping`].
+    ///
+    /// It applies unless vowels are preserved with [`GematriaBuilder::with_vowels`], which keeps all the marks.
+    pub fn with_mark_stripping(mut self, mark_stripping: MarkStripping) -> Self {
+        self.mark_stripping = Some(mark_stripping);
+        self
+    }
+
+    /// Sets how the final forms (ך, ם, ן, ף, ץ) are valued, overriding the default of the method
+    /// (and of a custom strategy), see [`SofitPolicy`].
+    pub fn with_sofit_policy(mut self, sofit_policy: SofitPolicy) -> Self {
+        self.sofit_policy = Some(sofit_policy);
+        self
+    }
+
+    /// Sets the alphabet of the context: its letters (unless a character map is given), final forms and marks,
+    /// see [`AlphabetSystem`].
+    ///
+    /// Defaults to the alphabet of the method: [`GreekAlphabet`] for [`GematriaMethod::Isopsephy`],
+    /// [`EnglishAlphabet`] for the English methods and [`HebrewAlphabet`] otherwise.
+    pub fn with_alphabet<A: AlphabetSystem + 'static>(mut self, alphabet: A) -> Self {
+        self.alphabet = Some(SharedAlphabet(Arc::new(alphabet)));
+        self
+    }
+
+    /// Uses the Greek alphabet, to calculate [`GematriaMethod::Isopsephy`]
+    /// with or without its archaic numerals, see [`GreekAlphabet`].
+    ///
+    /// Contexts calculating isopsephy use the full Greek alphabet by default.
+    pub fn with_greek_alphabet(self, alphabet: GreekAlphabet) -> Self {
+        self.with_alphabet(alphabet)
+    }
+
+    /// Transliterates the Latin words of texts (such as "shalom") into Hebrew letters before calculation,
+    /// with the given scheme, see [`Transliteration`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gematria_rs::{GematriaBuilder, Transliteration};
+    ///
+    /// let gmctx = GematriaBuilder::new()
+    ///     .with_transliteration(Transliteration::new())
+    ///     .init_gematria();
+    ///
+    /// assert_eq!(gmctx.calculate_value("shalom").value(), 376);
+    /// ```
+    pub fn with_transliteration(mut self, transliteration: Transliteration) -> Self {
+        self.transliteration = Some(transliteration);
+        self
+    }
+
+    /// Initializes the gematria library and returns necessary data structures.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the method has no built-in implementation, see [`GematriaBuilder::try_init_gematria`].
+    pub fn init_gematria(self) -> GematriaContext {
+        self.try_init_gematria().unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// Initializes the gematria library, failing with [`GematriaError::UnsupportedMethod`]
+    /// if the method has no built-in implementation.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gematria_rs::{GematriaBuilder, GematriaError, GematriaMethod};
+    ///
+    /// let result = GematriaBuilder::new()
+    ///     .with_method(GematriaMethod::MisparSiduri)
+    ///     .try_init_gematria();
+    ///
+    /// assert!(matches!(
+    ///     result,
+    ///     Err(GematriaError::UnsupportedMethod(GematriaMethod::MisparSiduri))
+    /// ));
+    /// ```
+    pub fn try_init_gematria(self) -> Result<GematriaContext, GematriaError> {
+        let method = match self.method {
+            // The custom strategy is injected once the context is initialized
+            Some(GematriaMethod::Custom) | None => GematriaMethod::MisparHechrechi,
+            Some(method) => method,
+        };
+        let alphabet = match self.alphabet {
+            Some(SharedAlphabet(alphabet)) => alphabet,
+            None => default_alphabet(method),
+        };
+        let map = match self.char_map {
+            Some(char_map) => HebrewCharacterMap::try_new(char_map)?,
+            None => HebrewCharacterMap::new(alphabet.letters()),
+        };
+        let mut ctx =
+            GematriaContext::try_new(map, method, self.enable_cache, self.presevre_vowels)?;
+        match (self.enable_cache, self.shared_cache, self.cache_capacity) {
+            (true, Some(cache), _) => ctx.cache = Some(cache),
+            (true, None, Some(capacity)) => ctx.cache = Some(SharedCache::with_capacity(capacity)),
+            _ => {}
+        }
+        if let Some(shift) = self.shift {
+            ctx.set_shift(shift);
+        }
+        ctx.modifiers = self.modifiers;
+        ctx.kolel = self.kolel;
+        ctx.recognize_numerals = self.recognize_numerals;
+        ctx.strip_abbreviations = self.strip_abbreviations;
+        if let Some(tokenizer) = self.tokenizer {
+            ctx.tokenizer = tokenizer;
+        }
+        #[cfg(feature = "normalization")]
+        {
+            ctx.normalization = self.normalization;
         }
+        if let Some(mark_stripping) = self.mark_stripping {
+            ctx.mark_stripping = mark_stripping;
+        }
+        ctx.alphabet = alphabet;
+        ctx.sofit_policy = self.sofit_policy.unwrap_or_default();
+        ctx.refresh_strategy();
+        ctx.transliteration = self.transliteration;
+        if let Some(strategy) = self.strategy {
+            let id = strategy.1;
+            ctx.replace_strategy(Box::new(strategy), id);
+        }
+        for (letter, spelling) in self.milui_spellings {
+            ctx.set_milui_spelling(letter, &spelling);
+        }
+        ctx.refresh_settings();
 
-        // Calculate and cache the value if not found
-        if let Some(index) = self.get_character_index(&character) {
-            let value = self.calculation_strategy.calculate_value(*index);
-            if let Some(ref cache) = self.cache {
-                cache.borrow_mut().insert(cache_key, value);
-            }
-            value
+        Ok(ctx)
+    }
+}
+
+// Utils function to get the alphabet valued by a method, the Hebrew alphabet apart from the Greek and English methods.
+pub(crate) fn default_alphabet(method: GematriaMethod) -> Arc<dyn AlphabetSystem> {
+    match method {
+        GematriaMethod::Isopsephy => Arc::new(GreekAlphabet::default()),
+        GematriaMethod::EnglishOrdinal
+        | GematriaMethod::EnglishQabalah
+        | GematriaMethod::Agrippa => Arc::new(EnglishAlphabet),
+        _ => Arc::new(HebrewAlphabet),
+    }
+}
+
+// Utils function to parse the method of gematria.
+fn process_method_dyn(
+    method: GematriaMethod,
+    char_map: HebrewCharacterMap,
+    shift: i32,
+    milui_spellings: &FullCharMap,
+    sofit_policy: SofitPolicy,
+    alphabet: &Arc<dyn AlphabetSystem>,
+) -> Result<Box<dyn GematriaCalculation>, GematriaError> {
+    let filled_letters_map = || {
+        let mut map = create_hebrew_filled_letters_map();
+        map.extend(milui_spellings.clone());
+        map
+    };
+    let strategy: Box<dyn GematriaCalculation> = match method {
+        GematriaMethod::MisparHechrechi => Box::new(MisparHechrechi),
+        GematriaMethod::MisparGadol => Box::new(MisparGadol),
+        GematriaMethod::MisparKatan => Box::new(MisparKatan),
+        GematriaMethod::Achbi => Box::new(Achbi),
+        GematriaMethod::Avgad => Box::new(Avgad::new(shift)),
+        GematriaMethod::AyakBachar => Box::new(AyakBachar),
+        GematriaMethod::AchasBeta => Box::new(AchasBeta),
+        GematriaMethod::MisparKidmi => Box::new(MisparKidmi),
+        GematriaMethod::MisparPerati => Box::new(MisparPerati),
+        GematriaMethod::MisparHaMerubahHaKlali => Box::new(MisparHaMerubahHaKlali),
+        GematriaMethod::MisparKatanMispari => Box::new(MisparKatanMispari),
+        GematriaMethod::MisparHaAkhor => Box::new(MisparHaAkhor),
+        GematriaMethod::Nikud => Box::new(Nikud),
+        GematriaMethod::Isopsephy => Box::new(Isopsephy),
+        GematriaMethod::EnglishOrdinal => Box::new(EnglishOrdinal),
+        GematriaMethod::EnglishQabalah => Box::new(EnglishQabalah),
+        GematriaMethod::Agrippa => Box::new(Agrippa),
+        GematriaMethod::OtiyotBeMilui => Box::new(OtyiotBeMilui::new(
+            filled_letters_map(),
+            char_map.char_to_index,
+        )),
+        GematriaMethod::MiluiHaMilui => {
+            Box::new(OtyiotBeMilui::new(filled_letters_map(), char_map.char_to_index).with_depth(2))
+        }
+        GematriaMethod::MisparNeelam => Box::new(MisparNeelam::new(
+            filled_letters_map(),
+            char_map.char_to_index,
+        )),
+        _ => return Err(GematriaError::UnsupportedMethod(method)),
+    };
+
+    Ok(SofitStrategy::wrap(strategy, sofit_policy, alphabet))
+}
+
+impl GematriaContext {
+    /// Creates a context calculating with the given method.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the method has no built-in implementation, see [`GematriaContext::try_new`].
+    pub fn new(
+        char_map: HebrewCharacterMap,
+        method: GematriaMethod,
+        enable_cache: bool,
+        preserve_vowels: bool,
+    ) -> Self {
+        Self::try_new(char_map, method, enable_cache, preserve_vowels)
+            .unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// Creates a context calculating with the given method, failing with
+    /// [`GematriaError::UnsupportedMethod`] if the method has no built-in implementation.
+    pub fn try_new(
+        char_map: HebrewCharacterMap,
+        method: GematriaMethod,
+        enable_cache: bool,
+        preserve_vowels: bool,
+    ) -> Result<Self, GematriaError> {
+        let shift = Avgad::DEFAULT_SHIFT;
+        let milui_spellings = HashMap::new();
+        let sofit_policy = SofitPolicy::default();
+        let alphabet = default_alphabet(method);
+        let strategy = process_method_dyn(
+            method,
+            char_map.clone(),
+            shift,
+            &milui_spellings,
+            sofit_policy,
+            &alphabet,
+        )?;
+
+        let cache = if enable_cache {
+            Some(SharedCache::new())
         } else {
-            0
+            None
+        };
+
+        let mut ctx = Self {
+            character_map: char_map,
+            calculation_strategy: strategy,
+            cache,
+            preserve_vowels,
+            shift,
+            modifiers: Vec::new(),
+            milui_spellings,
+            kolel: None,
+            recognize_numerals: false,
+            strip_abbreviations: false,
+            tokenizer: Tokenizer::default(),
+            #[cfg(feature = "normalization")]
+            normalization: None,
+            mark_stripping: MarkStripping::default(),
+            sofit_policy,
+            transliteration: None,
+            alphabet,
+            settings: 0,
+            strategy_id: 0,
+        };
+        ctx.refresh_settings();
+
+        Ok(ctx)
+    }
+
+    /// Processing different hebrew vowels, will check against the flags passed to `GematriaContext`.
+    /// Hebrew marks are stripped following the mark stripping, the marks of other alphabets are removed.
+    fn handle_vowels(&self, word: &str) -> String {
+        if self.preserve_vowels {
+            return word.to_string();
+        }
+        word.chars()
+            .filter(|&c| {
+                !self.mark_stripping.strips(c) && (is_hebrew_vowel(c) || !self.alphabet.is_mark(c))
+            })
+            .collect()
+    }
+
+    /// Transliterates the Latin words of a text with the transliteration of the context, if any,
+    /// decomposes its Hebrew presentation forms (and its Yiddish ligatures in Yiddish mode),
+    /// transcribes its Paleo-Hebrew letters and normalizes it with the Unicode normalization
+    /// form of the context, if any.
+    fn normalize<'t>(&self, text: &'t str) -> Cow<'t, str> {
+        let text = match self.transliteration {
+            Some(ref transliteration) => transliteration.apply(text),
+            None => Cow::Borrowed(text),
+        };
+        let text = then_apply(text, decompose_presentation_forms);
+        let text = match self.tokenizer.yiddish() {
+            true => then_apply(text, decompose_yiddish_ligatures),
+            false => text,
+        };
+        let text = then_apply(text, transcribe_paleo_hebrew);
+        #[cfg(feature = "normalization")]
+        let text = match self.normalization {
+            Some(normalization) => then_apply(text, |text| normalization.apply(text)),
+            None => text,
+        };
+        text
+    }
+
+    /// Removes all hebrew vowels from text.
+    fn remove_hebrew_vowels(&self, text: &str) -> String {
+        text.chars().filter(|&c| !self.is_hebrew_vowel(c)).collect()
+    }
+
+    /// Whether a character is a Hebrew vowel point or a mark of the alphabet of the context.
+    fn is_hebrew_vowel(&self, c: char) -> bool {
+        is_hebrew_vowel(c) || self.alphabet.is_mark(c)
+    }
+
+    /// Gets the hebrew char index within the alphabet order (1 based).
+    fn get_indices_for_word(&self, word: &str) -> Vec<u32> {
+        word.chars()
+            .filter_map(|c| self.character_map.get(c))
+            .cloned()
+            .collect()
+    }
+
+    /// Util function for calculate gematria value of a normalized text without using cache, `None` on overflow.
+    fn calculate_value_no_cache(&self, word: &str) -> Option<u64> {
+        self.checked_text_value(self.calculation_strategy.as_ref(), word)
+    }
+
+    /// Gets the letter indices of every word of a normalized text, splitting the text once for all methods.
+    fn get_word_letters(&self, text: &str) -> Vec<Vec<u32>> {
+        self.tokenizer
+            .tokenize(text)
+            .map(|w| self.get_indices_for_word(w))
+            .collect()
+    }
+
+    /// Calculates the value of a normalized text with the given strategy, from the letter indices of its words.
+    ///
+    /// A value overflowing a `u64` saturates at `u64::MAX`.
+    fn calculate_letters_value(
+        &self,
+        strategy: &dyn GematriaCalculation,
+        letters: &[Vec<u32>],
+        text: &str,
+    ) -> u64 {
+        // Letters are valued by their position within their own word
+        let total = checked_sum(letters.iter().flat_map(|indices| {
+            indices
+                .iter()
+                .enumerate()
+                .map(|(i, &index)| strategy.calculate_positional_value(index, i + 1))
+        }));
+        total
+            .and_then(|total| self.checked_complete_value(strategy, total, text))
+            .unwrap_or(u64::MAX)
+    }
+
+    /// Same as `calculate_letters_value`, looking the letters up while valuing them
+    /// rather than collecting their indices first, so a single method values a text without allocating.
+    fn calculate_text_value(&self, strategy: &dyn GematriaCalculation, text: &str) -> u64 {
+        self.checked_text_value(strategy, text).unwrap_or(u64::MAX)
+    }
+
+    /// Same as `calculate_text_value`, `None` when the value overflows a `u64`.
+    fn checked_text_value(&self, strategy: &dyn GematriaCalculation, text: &str) -> Option<u64> {
+        let total = self
+            .tokenizer
+            .tokenize(text)
+            .try_fold(0u64, |total, word| {
+                let word_total = checked_sum(
+                    word.chars()
+                        .filter_map(|c| self.get_character_index(&c))
+                        .enumerate()
+                        .map(|(i, &index)| strategy.calculate_positional_value(index, i + 1)),
+                )?;
+                total.checked_add(word_total)
+            })?;
+        self.checked_complete_value(strategy, total, text)
+    }
+
+    /// Completes the total of the letters of a normalized text with the values of its other characters,
+    /// the finalization of the method, the modifiers and the kolel adjustment, `None` on overflow.
+    fn checked_complete_value(
+        &self,
+        strategy: &dyn GematriaCalculation,
+        letters_total: u64,
+        text: &str,
+    ) -> Option<u64> {
+        let symbols_total = checked_sum(
+            text.chars()
+                .filter(|c| self.get_character_index(c).is_none())
+                .filter_map(|c| strategy.calculate_symbol_value(c))
+                .map(u64::from),
+        )?;
+        let total = strategy.checked_finalize_total(letters_total.checked_add(symbols_total)?)?;
+        let total = self
+            .modifiers
+            .iter()
+            .try_fold(total, |value, modifier| modifier.checked_apply(value))?;
+
+        match self.kolel {
+            Some(kolel) => total.checked_add(self.kolel_amount(text, kolel)),
+            None => Some(total),
+        }
+    }
+
+    /// Calculates the amount a kolel adjustment adds to a text.
+    fn kolel_amount(&self, text: &str, kolel: Kolel) -> u64 {
+        match kolel {
+            Kolel::One => 1,
+            Kolel::Words => self
+                .tokenizer
+                .tokenize(text)
+                .filter(|word| word.chars().any(|c| self.get_character_index(&c).is_some()))
+                .count() as u64,
+            Kolel::Letters => text
+                .chars()
+                .filter(|c| self.get_character_index(c).is_some())
+                .count() as u64,
+        }
+    }
+
+    /// Gets the character map used by the context.
+    pub fn character_map(&self) -> &HebrewCharacterMap {
+        &self.character_map
+    }
+
+    /// Gets the alphabet of the context.
+    pub fn alphabet(&self) -> &dyn AlphabetSystem {
+        self.alphabet.as_ref()
+    }
+
+    /// Gets the tokenizer splitting texts into words.
+    pub fn tokenizer(&self) -> &Tokenizer {
+        &self.tokenizer
+    }
+
+    /// Gets the current method used to calculate Gematria on the current [`GematriaContext`].
+    pub fn get_current_method(&self) -> GematriaMethod {
+        self.calculation_strategy.method_type()
+    }
+
+    /// Calculates the gematria value of a single Hebrew character.
+    pub fn calculate_char_value(&self, character: char) -> GematriaValue {
+        let method = self.get_current_method();
+        let cache_key = (
+            method,
+            self.settings,
+            self.strategy_id,
+            CacheKind::Char,
+            character.to_string(),
+        );
+
+        // Check if value is in cache
+        if let Some(value) = self.cached_value(&cache_key) {
+            return value;
         }
+
+        // Calculate and cache the value if not found
+        let value = match self.get_character_index(&character) {
+            Some(index) => self.calculation_strategy.calculate_value(*index) as u64,
+            None => match self.calculation_strategy.calculate_symbol_value(character) {
+                Some(value) => value as u64,
+                None => return 0,
+            },
+        };
+        self.cache_value(cache_key, value);
+        value
     }
 
     /// Calculates the gematria value of a Hebrew word or phrase.
-    pub fn calculate_value(&self, text: &str) -> GematriaResult {
+    ///
+    /// The value is calculated on the text as given, so methods valuing vowel points ([`GematriaMethod::Nikud`])
+    /// see them even when vowels are removed from the resulting word.
+    ///
+    /// A value overflowing a `u64` (such as a squared total of a long text) saturates at `u64::MAX`,
+    /// see [`GematriaContext::calculate_value_checked`].
+    pub fn calculate_value(&self, original: &str) -> GematriaResult {
+        let (result, _) = self.calculate_result(original);
+        result
+    }
+
+    /// Same as [`GematriaContext::calculate_value`], failing with [`GematriaError::Overflow`]
+    /// when the value overflows a `u64` rather than saturating.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gematria_rs::{GematriaBuilder, GematriaContext, GematriaError, Modifier};
+    ///
+    /// let gmctx = GematriaContext::default();
+    /// assert_eq!(gmctx.calculate_value_checked("שלום")?.value(), 376);
+    ///
+    /// let gmctx = GematriaBuilder::new()
+    ///     .with_modifier(Modifier::SquareTotal)
+    ///     .with_modifier(Modifier::SquareTotal)
+    ///     .with_modifier(Modifier::SquareTotal)
+    ///     .init_gematria();
+    /// assert_eq!(gmctx.calculate_value_checked("שלום"), Err(GematriaError::Overflow));
+    /// assert_eq!(gmctx.calculate_value("שלום").value(), u64::MAX);
+    /// # Ok::<(), GematriaError>(())
+    /// ```
+    pub fn calculate_value_checked(&self, text: &str) -> Result<GematriaResult, GematriaError> {
+        match self.calculate_result(text) {
+            (result, false) => Ok(result),
+            (_, true) => Err(GematriaError::Overflow),
+        }
+    }
+
+    /// Calculates the result of a text, along with whether its value overflowed (and saturated).
+    fn calculate_result(&self, original: &str) -> (GematriaResult, bool) {
         let method = self.get_current_method();
-        let processed_text = self.handle_vowels(text);
+        let normalized = self.normalize(original);
+        let (val, numeral, abbreviation) = self.calculate_normalized(&normalized);
+        let text = abbreviation.as_deref().unwrap_or(&normalized);
+
+        let result = GematriaResult::new(val.unwrap_or(u64::MAX), method, self.handle_vowels(text))
+            .with_original_word(original.to_string())
+            .with_kolel(self.kolel)
+            .with_breakdown(self.letter_breakdown(self.calculation_strategy.as_ref(), text))
+            .with_numeral(numeral)
+            .with_abbreviation(abbreviation.is_some());
+        (result, val.is_none())
+    }
+
+    /// Calculates the gematria value of a Hebrew word or phrase, without building a [`GematriaResult`].
+    ///
+    /// The value is the value of [`GematriaContext::calculate_value`], calculated without allocating
+    /// unless the text needs normalizing (such as transliterated or presentation form text):
+    /// the cache is bypassed and no word, breakdown or original word is kept, for hot loops over large texts.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gematria_rs::GematriaContext;
+    ///
+    /// let gmctx = GematriaContext::default();
+    /// assert_eq!(gmctx.calculate_value_raw("שָׁלוֹם"), 376);
+    /// assert_eq!(
+    ///     gmctx.calculate_value_raw("בראשית ברא"),
+    ///     gmctx.calculate_value("בראשית ברא").value()
+    /// );
+    /// ```
+    pub fn calculate_value_raw(&self, text: &str) -> GematriaValue {
+        let text = self.normalize(text);
+        self.calculate_text_value(self.calculation_strategy.as_ref(), &text)
+    }
+
+    /// Calculates the value of a normalized text (`None` on overflow), using the cache if enabled,
+    /// along with the number it denotes and the text without its abbreviation marks, if any.
+    fn calculate_normalized(&self, normalized: &str) -> (Option<u64>, Option<u32>, Option<String>) {
+        let (numeral, abbreviation) = self.numeral_or_abbreviation(normalized);
+        let text = abbreviation.as_deref().unwrap_or(normalized);
+
         // Check if caching is enabled and use it if available
-        if let Some(ref cache) = self.cache {
-            let mut cache = cache.borrow_mut();
-            if let Some(&value) = cache.get(&(method, processed_text.to_string())) {
-                return GematriaResult::new(value, method, processed_text.to_owned());
+        let value = match self.cache {
+            Some(_) => {
+                let cache_key = (
+                    self.get_current_method(),
+                    self.settings,
+                    self.strategy_id,
+                    CacheKind::Text,
+                    text.to_string(),
+                );
+                match self.cached_value(&cache_key) {
+                    Some(value) => Some(value),
+                    None => {
+                        let value = self.calculate_value_no_cache(text);
+                        if let Some(value) = value {
+                            self.cache_value(cache_key, value);
+                        }
+                        value
+                    }
+                }
             }
+            None => self.calculate_value_no_cache(text),
+        };
+
+        (value, numeral, abbreviation)
+    }
+
+    /// Calculates the value of a word along with the word as found in results, normalizing it once
+    /// and without the breakdown and original word of a full [`GematriaResult`], for searches and grouping.
+    fn word_value(&self, word: &str) -> (u64, String) {
+        let normalized = self.normalize(word);
+        let (value, _, abbreviation) = self.calculate_normalized(&normalized);
+        let text = abbreviation.as_deref().unwrap_or(&normalized);
+        (value.unwrap_or(u64::MAX), self.handle_vowels(text))
+    }
+
+    /// Gets the number denoted by a normalized text, or the text without its abbreviation marks,
+    /// the text being valued as the abbreviation.
+    fn numeral_or_abbreviation(&self, normalized: &str) -> (Option<u32>, Option<String>) {
+        let numeral = self.recognize_numeral(normalized);
+        // Numerals are kept as written, other marked words may be abbreviations
+        let abbreviation = match numeral {
+            Some(_) => None,
+            None => self.strip_abbreviation(normalized),
+        };
+        (numeral, abbreviation)
+    }
 
-            let val = self.calculate_value_no_cache(&processed_text);
-            cache.insert((method, processed_text.to_string()), val);
-            return GematriaResult::new(val, self.get_current_method(), processed_text.to_owned());
+    /// Gets a text without the gershayim marks of its abbreviations, when abbreviations are stripped.
+    fn strip_abbreviation(&self, text: &str) -> Option<String> {
+        if !self.strip_abbreviations {
+            return None;
         }
+        numerals::strip_abbreviation_marks(text)
+    }
 
-        // Calculate without cache
-        let val = self.calculate_value_no_cache(&processed_text);
-        GematriaResult::new(val, self.get_current_method(), processed_text.to_owned())
+    /// Gets the number denoted by a text when numerals are recognized and it is a marked numeral.
+    fn recognize_numeral(&self, text: &str) -> Option<u32> {
+        if !self.recognize_numerals || self.tokenizer.tokenize(text).nth(1).is_some() {
+            return None;
+        }
+        numerals::marked_numeral(&self.remove_hebrew_vowels(text))
     }
 
-    /// Searches for words in the provided text with a gematria value matching that of the target word.
-    pub fn search_matching_words(&self, target_word: &str, text: &str) -> Vec<String> {
-        let target_value = self.calculate_value(target_word).value();
-        text.split_whitespace()
-            .flat_map(|w| w.split('\u{05BE}'))
-            .filter_map(|word| {
-                let processed_text = self.handle_vowels(word);
-                let word_value = self.calculate_value(&processed_text).value();
-                if word_value == target_value {
-                    Some(processed_text)
-                } else {
-                    None
+    /// Gets the contribution of every character of a text (without whitespace) to its value.
+    fn letter_breakdown(&self, strategy: &dyn GematriaCalculation, text: &str) -> Vec<(char, u32)> {
+        // Letters are valued by their position within their own word, as in `calculate_letters_value`
+        let mut position = 0;
+        text.char_indices()
+            .filter_map(|(i, c)| {
+                if self.tokenizer.is_delimiter(text, i, c) {
+                    position = 0;
                 }
+                if c.is_whitespace() {
+                    return None;
+                }
+
+                let value = match self.get_character_index(&c) {
+                    Some(&index) => {
+                        position += 1;
+                        let value = strategy.calculate_positional_value(index, position);
+                        u32::try_from(value).unwrap_or(u32::MAX)
+                    }
+                    None => strategy.calculate_symbol_value(c).unwrap_or_default(),
+                };
+                Some((c, value))
             })
             .collect()
     }
 
-    /// Searches for words in the provided text with a gematria value matching that of the target value.
-    pub fn search_matching_values(&self, target_value: &u32, text: &str) -> Vec<String> {
-        text.split_whitespace()
-            .flat_map(|w| w.split('\u{05BE}'))
-            .filter_map(|word| {
-                let processed_text = self.handle_vowels(word);
-                let word_value = self.calculate_value(&processed_text).value();
-                if word_value == *target_value {
-                    Some(processed_text)
-                } else {
-                    None
-                }
+    /// Compares two words under all the implemented methods, reporting under which methods they are equivalent.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gematria_rs::{GematriaContext, GematriaMethod};
+    ///
+    /// let gmctx = GematriaContext::default();
+    /// let comparison = gmctx.compare_words("יין", "סוד");
+    ///
+    /// assert!(comparison[&GematriaMethod::MisparHechrechi].is_equal());
+    /// assert_eq!(comparison[&GematriaMethod::MisparGadol].values(), (720, 70));
+    ///
+    /// let equivalent: Vec<GematriaMethod> = comparison
+    ///     .iter()
+    ///     .filter(|(_, c)| c.is_equal())
+    ///     .map(|(&method, _)| method)
+    ///     .collect();
+    /// assert!(equivalent.contains(&GematriaMethod::MisparHaMerubahHaKlali));
+    /// assert!(!equivalent.contains(&GematriaMethod::MisparKatan));
+    /// ```
+    pub fn compare_words(&self, a: &str, b: &str) -> BTreeMap<GematriaMethod, MethodComparison> {
+        let values_b = self.calculate_all_methods(b);
+        self.calculate_all_methods(a)
+            .into_iter()
+            .map(|(method, value_a)| {
+                let comparison = MethodComparison {
+                    value_a,
+                    value_b: values_b[&method],
+                };
+                (method, comparison)
             })
             .collect()
     }
 
-    /// Reads a text and groups words with matching gematria values, avoiding duplicates.
+    /// Same as [`GematriaContext::calculate_value`], failing on characters which are not Hebrew
+    /// instead of silently ignoring them.
     ///
-    /// # Examples:
+    /// Letters of the character map, whitespace, Hebrew vowel points and punctuation (such as the geresh),
+    /// and symbols valued by the current method are accepted.
+    ///
+    /// # Examples
     ///
     /// ```
-    /// use std::io;
-    /// use gematria_rs::GematriaContext;
+    /// use gematria_rs::{GematriaContext, GematriaError};
     ///
     /// let gmctx = GematriaContext::default();
-    /// let grouped_result = gmctx.group_words_by_gematria("נכנס יין יצא סוד")?;
+    /// assert_eq!(gmctx.calculate_value_strict("שָׁלוֹם עולם")?.value(), 522);
     ///
+    /// let error = gmctx.calculate_value_strict("שלוm!").unwrap_err();
+    /// assert_eq!(error, GematriaError::InvalidCharacters(vec![(3, 'm'), (4, '!')]));
+    /// # Ok::<(), GematriaError>(())
+    /// ```
+    pub fn calculate_value_strict(&self, text: &str) -> Result<GematriaResult, GematriaError> {
+        let invalid: Vec<(usize, char)> = text
+            .chars()
+            .enumerate()
+            .filter(|(_, c)| {
+                self.get_character_index(c).is_none()
+                    && !c.is_whitespace()
+                    && !self.is_hebrew_vowel(*c)
+                    && !matches!(c, '\u{05F3}' | '\u{05F4}')
+                    && !presentation::is_presentation_form(*c)
+                    && !paleo::is_paleo_letter(*c)
+                    && *c != PALEO_WORD_SEPARATOR
+                    && !(self.tokenizer.yiddish() && yiddish::is_yiddish_ligature(*c))
+                    && self
+                        .calculation_strategy
+                        .calculate_symbol_value(*c)
+                        .is_none()
+            })
+            .collect();
+        if !invalid.is_empty() {
+            return Err(GematriaError::InvalidCharacters(invalid));
+        }
+
+        Ok(self.calculate_value(text))
+    }
+
+    /// Calculates the gematria value of a word or phrase under all the implemented methods at once.
+    ///
+    /// The text is split into letter indices once, and every method is applied on them.
+    /// The context settings (shift, milui spellings, modifiers and kolel) apply to all the methods,
+    /// and a custom strategy of the context is included under [`GematriaMethod::Custom`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gematria_rs::{GematriaContext, GematriaMethod};
+    ///
+    /// let gmctx = GematriaContext::default();
+    /// let values = gmctx.calculate_all_methods("שלום");
+    ///
+    /// assert_eq!(values[&GematriaMethod::MisparHechrechi], 376);
+    /// assert_eq!(values[&GematriaMethod::MisparGadol], 936);
+    /// assert_eq!(values[&GematriaMethod::MisparKatan], 18);
+    /// ```
+    pub fn calculate_all_methods(&self, text: &str) -> HashMap<GematriaMethod, GematriaValue> {
+        let text = &*self.normalize(text);
+        let letters = self.get_word_letters(text);
+        let mut values: HashMap<GematriaMethod, GematriaValue> = GematriaMethod::IMPLEMENTED
+            .iter()
+            .map(|&method| {
+                let strategy = process_method_dyn(
+                    method,
+                    self.character_map.clone(),
+                    self.shift,
+                    &self.milui_spellings,
+                    self.sofit_policy,
+                    &self.alphabet,
+                )
+                .expect("implemented methods have a strategy");
+                let value = self.calculate_letters_value(strategy.as_ref(), &letters, text);
+                (method, value)
+            })
+            .collect();
+        if self.get_current_method() == GematriaMethod::Custom {
+            let value =
+                self.calculate_letters_value(self.calculation_strategy.as_ref(), &letters, text);
+            values.insert(GematriaMethod::Custom, value);
+        }
+
+        values
+    }
+
+    /// Calculates the total of every verse of a text, along with its index (0 based).
+    ///
+    /// Verses end with a sof pasuk (׃) or a line break, verses without any word are skipped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gematria_rs::GematriaContext;
+    ///
+    /// let gmctx = GematriaContext::default();
+    /// let verses = gmctx.calculate_verses("נכנס יין׃ יצא סוד׃");
+    ///
+    /// assert_eq!(verses.len(), 2);
+    /// assert_eq!((verses[1].0, verses[1].1.word()), (1, "יצא סוד"));
+    /// assert_eq!(verses[1].1.value(), 171);
+    /// ```
+    pub fn calculate_verses(&self, text: &str) -> Vec<(usize, GematriaResult)> {
+        split_verses(text)
+            .enumerate()
+            .map(|(index, (_, verse))| (index, self.calculate_value(verse.trim())))
+            .collect()
+    }
+
+    /// Searches for the verses of a text whose total equals the target value, along with their index.
+    ///
+    /// Verses are split as in [`GematriaContext::calculate_verses`], use
+    /// `Tanakh::search_verses_by_value` (with the `tanakh` feature) to get verse references.
+    pub fn search_verses_by_value(&self, target: &u64, text: &str) -> Vec<(usize, GematriaResult)> {
+        self.calculate_verses(text)
+            .into_iter()
+            .filter(|(_, result)| result.value() == *target)
+            .collect()
+    }
+
+    /// Searches for words in the provided text with a gematria value matching that of the target word.
+    ///
+    /// Every [`Match`] holds the matched word, its value, and its token index and byte range within the text.
+    pub fn search_matching_words(&self, target_word: &str, text: &str) -> Vec<Match> {
+        let target_value = self.calculate_value(target_word).value();
+        self.search_matching_values(&target_value, text)
+    }
+
+    /// Same as [`GematriaContext::search_matching_words`], lazily yielding the matches,
+    /// so callers can stop early or stream the results.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// use gematria_rs::GematriaContext;
+    ///
+    /// let gmctx = GematriaContext::default();
+    /// let first = gmctx.search_matching_words_iter("יין", "נכנס סוד יצא יין").next().unwrap();
+    ///
+    /// assert_eq!(first.word(), "סוד");
+    /// assert_eq!(first.index(), 1);
+    /// ```
+    pub fn search_matching_words_iter<'a>(
+        &'a self,
+        target_word: &str,
+        text: &'a str,
+    ) -> impl Iterator<Item = Match> + 'a {
+        let target_value = self.calculate_value(target_word).value();
+        self.search_matching_values_iter(&target_value, text)
+    }
+
+    /// Searches for words in the provided text with a gematria value matching that of the target value.
+    ///
+    /// Every [`Match`] holds the matched word, its value, and its token index and byte range within the text.
+    /// With the `parallel` feature, the words are valued in parallel.
+    pub fn search_matching_values(&self, target_value: &u64, text: &str) -> Vec<Match> {
+        self.collect_matches(text, |value, _| value == *target_value)
+    }
+
+    /// Same as [`GematriaContext::search_matching_values`], lazily yielding the matches,
+    /// so callers can stop early or stream the results.
+    pub fn search_matching_values_iter<'a>(
+        &'a self,
+        target_value: &u64,
+        text: &'a str,
+    ) -> impl Iterator<Item = Match> + 'a {
+        let target_value = *target_value;
+        self.search_matching_filter(text, move |value, _| value == target_value)
+    }
+
+    /// Lazily searches for words satisfying a predicate on their value and word, such as a prime value
+    /// or a minimal word length, without allocating the words which do not match.
+    ///
+    /// The predicate receives the value and the word (as returned by the vowel handling of the context).
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// use gematria_rs::GematriaContext;
+    ///
+    /// let gmctx = GematriaContext::default();
+    /// let matches: Vec<String> = gmctx
+    ///     .search_matching_filter("יהוה אהבה אחד שלום", |value, word| {
+    ///         value % 13 == 0 && word.chars().count() >= 3
+    ///     })
+    ///     .map(|m| m.word().to_string())
+    ///     .collect();
+    ///
+    /// assert_eq!(matches, vec!["יהוה", "אהבה", "אחד"]);
+    /// ```
+    pub fn search_matching_filter<'a, F>(
+        &'a self,
+        text: &'a str,
+        predicate: F,
+    ) -> impl Iterator<Item = Match> + 'a
+    where
+        F: Fn(GematriaValue, &str) -> bool + 'a,
+    {
+        self.tokenizer
+            .tokenize_with_ranges(text)
+            .enumerate()
+            .filter_map(move |(index, (range, word))| {
+                let (value, word) = self.word_value(word);
+                if predicate(value, &word) {
+                    Some(Match::new(word, value, index).with_byte_range(range))
+                } else {
+                    None
+                }
+            })
+    }
+
+    /// Searches for words whose value is within `delta` of the target value (inclusive),
+    /// sorted by their distance from the target value and then by their position in the text.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// use gematria_rs::GematriaContext;
+    ///
+    /// let gmctx = GematriaContext::default();
+    /// let matches = gmctx.search_near_value(&75, 10, "נכנס יין יצא סוד אב");
+    ///
+    /// let words: Vec<(&str, u64)> = matches.iter().map(|m| (m.word(), m.value())).collect();
+    /// assert_eq!(words, vec![("יין", 70), ("סוד", 70)]);
+    /// ```
+    pub fn search_near_value(&self, target_value: &u64, delta: u64, text: &str) -> Vec<Match> {
+        let range = target_value.saturating_sub(delta)..=target_value.saturating_add(delta);
+        let mut matches = self.collect_matches(text, |value, _| range.contains(&value));
+        // The sort is stable, so words at the same distance remain in order of appearance
+        matches.sort_by_key(|m| m.value().abs_diff(*target_value));
+
+        matches
+    }
+
+    /// Searches for phrases of 2 to `max_words` contiguous words whose total value is the target value.
+    ///
+    /// The total of a phrase is the sum of the values of its words. Each match holds the words of the phrase
+    /// joined by a space, the token index of its first word and its byte range within the text.
+    /// Words without a value extend the phrases they follow into further matches.
+    ///
+    /// This is [`GematriaContext::find_phrases`] with a depth limit of `max_words`, without the single words
+    /// and without reporting whether the limit cut the search.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// use gematria_rs::GematriaContext;
+    ///
+    /// let gmctx = GematriaContext::default();
+    /// // יין (70) = כי (30) + מ (40)
+    /// let matches = gmctx.search_matching_phrases(&70, "יין כי מ סוד", 3);
+    ///
+    /// let phrases: Vec<&str> = matches.iter().map(|m| m.word()).collect();
+    /// assert_eq!(phrases, vec!["כי מ"]);
+    /// assert_eq!(matches[0].index(), 1);
+    /// ```
+    pub fn search_matching_phrases(
+        &self,
+        target_value: &u64,
+        text: &str,
+        max_words: usize,
+    ) -> Vec<Match> {
+        let limits = SearchLimits::new().with_max_depth(max_words);
+        self.find_phrases_of(*target_value, text, 2, &limits)
+            .into_items()
+    }
+
+    /// Searches for words matching any of the target values in a single pass over the text.
+    ///
+    /// Every target value is present as a key in the returned map, mapped to the words
+    /// matching it (in order of appearance), so scanning a corpus for many values requires
+    /// tokenizing it only once.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// use gematria_rs::GematriaContext;
+    ///
+    /// let gmctx = GematriaContext::default();
+    /// let matches = gmctx.search_matching_values_multi(&[70, 101], "נכנס יין יצא סוד");
+    ///
+    /// let words: Vec<&str> = matches[&70].iter().map(|m| m.word()).collect();
+    /// assert_eq!(words, vec!["יין", "סוד"]);
+    /// assert_eq!(matches[&101][0].index(), 2);
+    /// ```
+    pub fn search_matching_values_multi(
+        &self,
+        target_values: &[u64],
+        text: &str,
+    ) -> HashMap<u64, Vec<Match>> {
+        let mut matches: HashMap<u64, Vec<Match>> = target_values
+            .iter()
+            .map(|&value| (value, Vec::new()))
+            .collect();
+
+        for found in self.collect_matches(text, |value, _| matches.contains_key(&value)) {
+            if let Some(found_for_value) = matches.get_mut(&found.value) {
+                found_for_value.push(found);
+            }
+        }
+
+        matches
+    }
+
+    /// Collects the words of a text whose value and word satisfy a predicate, in order of appearance.
+    ///
+    /// With the `parallel` feature, the words are valued in parallel.
+    fn collect_matches<F>(&self, text: &str, predicate: F) -> Vec<Match>
+    where
+        F: Fn(GematriaValue, &str) -> bool + Sync + Send,
+    {
+        let tokens: Vec<(usize, (Range<usize>, &str))> = self
+            .tokenizer
+            .tokenize_with_ranges(text)
+            .enumerate()
+            .collect();
+        parallel::filter_map(&tokens, |(index, (range, word))| {
+            let (value, word) = self.word_value(word);
+            predicate(value, &word)
+                .then(|| Match::new(word, value, *index).with_byte_range(range.clone()))
+        })
+    }
+
+    /// Reads a text and groups words with matching gematria values, avoiding duplicates.
+    ///
+    /// With the `parallel` feature, the words are valued in parallel.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// use gematria_rs::GematriaContext;
+    ///
+    /// let gmctx = GematriaContext::default();
+    /// let grouped_result = gmctx.group_words("נכנס יין יצא סוד")?;
+    ///
+    /// assert_eq!(grouped_result, vec![(70, vec!["יין".to_string(),"סוד".to_string()])]);
+    /// # Ok::<(), gematria_rs::Error>(())
+    /// ```
+    pub fn group_words(&self, text: &str) -> Result<Vec<(u64, Vec<String>)>, Error> {
+        let words: Vec<&str> = self.tokenizer.tokenize(text).collect();
+        let mut grouped_words = HashMap::new();
+        for (value, word) in parallel::map(&words, |word| self.word_value(word)) {
+            grouped_words
+                .entry(value)
+                .or_insert_with(Vec::new)
+                .push_if_not_exists(word);
+        }
+
+        Ok(sort_groups(grouped_words))
+    }
+
+    /// Same as [`GematriaContext::group_words`], clustering the words of every group which are
+    /// anagrams of each other (the same multiset of letters, final forms counting as their regular letters).
+    ///
+    /// Clusters are ordered by their first word, in order of appearance within the group.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// use gematria_rs::GematriaContext;
+    ///
+    /// let gmctx = GematriaContext::default();
+    /// let grouped_result = gmctx.group_words_with_anagrams("שלום יין לשום סוד")?;
+    ///
+    /// assert_eq!(
+    ///     grouped_result,
+    ///     vec![
+    ///         (70, vec![vec!["יין".to_string()], vec!["סוד".to_string()]]),
+    ///         (376, vec![vec!["שלום".to_string(), "לשום".to_string()]]),
+    ///     ]
+    /// );
+    /// # Ok::<(), gematria_rs::Error>(())
+    /// ```
+    pub fn group_words_with_anagrams(&self, text: &str) -> Result<Vec<AnagramGroup>, Error> {
+        let groups = self.group_words(text)?;
+        Ok(groups
+            .into_iter()
+            .map(|(value, words)| {
+                let mut clusters: Vec<(Vec<u32>, Vec<String>)> = Vec::new();
+                for word in words {
+                    let key = self.anagram_key(&word);
+                    match clusters.iter_mut().find(|(k, _)| *k == key) {
+                        Some((_, cluster)) => cluster.push(word),
+                        None => clusters.push((key, vec![word])),
+                    }
+                }
+                (value, clusters.into_iter().map(|(_, c)| c).collect())
+            })
+            .collect())
+    }
+
+    /// Gets the sorted letter indices of a word, equal for anagrams.
+    fn anagram_key(&self, word: &str) -> Vec<u32> {
+        let mut key: Vec<u32> = self
+            .get_indices_for_word(word)
+            .into_iter()
+            .map(|index| self.alphabet.final_form_base(index).unwrap_or(index))
+            .collect();
+        key.sort_unstable();
+        key
+    }
+
+    /// Same as [`GematriaContext::group_words`], returning an [`io::Result`].
+    #[deprecated(
+        since = "0.2.0",
+        note = "use `group_words`, returning a `gematria_rs::Error`"
+    )]
+    pub fn group_words_by_gematria(&self, text: &str) -> io::Result<Vec<(u64, Vec<String>)>> {
+        Ok(self.group_words(text)?)
+    }
+
+    /// Same as [`GematriaContext::group_words`], reporting progress (in words) to a
+    /// [`ProgressSink`] and aborting with a [`GematriaError::Cancelled`] error once the
+    /// [`CancellationToken`] is cancelled.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// use gematria_rs::{CancellationToken, GematriaContext};
+    ///
+    /// let gmctx = GematriaContext::default();
+    /// let mut processed = 0;
+    /// let grouped_result = gmctx.group_words_with_progress(
+    ///     "נכנס יין יצא סוד",
+    ///     &mut |done, _total| processed = done,
+    ///     &CancellationToken::new(),
+    /// )?;
+    ///
+    /// assert_eq!(processed, 4);
     /// assert_eq!(grouped_result, vec![(70, vec!["יין".to_string(),"סוד".to_string()])]);
-    /// # Ok::<(), io::Error>(())
+    /// # Ok::<(), gematria_rs::Error>(())
     /// ```
-    pub fn group_words_by_gematria(&self, text: &str) -> io::Result<Vec<(u32, Vec<String>)>> {
+    pub fn group_words_with_progress(
+        &self,
+        text: &str,
+        progress: &mut dyn ProgressSink,
+        cancel: &CancellationToken,
+    ) -> Result<Vec<(u64, Vec<String>)>, Error> {
+        let words: Vec<&str> = self.tokenizer.tokenize(text).collect();
+        let total = words.len();
         let mut grouped_words = HashMap::new();
-        for word in text.split_whitespace().flat_map(|w| w.split('\u{05BE}')) {
-            let processed_text = self.handle_vowels(word);
+        for (processed, word) in words.into_iter().enumerate() {
+            if cancel.is_cancelled() {
+                return Err(Error::Cancelled);
+            }
 
-            let value = self.calculate_value(&processed_text).value();
+            let (value, word) = self.word_value(word);
 
             grouped_words
                 .entry(value)
                 .or_insert_with(Vec::new)
-                .push_if_not_exists(processed_text);
+                .push_if_not_exists(word);
+
+            progress.on_progress(processed + 1, total);
+        }
+
+        Ok(sort_groups(grouped_words))
+    }
+
+    /// Same as [`GematriaContext::group_words_with_progress`], returning an [`io::Result`]
+    /// with an [`io::ErrorKind::Interrupted`] error once cancelled.
+    #[deprecated(
+        since = "0.2.0",
+        note = "use `group_words_with_progress`, returning a `gematria_rs::Error`"
+    )]
+    pub fn group_words_by_gematria_with_progress(
+        &self,
+        text: &str,
+        progress: &mut dyn ProgressSink,
+        cancel: &CancellationToken,
+    ) -> io::Result<Vec<(u64, Vec<String>)>> {
+        Ok(self.group_words_with_progress(text, progress, cancel)?)
+    }
+
+    /// Gets the index of a Hebrew character.
+    pub fn get_character_index(&self, character: &char) -> Option<&u32> {
+        self.character_map.get(*character)
+    }
+
+    /// Sets the current gematria method to desired one.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the method has no built-in implementation, see [`GematriaContext::try_set_method`].
+    pub fn set_method(&mut self, method: GematriaMethod) {
+        self.try_set_method(method)
+            .unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// Sets the current gematria method, failing with [`GematriaError::UnsupportedMethod`]
+    /// (and keeping the current method) if the method has no built-in implementation.
+    ///
+    /// A context valuing the default alphabet of its method switches to the alphabet of the new method
+    /// (see [`GematriaBuilder::with_alphabet`]), an alphabet or character map given to the builder is kept.
+    pub fn try_set_method(&mut self, method: GematriaMethod) -> Result<(), GematriaError> {
+        let current = default_alphabet(self.get_current_method());
+        let (alphabet, character_map) = if self.alphabet.name() == current.name()
+            && *self.character_map.char_map() == current.letters()
+        {
+            let alphabet = default_alphabet(method);
+            let character_map = HebrewCharacterMap::new(alphabet.letters());
+            (alphabet, character_map)
+        } else {
+            (self.alphabet.clone(), self.character_map.clone())
+        };
+        let strategy = process_method_dyn(
+            method,
+            character_map.clone(),
+            self.shift,
+            &self.milui_spellings,
+            self.sofit_policy,
+            &alphabet,
+        )?;
+        self.calculation_strategy = strategy;
+        self.strategy_id = 0;
+        self.alphabet = alphabet;
+        self.character_map = character_map;
+        self.refresh_settings();
+
+        Ok(())
+    }
+
+    /// Sets a custom calculation strategy, replacing the current method.
+    ///
+    /// The values of the strategy are cached apart from the values of any other strategy, see [`SharedCache`].
+    pub fn set_strategy(&mut self, strategy: Box<dyn GematriaCalculation>) {
+        self.replace_strategy(strategy, next_strategy_id());
+    }
+
+    /// Replaces the strategy with a custom strategy of the given identity.
+    fn replace_strategy(&mut self, strategy: Box<dyn GematriaCalculation>, id: u64) {
+        self.calculation_strategy =
+            SofitStrategy::wrap(strategy, self.sofit_policy, &self.alphabet);
+        self.strategy_id = id;
+    }
+
+    /// Gets a cached value, `None` when it is not cached or caching is disabled.
+    fn cached_value(&self, key: &CacheKey) -> Option<u64> {
+        self.cache.as_ref()?.lock().get(key)
+    }
+
+    /// Caches a value, when caching is enabled.
+    fn cache_value(&self, key: CacheKey, value: u64) {
+        if let Some(ref cache) = self.cache {
+            cache.lock().insert(key, value);
+        }
+    }
+
+    /// Gets the maximum number of cached values, `None` when the cache is unbounded or disabled.
+    pub(crate) fn cache_capacity(&self) -> Option<usize> {
+        self.cache.as_ref()?.lock().capacity()
+    }
+
+    /// Discards the cached values, keeping the hit and miss counts.
+    ///
+    /// Long-running processes may clear the cache to release its memory, see [`GematriaContext::cache_stats`].
+    /// A shared cache is cleared for all the contexts sharing it.
+    pub fn clear_cache(&self) {
+        if let Some(ref cache) = self.cache {
+            cache.clear();
+        }
+    }
+
+    /// Gets the usage statistics of the cache (hits, misses, entries and memory), `None` when caching is disabled.
+    pub fn cache_stats(&self) -> Option<CacheStats> {
+        self.cache.as_ref().map(SharedCache::stats)
+    }
+
+    /// Gets the cache of the context, to share it with other contexts, `None` when caching is disabled.
+    pub fn shared_cache(&self) -> Option<SharedCache> {
+        self.cache.clone()
+    }
+
+    /// Recomputes the hash of the settings keying the cached values, after one of them changed.
+    fn refresh_settings(&mut self) {
+        self.settings = self.config().settings_hash();
+    }
+
+    /// Rebuilds the strategy of a built-in method, after one of its settings changed.
+    fn refresh_strategy(&mut self) {
+        let method = self.get_current_method();
+        if method != GematriaMethod::Custom {
+            self.set_method(method);
+        }
+    }
+
+    /// Sets the letter shift used by shift ciphers ([`GematriaMethod::Avgad`]).
+    ///
+    /// Cached values are discarded, as they may have been calculated with another shift.
+    pub fn set_shift(&mut self, shift: i32) {
+        self.shift = shift;
+        self.refresh_settings();
+        self.clear_cache();
+        self.refresh_strategy();
+    }
+
+    /// Sets the [`Kolel`] adjustment added to the value of texts, `None` disables it.
+    ///
+    /// Cached values are discarded, as they may have been calculated with another adjustment.
+    pub fn set_kolel(&mut self, kolel: Option<Kolel>) {
+        self.kolel = kolel;
+        self.refresh_settings();
+        self.clear_cache();
+    }
+
+    /// Overrides the full spelling of a letter used by the milui methods, e.g. `('ה', "הי")`.
+    ///
+    /// Non Hebrew characters of the spelling are not valued.
+    /// Cached values are discarded, as they may have been calculated with another spelling.
+    pub fn set_milui_spelling(&mut self, letter: char, spelling: &str) {
+        self.milui_spellings
+            .insert(letter, spelling.chars().collect());
+        self.refresh_settings();
+        self.clear_cache();
+        self.refresh_strategy();
+    }
+}
+
+impl PartialEq for GematriaResult {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == std::cmp::Ordering::Equal
+    }
+}
+
+impl Eq for GematriaResult {}
+
+impl PartialOrd for GematriaResult {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for GematriaResult {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.value
+            .cmp(&other.value)
+            .then_with(|| self.word.cmp(&other.word))
+            .then_with(|| self.method.cmp(&other.method))
+            .then_with(|| self.kolel.cmp(&other.kolel))
+    }
+}
+
+impl std::hash::Hash for GematriaResult {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.value.hash(state);
+        self.word.hash(state);
+        self.method.hash(state);
+        self.kolel.hash(state);
+    }
+}
+
+impl fmt::Display for GematriaResult {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} = {} ({:?})", self.word, self.value, self.method)
+    }
+}
+
+/// `GematriaResult` used for structured result of calculations.
+impl GematriaResult {
+    /// Creates a new result object.
+    pub fn new(value: GematriaValue, method: GematriaMethod, word: String) -> Self {
+        GematriaResult {
+            method,
+            value,
+            original_word: word.clone(),
+            normalized_word: word.chars().filter(|&c| !is_hebrew_vowel(c)).collect(),
+            word,
+            kolel: None,
+            breakdown: Vec::new(),
+            numeral: None,
+            abbreviation: false,
         }
+    }
+
+    /// Records the word exactly as given to the calculation, when vowels were removed from the word.
+    pub fn with_original_word(mut self, original_word: String) -> Self {
+        self.normalized_word = original_word
+            .chars()
+            .filter(|&c| !is_hebrew_vowel(c))
+            .collect();
+        self.original_word = original_word;
+        self
+    }
+
+    /// Records the contribution of every character of the word to the value.
+    pub fn with_breakdown(mut self, breakdown: Vec<(char, u32)>) -> Self {
+        self.breakdown = breakdown;
+        self
+    }
+
+    /// Gets the characters of the calculated text (without whitespace) and their contribution to the value,
+    /// 0 for ignored characters.
+    ///
+    /// Contributions are taken before word level adjustments (such as [`Modifier`]s and [`Kolel`]),
+    /// so they only sum to the value for methods valuing each letter on its own.
+    ///
+    /// # Examples
+    /// ```
+    /// use gematria_rs::GematriaContext;
+    ///
+    /// let result = GematriaContext::default().calculate_value("ש\"ל");
+    ///
+    /// assert_eq!(result.breakdown(), &[('ש', 300), ('"', 0), ('ל', 30)]);
+    /// ```
+    pub fn breakdown(&self) -> &[(char, u32)] {
+        &self.breakdown
+    }
+
+    /// Records the kolel adjustment included in the value.
+    pub fn with_kolel(mut self, kolel: Option<Kolel>) -> Self {
+        self.kolel = kolel;
+        self
+    }
+
+    /// Gets the kolel adjustment included in the value, `None` if no adjustment was applied.
+    pub fn kolel(&self) -> Option<Kolel> {
+        self.kolel
+    }
+
+    /// Records the number denoted by the word, when recognized as a Hebrew numeral.
+    pub fn with_numeral(mut self, numeral: Option<u32>) -> Self {
+        self.numeral = numeral;
+        self
+    }
+
+    /// Gets the number denoted by the word when it is a marked Hebrew numeral (such as תשפ״ד),
+    /// see [`GematriaBuilder::with_numerals`]. The letter sum remains available as the value.
+    ///
+    /// # Examples
+    /// ```
+    /// use gematria_rs::GematriaBuilder;
+    ///
+    /// let gmctx = GematriaBuilder::new().with_numerals(true).init_gematria();
+    ///
+    /// let year = gmctx.calculate_value("ה׳תשפ״ד");
+    /// assert_eq!((year.numeral(), year.value()), (Some(5784), 789));
+    /// assert_eq!(gmctx.calculate_value("צה״ל").numeral(), None);
+    /// ```
+    pub fn numeral(&self) -> Option<u32> {
+        self.numeral
+    }
+
+    /// Records whether the word is an abbreviation whose gershayim marks were stripped.
+    pub fn with_abbreviation(mut self, abbreviation: bool) -> Self {
+        self.abbreviation = abbreviation;
+        self
+    }
+
+    /// Gets whether the word is an abbreviation (such as צה״ל) whose gershayim marks were stripped,
+    /// see [`GematriaBuilder::with_abbreviations`].
+    ///
+    /// # Examples
+    /// ```
+    /// use gematria_rs::GematriaBuilder;
+    ///
+    /// let gmctx = GematriaBuilder::new().with_abbreviations(true).init_gematria();
+    /// let result = gmctx.calculate_value("רמב\"ם");
+    ///
+    /// assert!(result.is_abbreviation());
+    /// assert_eq!((result.word(), result.original_word()), ("רמבם", "רמב\"ם"));
+    /// assert_eq!(result.value(), 282);
+    /// ```
+    pub fn is_abbreviation(&self) -> bool {
+        self.abbreviation
+    }
+
+    /// Converts the result into a map of its fields, for templating engines.
+    ///
+    /// The keys are `value`, `method`, `word` and `reduced_value` (the value reduced to a single digit),
+    /// `kolel` when a kolel adjustment was applied, `numeral` when the word was recognized as a numeral
+    /// and `abbreviation` when the word is a stripped abbreviation.
+    ///
+    /// # Examples
+    /// ```
+    /// use gematria_rs::GematriaContext;
+    ///
+    /// let map = GematriaContext::default().calculate_value("שלום").to_map();
+    ///
+    /// assert_eq!(map["value"], "376");
+    /// assert_eq!(map["method"], "MisparHechrechi");
+    /// assert_eq!(map["word"], "שלום");
+    /// assert_eq!(map["reduced_value"], "7");
+    /// ```
+    pub fn to_map(&self) -> HashMap<String, String> {
+        let mut map = HashMap::from([
+            ("value".to_string(), self.value.to_string()),
+            ("method".to_string(), format!("{:?}", self.method)),
+            ("word".to_string(), self.word.clone()),
+            (
+                "reduced_value".to_string(),
+                methods::reduce_to_single_digit(self.value).to_string(),
+            ),
+        ]);
+        if let Some(kolel) = self.kolel {
+            map.insert("kolel".to_string(), format!("{:?}", kolel));
+        }
+        if let Some(numeral) = self.numeral {
+            map.insert("numeral".to_string(), numeral.to_string());
+        }
+        if self.abbreviation {
+            map.insert("abbreviation".to_string(), true.to_string());
+        }
+
+        map
+    }
+
+    /// Gets the gematria value.
+    pub fn value(&self) -> GematriaValue {
+        self.value
+    }
+
+    /// Gets the word exactly as given to the calculation, whatever the vowels handling of the context.
+    ///
+    /// # Examples
+    /// ```
+    /// use gematria_rs::GematriaContext;
+    ///
+    /// let result = GematriaContext::default().calculate_value("שָׁלוֹם");
+    ///
+    /// assert_eq!(result.word(), "שלום");
+    /// assert_eq!(result.original_word(), "שָׁלוֹם");
+    /// assert_eq!(result.normalized_word(), "שלום");
+    /// ```
+    pub fn original_word(&self) -> &str {
+        &self.original_word
+    }
+
+    /// Gets the word without vowel points and cantillation marks, whatever the vowels handling of the context.
+    pub fn normalized_word(&self) -> &str {
+        &self.normalized_word
+    }
+
+    /// Gets the gematria value as a `u32`, returning `None` if it does not fit.
+    ///
+    /// # Examples
+    /// ```
+    /// use gematria_rs::GematriaContext;
+    ///
+    /// let gmctx = GematriaContext::default();
+    /// assert_eq!(gmctx.calculate_value("שלום").value_u32(), Some(376));
+    /// ```
+    pub fn value_u32(&self) -> Option<u32> {
+        u32::try_from(self.value).ok()
+    }
+
+    /// Gets the calculation method used.
+    pub fn method(&self) -> &GematriaMethod {
+        &self.method
+    }
+
+    /// Gets the word for which the gematria value was calculated.
+    pub fn word(&self) -> &str {
+        &self.word
+    }
+}
+
+impl Match {
+    /// Creates a new match object.
+    pub fn new(word: String, value: GematriaValue, index: usize) -> Self {
+        Match {
+            word,
+            value,
+            index,
+            byte_range: 0..0,
+        }
+    }
+
+    /// Records the byte range of the matched word within the searched text.
+    pub fn with_byte_range(mut self, byte_range: Range<usize>) -> Self {
+        self.byte_range = byte_range;
+        self
+    }
+
+    /// Gets the matched word.
+    pub fn word(&self) -> &str {
+        &self.word
+    }
+
+    /// Gets the gematria value of the matched word.
+    pub fn value(&self) -> GematriaValue {
+        self.value
+    }
+
+    /// Gets the gematria value of the matched word as a `u32`, returning `None` if it does not fit.
+    pub fn value_u32(&self) -> Option<u32> {
+        u32::try_from(self.value).ok()
+    }
+
+    /// Gets the index of the matched word within the searched text.
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// Gets the byte range of the matched word within the searched text, as given to the search
+    /// (including vowels), so `&text[m.byte_range()]` is the matched part of the text.
+    ///
+    /// # Examples
+    /// ```
+    /// use gematria_rs::GematriaContext;
+    ///
+    /// let text = "נכנס יַיִן יצא סוד";
+    /// let matches = GematriaContext::default().search_matching_values(&70, text);
+    ///
+    /// assert_eq!(matches[0].word(), "יין");
+    /// assert_eq!(&text[matches[0].byte_range()], "יַיִן");
+    /// assert_eq!(matches[1].byte_offset(), 27);
+    /// ```
+    pub fn byte_range(&self) -> Range<usize> {
+        self.byte_range.clone()
+    }
+
+    /// Gets the byte offset of the matched word within the searched text.
+    pub fn byte_offset(&self) -> usize {
+        self.byte_range.start
+    }
+
+    /// Converts the match into a map of its fields (`word`, `value` and `index`), for templating engines.
+    pub fn to_map(&self) -> HashMap<String, String> {
+        HashMap::from([
+            ("word".to_string(), self.word.clone()),
+            ("value".to_string(), self.value.to_string()),
+            ("index".to_string(), self.index.to_string()),
+        ])
+    }
+}
+
+impl MethodComparison {
+    /// Gets the values of the two words, in order.
+    pub fn values(&self) -> (GematriaValue, GematriaValue) {
+        (self.value_a, self.value_b)
+    }
+
+    /// Gets the value of the first word.
+    pub fn value_a(&self) -> GematriaValue {
+        self.value_a
+    }
+
+    /// Gets the value of the second word.
+    pub fn value_b(&self) -> GematriaValue {
+        self.value_b
+    }
+
+    /// Whether the two words are equivalent, i.e. have the same value.
+    pub fn is_equal(&self) -> bool {
+        self.value_a == self.value_b
+    }
+}
+
+// Helper function to sum values, `None` on overflow.
+fn checked_sum(mut values: impl Iterator<Item = u64>) -> Option<u64> {
+    values.try_fold(0u64, u64::checked_add)
+}
+
+// Helper function to keep the groups of more than one word, sorted by size and then by value.
+fn sort_groups(mut grouped_words: HashMap<u64, Vec<String>>) -> Vec<(u64, Vec<String>)> {
+    // Filter out entries with only one word
+    grouped_words.retain(|_, v| v.len() > 1);
+
+    // Convert HashMap to Vec and sort by the length of the vectors
+    let mut grouped_vec: Vec<(u64, Vec<String>)> = grouped_words.into_iter().collect();
+
+    // Sort by the length of the vectors (primary) and gematria value (secondary)
+    grouped_vec.sort_by(|a, b| match b.1.len().cmp(&a.1.len()) {
+        std::cmp::Ordering::Equal => a.0.cmp(&b.0),
+        other => other,
+    });
+
+    grouped_vec
+}
+
+// Helper function to add a word to the vector if it doesn't already exist
+trait PushIfNotExists {
+    fn push_if_not_exists(&mut self, item: String);
+}
+
+impl PushIfNotExists for Vec<String> {
+    fn push_if_not_exists(&mut self, item: String) {
+        if !self.contains(&item) {
+            self.push(item);
+        }
+    }
+}
+
+/// Used to implement `Into` functionality for easy calculations.
+pub trait IntoGematriaVal {
+    /// Calculate the value for a given cipher
+    fn gematria_val(&self, method: &GematriaMethod) -> GematriaValue;
+
+    /// Calculate the value for a given cipher as a `u32`, saturating at `u32::MAX`.
+    #[deprecated(
+        since = "0.2.0",
+        note = "values are now `GematriaValue` (u64), use `gematria_val` instead"
+    )]
+    fn gematria_val_u32(&self, method: &GematriaMethod) -> u32 {
+        u32::try_from(self.gematria_val(method)).unwrap_or(u32::MAX)
+    }
+}
+
+impl IntoGematriaVal for char {
+    /// # Examples
+    /// ```
+    /// use gematria_rs::*;
+    ///
+    /// let val = 'א'.gematria_val(&GematriaMethod::MisparHechrechi);
+    /// assert_eq!(val, 1)
+    /// ```
+    fn gematria_val(&self, method: &GematriaMethod) -> GematriaValue {
+        let gmctx = GematriaBuilder::new()
+            .with_method(*method)
+            .with_vowels(true)
+            .init_gematria();
+        gmctx.calculate_char_value(*self)
+    }
+}
+
+impl IntoGematriaVal for String {
+    /// # Examples
+    /// ```
+    /// use gematria_rs::*;
+    ///
+    /// let val = "בעזרת השם".to_string().gematria_val(&GematriaMethod::MisparHechrechi);
+    /// assert_eq!(val, 1024)
+    /// ```
+    fn gematria_val(&self, method: &GematriaMethod) -> GematriaValue {
+        let gmctx = GematriaBuilder::new()
+            .with_method(*method)
+            .with_vowels(true)
+            .init_gematria();
+        gmctx.calculate_value(self).value()
+    }
+}
+
+impl IntoGematriaVal for str {
+    /// # Examples
+    /// ```
+    /// use gematria_rs::*;
+    ///
+    /// let val = "בעזרת השם".gematria_val(&GematriaMethod::MisparHechrechi);
+    /// assert_eq!(val, 1024)
+    /// ```
+    fn gematria_val(&self, method: &GematriaMethod) -> GematriaValue {
+        let gmctx = GematriaBuilder::new()
+            .with_method(*method)
+            .with_vowels(true)
+            .init_gematria();
+        gmctx.calculate_value(self).value()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    // Tests to validate the functionality of the Gematria-rs library
+    use super::*;
+
+    #[test]
+    fn test_default_map() {
+        let gmctx = GematriaContext::default();
+
+        assert_eq!(gmctx.get_current_method(), GematriaMethod::MisparHechrechi);
+        assert_eq!(gmctx.calculate_char_value('א'), 1);
+        assert_eq!(gmctx.calculate_char_value('ב'), 2);
+        assert_eq!(gmctx.calculate_char_value('ג'), 3);
+        assert_eq!(gmctx.calculate_char_value('ד'), 4);
+        assert_eq!(gmctx.calculate_char_value('ה'), 5);
+        assert_eq!(gmctx.calculate_char_value('ו'), 6);
+        assert_eq!(gmctx.calculate_char_value('ז'), 7);
+        assert_eq!(gmctx.calculate_char_value('ח'), 8);
+        assert_eq!(gmctx.calculate_char_value('ט'), 9);
+        assert_eq!(gmctx.calculate_char_value('י'), 10);
+        assert_eq!(gmctx.calculate_char_value('כ'), 20);
+        assert_eq!(gmctx.calculate_char_value('ל'), 30);
+        assert_eq!(gmctx.calculate_char_value('מ'), 40);
+        assert_eq!(gmctx.calculate_char_value('נ'), 50);
+        assert_eq!(gmctx.calculate_char_value('ס'), 60);
+        assert_eq!(gmctx.calculate_char_value('ע'), 70);
+        assert_eq!(gmctx.calculate_char_value('פ'), 80);
+        assert_eq!(gmctx.calculate_char_value('צ'), 90);
+        assert_eq!(gmctx.calculate_char_value('ק'), 100);
+        assert_eq!(gmctx.calculate_char_value('ר'), 200);
+        assert_eq!(gmctx.calculate_char_value('ש'), 300);
+        assert_eq!(gmctx.calculate_char_value('ת'), 400);
+        assert_eq!(gmctx.calculate_char_value('ך'), 20);
+        assert_eq!(gmctx.calculate_char_value('ם'), 40);
+        assert_eq!(gmctx.calculate_char_value('ן'), 50);
+        assert_eq!(gmctx.calculate_char_value('ף'), 80);
+        assert_eq!(gmctx.calculate_char_value('ץ'), 90);
+    }
+
+    #[test]
+    fn test_filled_letters_map() {
+        let gmctx = GematriaBuilder::new()
+            .with_method(GematriaMethod::OtiyotBeMilui)
+            .init_gematria();
+
+        assert_eq!(gmctx.calculate_char_value('א'), 111);
+        assert_eq!(gmctx.calculate_char_value('ב'), 412);
+        assert_eq!(gmctx.calculate_char_value('ג'), 83);
+        assert_eq!(gmctx.calculate_char_value('ד'), 434);
+        assert_eq!(gmctx.calculate_char_value('ה'), 6);
+        assert_eq!(gmctx.calculate_char_value('ו'), 22);
+        assert_eq!(gmctx.calculate_char_value('ז'), 67);
+        assert_eq!(gmctx.calculate_char_value('ח'), 418);
+        assert_eq!(gmctx.calculate_char_value('ט'), 419);
+        assert_eq!(gmctx.calculate_char_value('י'), 20);
+        assert_eq!(gmctx.calculate_char_value('כ'), 100);
+        assert_eq!(gmctx.calculate_char_value('ל'), 74);
+        assert_eq!(gmctx.calculate_char_value('מ'), 80);
+        assert_eq!(gmctx.calculate_char_value('נ'), 106);
+        assert_eq!(gmctx.calculate_char_value('ס'), 120);
+        assert_eq!(gmctx.calculate_char_value('ע'), 130);
+        assert_eq!(gmctx.calculate_char_value('פ'), 81);
+        assert_eq!(gmctx.calculate_char_value('צ'), 104);
+        assert_eq!(gmctx.calculate_char_value('ק'), 186);
+        assert_eq!(gmctx.calculate_char_value('ר'), 510);
+        assert_eq!(gmctx.calculate_char_value('ש'), 360);
+        assert_eq!(gmctx.calculate_char_value('ת'), 416);
+    }
+
+    #[test]
+    fn test_hechrechi_final_forms() {
+        let gmctx = GematriaBuilder::new()
+            .with_method(GematriaMethod::MisparHechrechi)
+            .init_gematria();
+
+        let character = 'צ';
+        let character_final = 'ץ';
+        let value = gmctx.calculate_char_value(character);
+        let value_same = gmctx.calculate_char_value(character_final);
+
+        assert_eq!(value, value_same);
+    }
+
+    #[test]
+    fn test_gadol_final_forms() {
+        let gmctx = GematriaBuilder::new()
+            .with_method(GematriaMethod::MisparGadol)
+            .init_gematria();
+
+        let character = 'צ';
+        let character_final = 'ץ';
+        let value = gmctx.calculate_char_value(character);
+        let value_not_same = gmctx.calculate_char_value(character_final);
+
+        assert_ne!(value, value_not_same);
+        assert_eq!(value_not_same, 900);
+    }
+
+    #[test]
+    fn test_katan_final_forms() {
+        let gmctx = GematriaBuilder::new()
+            .with_method(GematriaMethod::MisparKatan)
+            .init_gematria();
+
+        let character = 'צ';
+        let character_final = 'ץ';
+        let value = gmctx.calculate_char_value(character);
+        let value_same = gmctx.calculate_char_value(character_final);
+
+        assert_eq!(value, value_same);
+        assert_eq!(value_same, 9);
+    }
+
+    #[test]
+    fn test_achbi() {
+        let gmctx = GematriaBuilder::new()
+            .with_method(GematriaMethod::Achbi)
+            .init_gematria();
+
+        assert_eq!(gmctx.calculate_char_value('א'), 20);
+        assert_eq!(gmctx.calculate_char_value('ו'), 6);
+        assert_eq!(gmctx.calculate_char_value('ל'), 400);
+        assert_eq!(gmctx.calculate_char_value('ת'), 30);
+        assert_eq!(gmctx.calculate_char_value('ך'), 1);
+        // בבל is encoded as יית
+        assert_eq!(gmctx.calculate_value("בבל").value(), 420);
+    }
+
+    #[test]
+    fn test_avgad() {
+        let gmctx = GematriaBuilder::new()
+            .with_method(GematriaMethod::Avgad)
+            .init_gematria();
+
+        assert_eq!(gmctx.calculate_char_value('א'), 2);
+        assert_eq!(gmctx.calculate_char_value('ת'), 1);
+        assert_eq!(gmctx.calculate_char_value('ך'), 30);
+        // יהוה is encoded as כוזו
+        assert_eq!(gmctx.calculate_value("יהוה").value(), 39);
+    }
+
+    #[test]
+    fn test_avgad_shift() {
+        let mut gmctx = GematriaBuilder::new()
+            .with_method(GematriaMethod::Avgad)
+            .with_shift(-1)
+            .with_cache(true)
+            .init_gematria();
+
+        assert_eq!(gmctx.calculate_char_value('א'), 400);
+        assert_eq!(gmctx.calculate_value("בג").value(), 3);
+
+        gmctx.set_shift(23);
+        assert_eq!(gmctx.calculate_char_value('א'), 2);
+        assert_eq!(gmctx.calculate_value("בג").value(), 7);
+    }
+
+    #[test]
+    fn test_ayak_bachar() {
+        let gmctx = GematriaBuilder::new()
+            .with_method(GematriaMethod::AyakBachar)
+            .init_gematria();
+
+        assert_eq!(gmctx.calculate_char_value('א'), 10);
+        assert_eq!(gmctx.calculate_char_value('י'), 100);
+        assert_eq!(gmctx.calculate_char_value('ק'), 1);
+        assert_eq!(gmctx.calculate_char_value('נ'), 500);
+        assert_eq!(gmctx.calculate_char_value('ץ'), 9);
+        // אבג is encoded as יכל
+        assert_eq!(gmctx.calculate_value("אבג").value(), 60);
+    }
+
+    #[test]
+    fn test_kidmi() {
+        let gmctx = GematriaBuilder::new()
+            .with_method(GematriaMethod::MisparKidmi)
+            .init_gematria();
+
+        assert_eq!(gmctx.calculate_char_value('א'), 1);
+        assert_eq!(gmctx.calculate_char_value('ד'), 10);
+        assert_eq!(gmctx.calculate_char_value('י'), 55);
+        assert_eq!(gmctx.calculate_char_value('כ'), 75);
+        assert_eq!(gmctx.calculate_char_value('ך'), 75);
+        assert_eq!(gmctx.calculate_char_value('ת'), 1495);
+        assert_eq!(gmctx.calculate_value("אב").value(), 4);
+    }
+
+    #[test]
+    fn test_perati() {
+        let gmctx = GematriaBuilder::new()
+            .with_method(GematriaMethod::MisparPerati)
+            .init_gematria();
+
+        assert_eq!(gmctx.calculate_char_value('ב'), 4);
+        assert_eq!(gmctx.calculate_char_value('ת'), 160000);
+        assert_eq!(gmctx.calculate_char_value('ץ'), 8100);
+        assert_eq!(
+            gmctx.calculate_value("שלום").value(),
+            90000 + 900 + 36 + 1600
+        );
+
+        // Totals do not overflow u32
+        let long_word = "ת".repeat(30000);
+        assert_eq!(gmctx.calculate_value(&long_word).value(), 4_800_000_000);
+        assert_eq!(gmctx.calculate_value(&long_word).value_u32(), None);
+    }
+
+    #[test]
+    fn test_modifiers() {
+        let shalom = "שלום";
+        let with = |modifier| {
+            GematriaBuilder::new()
+                .with_modifier(modifier)
+                .with_cache(true)
+                .init_gematria()
+                .calculate_value(shalom)
+                .value()
+        };
+
+        assert_eq!(with(Modifier::ReduceToSingleDigit), 7);
+        assert_eq!(with(Modifier::Modulo(26)), 12);
+        assert_eq!(with(Modifier::Modulo(0)), 376);
+        assert_eq!(with(Modifier::PlusKolel), 377);
+        assert_eq!(with(Modifier::SquareTotal), 141376);
+
+        // Modifiers wrap any base method and are applied in order
+        let gmctx = GematriaBuilder::new()
+            .with_method(GematriaMethod::MisparGadol)
+            .with_modifier(Modifier::SquareTotal)
+            .with_modifier(Modifier::Modulo(1000))
+            .init_gematria();
+        assert_eq!(gmctx.calculate_value(shalom).value(), 936 * 936 % 1000);
+
+        // Overflowing totals saturate, or fail when checked
+        assert_eq!(Modifier::SquareTotal.checked_apply(u64::MAX), None);
+        assert_eq!(Modifier::PlusKolel.apply(u64::MAX), u64::MAX);
+        let gmctx = GematriaBuilder::new()
+            .with_method(GematriaMethod::MisparHaMerubahHaKlali)
+            .with_modifier(Modifier::SquareTotal)
+            .with_cache(true)
+            .init_gematria();
+        assert_eq!(gmctx.calculate_value(shalom).value(), 141376 * 141376);
+        let text = "ת".repeat(100_000);
+        assert_eq!(
+            gmctx.calculate_value_checked(&text),
+            Err(GematriaError::Overflow)
+        );
+        assert_eq!(gmctx.calculate_value(&text).value(), u64::MAX);
+        assert_eq!(gmctx.calculate_value_raw(&text), u64::MAX);
+
+        // Totals of saturated values saturate too, rather than overflowing
+        let gmctx = GematriaBuilder::new()
+            .with_modifier(Modifier::SquareTotal)
+            .with_modifier(Modifier::SquareTotal)
+            .with_modifier(Modifier::SquareTotal)
+            .init_gematria();
+        let text = "שלום שלום";
+        assert_eq!(
+            gmctx.calculate_document(text, AggregatePolicy::Sum),
+            u64::MAX
+        );
+        assert!(!gmctx.search_matching_phrases(&u64::MAX, text, 2).is_empty());
+        let found = gmctx.find_phrases(u64::MAX, text, &SearchLimits::new());
+        assert!(found.items().iter().all(|m| m.value() == u64::MAX));
+        let mut tracker = MilestoneTracker::new([u64::MAX], |_| {});
+        tracker.observe(&gmctx.calculate_value("שלום"));
+        tracker.observe(&gmctx.calculate_value("שלום"));
+        assert_eq!(tracker.running_total(), u64::MAX);
+    }
+
+    #[test]
+    fn test_merubah_haklali() {
+        let gmctx = GematriaBuilder::new()
+            .with_method(GematriaMethod::MisparHaMerubahHaKlali)
+            .init_gematria();
+
+        assert_eq!(gmctx.calculate_value("שלום").value(), 141376);
+        assert_eq!(gmctx.calculate_value("אב").value(), 9);
+        // Letters keep their standard value
+        assert_eq!(gmctx.calculate_char_value('ב'), 2);
+    }
+
+    #[test]
+    fn test_katan_mispari() {
+        let katan = GematriaBuilder::new()
+            .with_method(GematriaMethod::MisparKatan)
+            .init_gematria();
+        let mispari = GematriaBuilder::new()
+            .with_method(GematriaMethod::MisparKatanMispari)
+            .init_gematria();
+
+        // The per letter reduction is kept, the total is reduced as well
+        assert_eq!(katan.calculate_value("שלום").value(), 18);
+        assert_eq!(mispari.calculate_value("שלום").value(), 9);
+        assert_eq!(mispari.calculate_value("בעזרת השם").value(), 9);
+        assert_eq!(mispari.calculate_char_value('ת'), 4);
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn test_value_u32_compat() {
+        assert_eq!(
+            "שלום".gematria_val_u32(&GematriaMethod::MisparHechrechi),
+            376
+        );
+        assert_eq!(
+            "ת".repeat(30000)
+                .gematria_val_u32(&GematriaMethod::MisparPerati),
+            u32::MAX
+        );
+    }
+
+    #[test]
+    fn test_neelam() {
+        let gmctx = GematriaBuilder::new()
+            .with_method(GematriaMethod::MisparNeelam)
+            .init_gematria();
+
+        assert_eq!(gmctx.calculate_char_value('א'), 110);
+        assert_eq!(gmctx.calculate_char_value('ה'), 1);
+        assert_eq!(gmctx.calculate_char_value('ם'), 40);
+        // יוד, הא, ויו, הא
+        assert_eq!(gmctx.calculate_value("יהוה").value(), 10 + 1 + 16 + 1);
+    }
+
+    #[test]
+    fn test_milui_hamilui() {
+        let gmctx = GematriaBuilder::new()
+            .with_method(GematriaMethod::MiluiHaMilui)
+            .init_gematria();
+
+        // הא -> הא + אלף
+        assert_eq!(gmctx.calculate_char_value('ה'), 6 + 111);
+        // אלף -> אלף + למד + פא
+        assert_eq!(gmctx.calculate_char_value('א'), 111 + 74 + 81);
+        assert_eq!(gmctx.get_current_method(), GematriaMethod::MiluiHaMilui);
+    }
+
+    #[test]
+    fn test_achas_beta() {
+        let gmctx = GematriaBuilder::new()
+            .with_method(GematriaMethod::AchasBeta)
+            .init_gematria();
+
+        // שלום -> זקמר
+        assert_eq!(gmctx.calculate_value("שלום").value(), 7 + 100 + 40 + 200);
+        assert_eq!(gmctx.calculate_char_value('ת'), 400);
+    }
+
+    #[test]
+    fn test_milui_traditions() {
+        let milui = |tradition| {
+            GematriaBuilder::new()
+                .with_method(GematriaMethod::OtiyotBeMilui)
+                .with_milui_tradition(tradition)
+                .init_gematria()
+                .calculate_value("יהוה")
+                .value()
+        };
+
+        assert_eq!(milui(MiluiTradition::Av), 72);
+        assert_eq!(milui(MiluiTradition::Sag), 63);
+        assert_eq!(milui(MiluiTradition::Mah), 45);
+        assert_eq!(milui(MiluiTradition::Ban), 52);
+    }
+
+    #[test]
+    fn test_milui_spelling_override() {
+        let mut gmctx = GematriaBuilder::new()
+            .with_method(GematriaMethod::MisparNeelam)
+            .with_cache(true)
+            .with_milui_tradition(MiluiTradition::Ban)
+            .with_milui_spelling('ה', "הי")
+            .init_gematria();
+
+        assert_eq!(gmctx.calculate_char_value('ה'), 10);
+        assert_eq!(gmctx.calculate_char_value('ו'), 6);
+
+        gmctx.set_milui_spelling('ה', "הא");
+        assert_eq!(gmctx.calculate_char_value('ה'), 1);
+    }
+
+    #[test]
+    fn test_kolel() {
+        let mut gmctx = GematriaBuilder::new()
+            .with_cache(true)
+            .with_kolel(Kolel::Letters)
+            .init_gematria();
+
+        let result = gmctx.calculate_value("בְּעֶזְרַת הַשֵּׁם");
+        assert_eq!(result.value(), 1024 + 8);
+        assert_eq!(result.kolel(), Some(Kolel::Letters));
+
+        gmctx.set_kolel(Some(Kolel::One));
+        assert_eq!(gmctx.calculate_value("בְּעֶזְרַת הַשֵּׁם").value(), 1025);
+
+        gmctx.set_kolel(None);
+        let result = gmctx.calculate_value("בְּעֶזְרַת הַשֵּׁם");
+        assert_eq!(result.value(), 1024);
+        assert_eq!(result.kolel(), None);
+    }
+
+    #[test]
+    fn test_haakhor() {
+        let gmctx = GematriaBuilder::new()
+            .with_method(GematriaMethod::MisparHaAkhor)
+            .init_gematria();
+
+        assert_eq!(gmctx.calculate_value("שָׁלוֹם").value(), 300 + 60 + 18 + 160);
+        // Positions restart on every word
+        assert_eq!(gmctx.calculate_value("אב אב").value(), 2 * (1 + 4));
+        assert_eq!(gmctx.calculate_char_value('ת'), 400);
+    }
+
+    #[test]
+    fn test_to_map() {
+        let gmctx = GematriaBuilder::new()
+            .with_kolel(Kolel::One)
+            .init_gematria();
+        let map = gmctx.calculate_value("בעזרת השם").to_map();
+
+        assert_eq!(map.len(), 5);
+        assert_eq!(map["value"], "1025");
+        assert_eq!(map["reduced_value"], "8");
+        assert_eq!(map["kolel"], "One");
+
+        let map = Match::new("סוד".to_string(), 70, 3).to_map();
+        assert_eq!(map["index"], "3");
+    }
+
+    #[test]
+    fn test_nikud() {
+        let gmctx = GematriaBuilder::new()
+            .with_method(GematriaMethod::Nikud)
+            .init_gematria();
 
-        // Filter out entries with only one word
-        grouped_words.retain(|_, v| v.len() > 1);
+        // Kamatz, chataf patach and segol
+        assert_eq!(gmctx.calculate_value("אָבֲגֶ").value(), 16 + 26 + 30);
+        // Letters, dagesh and shin dot are not valued, the word has a cholam
+        let result = gmctx.calculate_value("שָׁלוֹם");
+        assert_eq!(result.value(), 16 + 10);
+        assert_eq!(result.word(), "שלום");
+        assert_eq!(gmctx.calculate_char_value('\u{05B0}'), 20);
+        assert_eq!(gmctx.calculate_char_value('א'), 0);
+    }
 
-        // Convert HashMap to Vec and sort by the length of the vectors
-        let mut grouped_vec: Vec<(u32, Vec<String>)> = grouped_words.into_iter().collect();
+    #[test]
+    fn test_custom_strategy() {
+        struct Doubled;
 
-        // Sort by the length of the vectors (primary) and gematria value (secondary)
-        grouped_vec.sort_by(|a, b| match b.1.len().cmp(&a.1.len()) {
-            std::cmp::Ordering::Equal => a.0.cmp(&b.0),
-            other => other,
-        });
+        impl GematriaCalculation for Doubled {
+            fn calculate_value(&self, letter_index: u32) -> u32 {
+                2 * std_gematria_value(&letter_index)
+            }
 
-        // Return the sorted Vec
-        Ok(grouped_vec)
-    }
+            fn method_type(&self) -> GematriaMethod {
+                GematriaMethod::Custom
+            }
+        }
 
-    /// Gets the index of a Hebrew character.
-    pub fn get_character_index(&self, character: &char) -> Option<&u32> {
-        self.character_map.char_to_index.get(character)
+        let builder = GematriaBuilder::new()
+            .with_cache(true)
+            .with_strategy(Box::new(Doubled))
+            .with_shift(3);
+        let mut gmctx = builder.clone().init_gematria();
+        assert_eq!(gmctx.calculate_value("אב").value(), 6);
+        // Changing the settings of built-in methods keeps the custom strategy
+        gmctx.set_shift(2);
+        assert_eq!(gmctx.calculate_value("אב").value(), 6);
+
+        gmctx.set_method(GematriaMethod::MisparHechrechi);
+        assert_eq!(gmctx.calculate_value("אב").value(), 3);
+        assert_eq!(builder.init_gematria().calculate_value("ג").value(), 6);
     }
 
-    /// Sets the current gematria method to desired one.
-    pub fn set_method(&mut self, method: GematriaMethod) {
-        self.calculation_strategy = process_method_dyn(method, self.character_map.clone());
-    }
-}
+    #[test]
+    fn test_custom_char_map() {
+        // Hebrew letters mapped to english letters
+        let gmctx = GematriaBuilder::new()
+            .with_char_map(CharMap::from([('a', 1), ('b', 2), ('k', 11)]))
+            .init_gematria();
 
-/// `GematriaResult` used for structured result of calculations.
-impl GematriaResult {
-    /// Creates a new result object.
-    pub fn new(value: u32, method: GematriaMethod, word: String) -> Self {
-        GematriaResult {
-            method,
-            value,
-            word,
+        assert_eq!(gmctx.calculate_value("abk").value(), 23);
+        assert_eq!(gmctx.calculate_value("שלום").value(), 0);
+        assert_eq!(gmctx.character_map().index_of('k'), Some(11));
+
+        // The Hebrew block table follows the mapping
+        let map = HebrewCharacterMap::from_pairs([('א', 2), ('ת', 1), ('a', 3)]);
+        assert_eq!(map.index_of('א'), Some(2));
+        assert_eq!(map.index_of('ת'), Some(1));
+        assert_eq!(map.index_of('ב'), None);
+        assert_eq!(map.index_of('a'), Some(3));
+        assert_eq!(map.index_of('\u{05EB}'), None);
+
+        // Indices the methods do not value are refused
+        for index in [0, 28] {
+            let result = GematriaBuilder::new()
+                .with_char_map(CharMap::from([('a', index)]))
+                .try_init_gematria();
+            assert!(matches!(result, Err(GematriaError::InvalidLetterIndex('a', i)) if i == index));
         }
     }
 
-    /// Gets the gematria value.
-    pub fn value(&self) -> u32 {
-        self.value
+    #[test]
+    fn test_calculate_all_methods() {
+        let gmctx = GematriaBuilder::new()
+            .with_kolel(Kolel::One)
+            .with_milui_tradition(MiluiTradition::Sag)
+            .init_gematria();
+        let values = gmctx.calculate_all_methods("יהוה");
+
+        assert_eq!(values.len(), GematriaMethod::IMPLEMENTED.len());
+        for method in GematriaMethod::IMPLEMENTED {
+            // Greek and English methods default to their alphabet, so the alphabet of the context is given
+            let expected = GematriaBuilder::new()
+                .with_method(method)
+                .with_char_map(gmctx.character_map().char_map().clone())
+                .with_kolel(Kolel::One)
+                .with_milui_tradition(MiluiTradition::Sag)
+                .init_gematria()
+                .calculate_value("יהוה")
+                .value();
+            assert_eq!(values[&method], expected, "{:?}", method);
+        }
+        assert_eq!(values[&GematriaMethod::OtiyotBeMilui], 64);
     }
 
-    /// Gets the calculation method used.
-    pub fn method(&self) -> &GematriaMethod {
-        &self.method
+    #[test]
+    fn test_method_display_and_parse() {
+        for method in GematriaMethod::all() {
+            assert_eq!(method.to_string().parse::<GematriaMethod>(), Ok(method));
+            assert_eq!(
+                format!("{:?}", method).parse::<GematriaMethod>(),
+                Ok(method)
+            );
+        }
+        assert_eq!(
+            "mispar-ha-merubah-ha-klali".parse::<GematriaMethod>(),
+            Ok(GematriaMethod::MisparHaMerubahHaKlali)
+        );
+
+        let error = "Custom".parse::<GematriaMethod>().unwrap_err();
+        assert_eq!(error.input(), "Custom");
+        assert!(error.to_string().contains("Mispar Gadol"));
+        assert!("MisparSiduri".parse::<GematriaMethod>().is_err());
     }
 
-    /// Gets the word for which the gematria value was calculated.
-    pub fn word(&self) -> &str {
-        &self.word
+    #[test]
+    fn test_calculate_value_strict() {
+        let gmctx = GematriaContext::default();
+        assert_eq!(gmctx.calculate_value_strict("תשפ״ד").unwrap().value(), 784);
+
+        let error = gmctx.calculate_value_strict("1 אב c").unwrap_err();
+        assert_eq!(
+            error,
+            GematriaError::InvalidCharacters(vec![(0, '1'), (5, 'c')])
+        );
+        assert_eq!(error.to_string(), "invalid characters: '1' at 0, 'c' at 5");
     }
-}
-
-// Helper function to add a word to the vector if it doesn't already exist
-trait PushIfNotExists {
-    fn push_if_not_exists(&mut self, item: String);
-}
 
-impl PushIfNotExists for Vec<String> {
-    fn push_if_not_exists(&mut self, item: String) {
-        if !self.contains(&item) {
-            self.push(item);
+    #[test]
+    fn test_unsupported_methods() {
+        for method in [GematriaMethod::MisparBoneh, GematriaMethod::MisparMusafi] {
+            let result = GematriaBuilder::new()
+                .with_method(method)
+                .try_init_gematria();
+            assert!(matches!(result, Err(GematriaError::UnsupportedMethod(m)) if m == method));
         }
-    }
-}
 
-/// Used to implement `Into` functionality for easy calculations.
-pub trait IntoGematriaVal {
-    /// Calculate the value for a given cipher
-    fn gematria_val(&self, method: &GematriaMethod) -> u32;
-}
+        let mut gmctx = GematriaContext::default();
+        assert_eq!(
+            gmctx.try_set_method(GematriaMethod::Custom),
+            Err(GematriaError::UnsupportedMethod(GematriaMethod::Custom))
+        );
+        assert_eq!(gmctx.get_current_method(), GematriaMethod::MisparHechrechi);
+        assert_eq!(gmctx.try_set_method(GematriaMethod::MisparGadol), Ok(()));
+        assert_eq!(gmctx.calculate_value("ם").value(), 600);
+    }
 
-impl IntoGematriaVal for char {
-    /// # Examples
-    /// ```
-    /// use gematria_rs::*;
-    ///
-    /// let val = 'א'.gematria_val(&GematriaMethod::MisparHechrechi);
-    /// assert_eq!(val, 1)
-    /// ```
-    fn gematria_val(&self, method: &GematriaMethod) -> u32 {
+    #[test]
+    fn test_breakdown() {
         let gmctx = GematriaBuilder::new()
-            .with_method(*method)
-            .with_vowels(true)
+            .with_method(GematriaMethod::MisparHaAkhor)
+            .with_cache(true)
             .init_gematria();
-        gmctx.calculate_char_value(*self)
-    }
-}
+        let expected = [('א', 1), ('ב', 4), ('־', 0), ('ג', 3), ('!', 0)];
+
+        assert_eq!(gmctx.calculate_value("אב־ג !").breakdown(), &expected);
+        // Cached results keep their breakdown
+        assert_eq!(gmctx.calculate_value("אב־ג !").breakdown(), &expected);
 
-impl IntoGematriaVal for String {
-    /// # Examples
-    /// ```
-    /// use gematria_rs::*;
-    ///
-    /// let val = "בעזרת השם".to_string().gematria_val(&GematriaMethod::MisparHechrechi);
-    /// assert_eq!(val, 1024)
-    /// ```
-    fn gematria_val(&self, method: &GematriaMethod) -> u32 {
         let gmctx = GematriaBuilder::new()
-            .with_method(*method)
-            .with_vowels(true)
+            .with_method(GematriaMethod::Nikud)
             .init_gematria();
-        gmctx.calculate_value(self).value()
+        let result = gmctx.calculate_value("שָׁ");
+        assert_eq!(result.breakdown(), &[('ש', 0), ('ָ', 16), ('ׁ', 0)]);
+        assert_eq!(result.word(), "ש");
     }
-}
 
-impl IntoGematriaVal for str {
-    /// # Examples
-    /// ```
-    /// use gematria_rs::*;
-    ///
-    /// let val = "בעזרת השם".gematria_val(&GematriaMethod::MisparHechrechi);
-    /// assert_eq!(val, 1024)
-    /// ```
-    fn gematria_val(&self, method: &GematriaMethod) -> u32 {
+    #[test]
+    fn test_result_equality() {
+        let gmctx = GematriaContext::default();
+        let a = gmctx.calculate_value("שָׁלוֹם");
+        let b = gmctx.calculate_value("שלום");
+
+        // Vowels are removed from the word, so only the breakdown differs
+        assert_eq!(a, b);
+        assert_eq!(std::collections::HashSet::from([a, b]).len(), 1);
+        assert!(gmctx.calculate_value("יהוה") < gmctx.calculate_value("סוד"));
+        assert_ne!(
+            gmctx.calculate_value("שלום"),
+            GematriaResult::new(376, GematriaMethod::MisparGadol, "שלום".to_string())
+        );
+        assert_eq!(
+            gmctx.calculate_value("יין").to_string(),
+            "יין = 70 (MisparHechrechi)"
+        );
+    }
+
+    #[test]
+    fn test_original_and_normalized_word() {
         let gmctx = GematriaBuilder::new()
-            .with_method(*method)
             .with_vowels(true)
+            .with_cache(true)
             .init_gematria();
-        gmctx.calculate_value(self).value()
-    }
-}
+        for _ in 0..2 {
+            let result = gmctx.calculate_value("בְּרֵאשִׁית");
+            assert_eq!(result.word(), "בְּרֵאשִׁית");
+            assert_eq!(result.original_word(), "בְּרֵאשִׁית");
+            assert_eq!(result.normalized_word(), "בראשית");
+        }
 
-#[cfg(test)]
-mod tests {
-    // Tests to validate the functionality of the Gematria-rs library
-    use super::*;
+        let result = GematriaResult::new(1, GematriaMethod::MisparHechrechi, "אָ".to_string());
+        assert_eq!(result.original_word(), "אָ");
+        assert_eq!(result.normalized_word(), "א");
+    }
 
     #[test]
-    fn test_default_map() {
-        let gmctx = GematriaContext::default();
-
-        assert_eq!(gmctx.get_current_method(), GematriaMethod::MisparHechrechi);
-        assert_eq!(gmctx.calculate_char_value('א'), 1);
-        assert_eq!(gmctx.calculate_char_value('ב'), 2);
-        assert_eq!(gmctx.calculate_char_value('ג'), 3);
-        assert_eq!(gmctx.calculate_char_value('ד'), 4);
-        assert_eq!(gmctx.calculate_char_value('ה'), 5);
-        assert_eq!(gmctx.calculate_char_value('ו'), 6);
-        assert_eq!(gmctx.calculate_char_value('ז'), 7);
-        assert_eq!(gmctx.calculate_char_value('ח'), 8);
-        assert_eq!(gmctx.calculate_char_value('ט'), 9);
-        assert_eq!(gmctx.calculate_char_value('י'), 10);
-        assert_eq!(gmctx.calculate_char_value('כ'), 20);
-        assert_eq!(gmctx.calculate_char_value('ל'), 30);
-        assert_eq!(gmctx.calculate_char_value('מ'), 40);
-        assert_eq!(gmctx.calculate_char_value('נ'), 50);
-        assert_eq!(gmctx.calculate_char_value('ס'), 60);
-        assert_eq!(gmctx.calculate_char_value('ע'), 70);
-        assert_eq!(gmctx.calculate_char_value('פ'), 80);
-        assert_eq!(gmctx.calculate_char_value('צ'), 90);
-        assert_eq!(gmctx.calculate_char_value('ק'), 100);
-        assert_eq!(gmctx.calculate_char_value('ר'), 200);
-        assert_eq!(gmctx.calculate_char_value('ש'), 300);
-        assert_eq!(gmctx.calculate_char_value('ת'), 400);
-        assert_eq!(gmctx.calculate_char_value('ך'), 20);
-        assert_eq!(gmctx.calculate_char_value('ם'), 40);
-        assert_eq!(gmctx.calculate_char_value('ן'), 50);
-        assert_eq!(gmctx.calculate_char_value('ף'), 80);
-        assert_eq!(gmctx.calculate_char_value('ץ'), 90);
+    fn test_shared_between_threads() {
+        fn assert_send_sync<T: Send + Sync>() {}
+        assert_send_sync::<GematriaContext>();
+
+        let gmctx = Arc::new(GematriaBuilder::new().with_cache(true).init_gematria());
+        let values: Vec<u64> = std::thread::scope(|scope| {
+            let handles: Vec<_> = ["שלום", "אמת", "שלום"]
+                .into_iter()
+                .map(|word| {
+                    let gmctx = Arc::clone(&gmctx);
+                    scope.spawn(move || gmctx.calculate_value(word).value())
+                })
+                .collect();
+            handles.into_iter().map(|h| h.join().unwrap()).collect()
+        });
+        assert_eq!(values, [376, 441, 376]);
+        assert_eq!(gmctx.cache.as_ref().unwrap().lock().len(), 2);
     }
 
     #[test]
-    fn test_filled_letters_map() {
-        let gmctx = GematriaBuilder::new()
-            .with_method(GematriaMethod::OtiyotBeMilui)
-            .init_gematria();
+    fn test_calculate_value_raw() {
+        let text = "בְּרֵאשִׁית בָּרָא רמב״ם \u{FB2A}לום";
+        for method in GematriaMethod::IMPLEMENTED {
+            let gmctx = GematriaBuilder::new()
+                .with_method(method)
+                .with_kolel(Kolel::Words)
+                .init_gematria();
+            assert_eq!(
+                gmctx.calculate_value_raw(text),
+                gmctx.calculate_value(text).value(),
+                "{}",
+                method
+            );
+        }
+    }
 
-        assert_eq!(gmctx.calculate_char_value('א'), 111);
-        assert_eq!(gmctx.calculate_char_value('ב'), 412);
-        assert_eq!(gmctx.calculate_char_value('ג'), 83);
-        assert_eq!(gmctx.calculate_char_value('ד'), 434);
-        assert_eq!(gmctx.calculate_char_value('ה'), 6);
-        assert_eq!(gmctx.calculate_char_value('ו'), 22);
-        assert_eq!(gmctx.calculate_char_value('ז'), 67);
-        assert_eq!(gmctx.calculate_char_value('ח'), 418);
-        assert_eq!(gmctx.calculate_char_value('ט'), 419);
-        assert_eq!(gmctx.calculate_char_value('י'), 20);
-        assert_eq!(gmctx.calculate_char_value('כ'), 100);
-        assert_eq!(gmctx.calculate_char_value('ל'), 74);
-        assert_eq!(gmctx.calculate_char_value('מ'), 80);
-        assert_eq!(gmctx.calculate_char_value('נ'), 106);
-        assert_eq!(gmctx.calculate_char_value('ס'), 120);
-        assert_eq!(gmctx.calculate_char_value('ע'), 130);
-        assert_eq!(gmctx.calculate_char_value('פ'), 81);
-        assert_eq!(gmctx.calculate_char_value('צ'), 104);
-        assert_eq!(gmctx.calculate_char_value('ק'), 186);
-        assert_eq!(gmctx.calculate_char_value('ר'), 510);
-        assert_eq!(gmctx.calculate_char_value('ש'), 360);
-        assert_eq!(gmctx.calculate_char_value('ת'), 416);
+    #[test]
+    fn test_const_word_values() {
+        let word = "בראשיתךםןףץ";
+        for method in GematriaMethod::IMPLEMENTED {
+            let gmctx = GematriaBuilder::new().with_method(method).init_gematria();
+            if let Some(value) = method.word_value(word) {
+                assert_eq!(value, gmctx.calculate_value(word).value(), "{}", method);
+            }
+            // The total of Mispar HaMerubah HaKlali is squared, so a letter is not valued as its word
+            if method != GematriaMethod::MisparHaMerubahHaKlali {
+                assert_eq!(
+                    method.letter_value(22).map(u64::from),
+                    method.word_value("ת"),
+                    "{}",
+                    method
+                );
+            }
+        }
+        assert_eq!(GematriaMethod::MisparGadol.word_value("ץ"), Some(900));
+        assert_eq!(GematriaMethod::MisparNeelam.word_value("ת"), None);
     }
 
     #[test]
-    fn test_hechrechi_final_forms() {
-        let gmctx = GematriaBuilder::new()
-            .with_method(GematriaMethod::MisparHechrechi)
-            .init_gematria();
+    fn test_search_iter_is_lazy() {
+        let gmctx = GematriaBuilder::new().with_cache(true).init_gematria();
+        let mut matches = gmctx.search_matching_values_iter(&70, "יין נכנס סוד יצא");
+
+        assert_eq!(matches.next().unwrap().word(), "יין");
+        // Words past the first match were not calculated yet
+        assert_eq!(gmctx.cache.as_ref().unwrap().lock().len(), 1);
+        assert_eq!(matches.next().unwrap().byte_range(), 16..22);
+        assert!(matches.next().is_none());
+    }
 
-        let character = 'צ';
-        let character_final = 'ץ';
-        let value = gmctx.calculate_char_value(character);
-        let value_same = gmctx.calculate_char_value(character_final);
+    #[test]
+    fn test_search_matching_phrases() {
+        let gmctx = GematriaContext::default();
+        let text = "א ב ג ! ד";
+        let phrases = |target, max_words| -> Vec<String> {
+            gmctx
+                .search_matching_phrases(&target, text, max_words)
+                .iter()
+                .map(|m| m.word().to_string())
+                .collect()
+        };
 
-        assert_eq!(value, value_same);
+        // Single words are not phrases, words without a value extend phrases
+        assert_eq!(phrases(3, 4), vec!["א ב", "ג !"]);
+        assert_eq!(phrases(5, 2), vec!["ב ג"]);
+        assert_eq!(phrases(5, 3), vec!["ב ג", "ב ג !"]);
+        // The same phrases as the limited search
+        let found = gmctx.find_phrases(5, text, &SearchLimits::new().with_max_depth(3));
+        let words: Vec<&str> = found.items().iter().map(|m| m.word()).collect();
+        assert_eq!(words, phrases(5, 3));
+        assert!(found.is_truncated());
+        assert_eq!(phrases(9, 4), vec!["ב ג ! ד"]);
+        assert!(phrases(9, 3).is_empty());
+
+        assert!(phrases(7, 2).is_empty());
+        let matches = gmctx.search_matching_phrases(&7, text, 3);
+        assert_eq!(matches[0].index(), 2);
+        assert_eq!(&text[matches[0].byte_range()], "ג ! ד");
     }
 
     #[test]
-    fn test_gadol_final_forms() {
-        let gmctx = GematriaBuilder::new()
-            .with_method(GematriaMethod::MisparGadol)
-            .init_gematria();
+    fn test_search_near_value() {
+        let gmctx = GematriaContext::default();
+        let text = "ג א ה ב ת";
+
+        let matches = gmctx.search_near_value(&3, 2, text);
+        let found: Vec<(&str, usize)> = matches.iter().map(|m| (m.word(), m.index())).collect();
+        assert_eq!(found, vec![("ג", 0), ("ב", 3), ("א", 1), ("ה", 2)]);
+        // The range saturates instead of overflowing
+        assert_eq!(gmctx.search_near_value(&1, 5, text).len(), 4);
+        assert_eq!(gmctx.search_near_value(&u64::MAX, u64::MAX, text).len(), 5);
+        assert!(gmctx.search_near_value(&200, 0, text).is_empty());
+    }
 
-        let character = 'צ';
-        let character_final = 'ץ';
-        let value = gmctx.calculate_char_value(character);
-        let value_not_same = gmctx.calculate_char_value(character_final);
+    #[test]
+    fn test_search_matching_filter() {
+        let gmctx = GematriaContext::default();
+        let is_prime = |n: u64| {
+            n > 1
+                && (2..n)
+                    .take_while(|d| d * d <= n)
+                    .all(|d| !n.is_multiple_of(d))
+        };
 
-        assert_ne!(value, value_not_same);
-        assert_eq!(value_not_same, 900);
+        let mut primes =
+            gmctx.search_matching_filter("א ב ג ד יז", |value, _| is_prime(value));
+        let first = primes.next().unwrap();
+        assert_eq!((first.word(), first.index()), ("ב", 1));
+        let rest: Vec<u64> = primes.map(|m| m.value()).collect();
+        assert_eq!(rest, vec![3, 17]);
+
+        // Words are found as calculated, vowels and presentation forms included
+        let text = "שָׁלוֹם \u{FB2A}לום סוד";
+        let words: Vec<(String, u64)> = gmctx
+            .search_matching_filter(text, |_, _| true)
+            .map(|m| (m.word().to_string(), m.value()))
+            .collect();
+        let expected: Vec<(String, u64)> = text
+            .split(' ')
+            .map(|word| gmctx.calculate_value(word))
+            .map(|result| (result.word().to_string(), result.value()))
+            .collect();
+        assert_eq!(words, expected);
     }
 
     #[test]
-    fn test_katan_final_forms() {
-        let gmctx = GematriaBuilder::new()
-            .with_method(GematriaMethod::MisparKatan)
-            .init_gematria();
+    fn test_group_words_with_anagrams() {
+        let gmctx = GematriaBuilder::new().with_vowels(true).init_gematria();
+        let groups = gmctx.group_words_with_anagrams("מלך צ כלם לָמֶךְ ל").unwrap();
+
+        assert_eq!(groups.len(), 1);
+        let (value, clusters) = &groups[0];
+        assert_eq!(*value, 90);
+        assert_eq!(
+            clusters,
+            &vec![
+                vec!["מלך".to_string(), "כלם".to_string(), "לָמֶךְ".to_string()],
+                vec!["צ".to_string()],
+            ]
+        );
+    }
 
-        let character = 'צ';
-        let character_final = 'ץ';
-        let value = gmctx.calculate_char_value(character);
-        let value_same = gmctx.calculate_char_value(character_final);
+    #[test]
+    fn test_compare_words() {
+        let gmctx = GematriaContext::default();
+        let comparison = gmctx.compare_words("אב", "יכ");
+
+        assert_eq!(comparison.len(), GematriaMethod::IMPLEMENTED.len());
+        assert_eq!(
+            comparison[&GematriaMethod::MisparHechrechi].values(),
+            (3, 30)
+        );
+        assert!(!comparison[&GematriaMethod::MisparHechrechi].is_equal());
+        assert!(comparison[&GematriaMethod::MisparKatan].is_equal());
+        assert!(!comparison[&GematriaMethod::MisparGadol].is_equal());
+    }
 
-        assert_eq!(value, value_same);
-        assert_eq!(value_same, 9);
+    #[test]
+    fn test_calculate_verses() {
+        let gmctx = GematriaContext::default();
+        let verses = gmctx.calculate_verses("בְּרֵאשִׁית בָּרָא׃\nאֱלֹהִים\n\n׃ אֵת הַשָּׁמַיִם׃");
+
+        let totals: Vec<(usize, u64)> = verses.iter().map(|(i, r)| (*i, r.value())).collect();
+        assert_eq!(totals, vec![(0, 1116), (1, 86), (2, 796)]);
+        let found = gmctx.search_verses_by_value(&86, "בראשית ברא׃ אלהים׃ אלהים");
+        let indices: Vec<usize> = found.iter().map(|(i, _)| *i).collect();
+        assert_eq!(indices, [1, 2]);
+        assert_eq!(
+            tokenize("הָאָרֶץ׃ אֶת־הָאוֹר׀ ב").collect::<Vec<_>>(),
+            ["הָאָרֶץ", "אֶת", "הָאוֹר", "ב"]
+        );
     }
 
     #[test]
@@ -766,6 +3604,31 @@ mod tests {
         assert_eq!(aleph_filled_result, 111);
     }
 
+    #[test]
+    fn test_alter_method_alphabet() {
+        // The default alphabet follows the method
+        let mut gmctx = GematriaContext::default();
+        gmctx.set_method(GematriaMethod::Isopsephy);
+        assert_eq!(gmctx.calculate_value("αβγ").value(), 6);
+        assert_eq!(gmctx.alphabet().name(), "Greek");
+        gmctx.set_method(GematriaMethod::EnglishOrdinal);
+        assert_eq!(gmctx.calculate_value("abc").value(), 6);
+        gmctx.set_method(GematriaMethod::MisparGadol);
+        assert_eq!(gmctx.calculate_value("שלום").value(), 936);
+        let gadol = GematriaBuilder::new()
+            .with_method(GematriaMethod::MisparGadol)
+            .init_gematria();
+        assert_eq!(gmctx.config(), gadol.config());
+
+        // A given character map is kept
+        let mut gmctx = GematriaBuilder::new()
+            .with_char_map(CharMap::from([('a', 1), ('b', 2)]))
+            .init_gematria();
+        gmctx.set_method(GematriaMethod::Isopsephy);
+        assert_eq!(gmctx.calculate_value("ab").value(), 3);
+        assert_eq!(gmctx.calculate_value("αβ").value(), 0);
+    }
+
     #[test]
     fn test_search_match() {
         let gmctx = GematriaBuilder::new()
@@ -778,10 +3641,33 @@ mod tests {
         let text = "נכנס יין יצא סוד";
         let matching_words = gmctx.search_matching_words(target_word, text);
 
-        assert!(matching_words.contains(&"סוד".to_string()));
+        assert!(matching_words.iter().any(|m| m.word() == "סוד"));
+    }
+
+    #[test]
+    fn test_search_multi() {
+        let gmctx = GematriaBuilder::new()
+            .with_method(GematriaMethod::MisparHechrechi)
+            .with_cache(true)
+            .init_gematria();
+
+        let text = "נכנס יין יצא סוד";
+        let matches = gmctx.search_matching_values_multi(&[70, 180, 1], text);
+
+        assert_eq!(matches.len(), 3);
+        assert_eq!(
+            matches[&70],
+            vec![
+                Match::new("יין".to_string(), 70, 1).with_byte_range(9..15),
+                Match::new("סוד".to_string(), 70, 3).with_byte_range(23..29)
+            ]
+        );
+        assert_eq!(matches[&180][0].word(), "נכנס");
+        assert!(matches[&1].is_empty());
     }
 
     #[test]
+    #[allow(deprecated)]
     fn test_group_words_by_gematria() {
         let gmctx = GematriaBuilder::new()
             .with_method(GematriaMethod::MisparHechrechi)
@@ -799,6 +3685,81 @@ mod tests {
         assert!(result.windows(2).all(|w| w[0].1.len() >= w[1].1.len()));
     }
 
+    #[test]
+    #[allow(deprecated)]
+    fn test_group_words_cancelled() {
+        let gmctx = GematriaContext::default();
+        let cancel = CancellationToken::new();
+        let mut updates = Vec::new();
+
+        let result = gmctx.group_words_by_gematria_with_progress(
+            "נכנס יין יצא סוד",
+            &mut |done, total| {
+                updates.push((done, total));
+                if done == 2 {
+                    cancel.cancel();
+                }
+            },
+            &cancel,
+        );
+
+        assert_eq!(result.unwrap_err().kind(), io::ErrorKind::Interrupted);
+        assert_eq!(updates, vec![(1, 4), (2, 4)]);
+
+        cancel.cancel();
+        let result = gmctx.group_words_with_progress("נכנס יין", &mut NoProgress, &cancel);
+        assert_eq!(result, Err(Error::Cancelled));
+    }
+
+    #[test]
+    fn test_numerals_and_abbreviations() {
+        let gmctx = GematriaBuilder::new()
+            .with_numerals(true)
+            .with_abbreviations(true)
+            .init_gematria();
+
+        let numeral = gmctx.calculate_value("י״ד");
+        assert_eq!(numeral.numeral(), Some(14));
+        assert!(!numeral.is_abbreviation());
+        assert_eq!(numeral.word(), "י״ד");
+
+        let abbreviation = gmctx.calculate_value("צה״ל");
+        assert_eq!(abbreviation.numeral(), None);
+        assert!(abbreviation.is_abbreviation());
+        assert_eq!(abbreviation.value(), 125);
+        assert_eq!(abbreviation.to_map()["abbreviation"], "true");
+
+        let plain = GematriaContext::default().calculate_value("צה״ל");
+        assert!(!plain.is_abbreviation());
+        assert_eq!(plain.word(), "צה״ל");
+    }
+
+    #[test]
+    fn test_tokenizer_configuration() {
+        let modern = GematriaBuilder::new()
+            .with_tokenizer(
+                Tokenizer::new()
+                    .with_maqaf_split(false)
+                    .with_punctuation(Punctuation::Split),
+            )
+            .init_gematria();
+        let text = "בית־אל, שלום.";
+
+        let words: Vec<String> = modern
+            .search_matching_filter(text, |_, _| true)
+            .map(|m| m.word().to_string())
+            .collect();
+        // The maqaf is removed along with the vowels of the joined word
+        assert_eq!(words, ["ביתאל", "שלום"]);
+        assert_eq!(
+            GematriaContext::default()
+                .search_matching_values(&31, text)
+                .len(),
+            1
+        );
+        assert!(modern.search_matching_values(&31, text).is_empty());
+    }
+
     #[test]
     fn test_trait_char() {
         let method = &GematriaMethod::MisparHechrechi;
@@ -0,0 +1,291 @@
+// Guards bounding the work of expensive searches (results, time and depth).
+
+use std::{
+    ops::Range,
+    time::{Duration, Instant},
+};
+
+use crate::{GematriaContext, GematriaValue, Match};
+
+/// `SearchLimits` bounds an expensive search, which then returns the results found so far
+/// as a [`Limited`] result flagged as truncated, instead of running unbounded.
+///
+/// All the limits are unset by default.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct SearchLimits {
+    // The maximum number of results.
+    max_results: Option<usize>,
+
+    // The maximum time spent searching.
+    max_time: Option<Duration>,
+
+    // The maximum depth of the search, e.g. the number of words in a phrase.
+    max_depth: Option<usize>,
+}
+
+impl SearchLimits {
+    /// Creates limits with no bound set.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the maximum number of results.
+    pub fn with_max_results(mut self, max_results: usize) -> Self {
+        self.max_results = Some(max_results);
+        self
+    }
+
+    /// Sets the maximum time spent searching, checked between steps of the search.
+    pub fn with_max_time(mut self, max_time: Duration) -> Self {
+        self.max_time = Some(max_time);
+        self
+    }
+
+    /// Sets the maximum depth of the search, e.g. the number of words in a phrase.
+    pub fn with_max_depth(mut self, max_depth: usize) -> Self {
+        self.max_depth = Some(max_depth);
+        self
+    }
+
+    /// Gets the maximum number of results.
+    pub fn max_results(&self) -> Option<usize> {
+        self.max_results
+    }
+
+    /// Gets the maximum time spent searching.
+    pub fn max_time(&self) -> Option<Duration> {
+        self.max_time
+    }
+
+    /// Gets the maximum depth of the search.
+    pub fn max_depth(&self) -> Option<usize> {
+        self.max_depth
+    }
+}
+
+/// `Limited` holds the results of a bounded search, and whether the search was cut short.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Limited<T> {
+    // The results found.
+    items: Vec<T>,
+
+    // Whether a limit was reached before the search completed.
+    truncated: bool,
+}
+
+impl<T> Limited<T> {
+    pub(crate) fn new(items: Vec<T>, truncated: bool) -> Self {
+        Self { items, truncated }
+    }
+
+    /// Gets the results found.
+    pub fn items(&self) -> &[T] {
+        &self.items
+    }
+
+    /// Consumes the object, returning the results found.
+    pub fn into_items(self) -> Vec<T> {
+        self.items
+    }
+
+    /// Whether a limit was reached before the search completed, so the results may be partial.
+    pub fn is_truncated(&self) -> bool {
+        self.truncated
+    }
+}
+
+// Tracks the limits while a search is running.
+pub(crate) struct LimitGuard {
+    limits: SearchLimits,
+    started: Instant,
+}
+
+impl LimitGuard {
+    pub(crate) fn start(limits: &SearchLimits) -> Self {
+        LimitGuard {
+            limits: *limits,
+            started: Instant::now(),
+        }
+    }
+
+    // Whether the search has to stop, given the number of results found so far.
+    pub(crate) fn exceeded(&self, results: usize) -> bool {
+        self.limits.max_results.is_some_and(|max| results >= max)
+            || self
+                .limits
+                .max_time
+                .is_some_and(|max| self.started.elapsed() >= max)
+    }
+}
+
+impl GematriaContext {
+    /// Searches for words matching any of the target values, in order of appearance, within the given limits.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// use gematria_rs::{GematriaContext, SearchLimits};
+    ///
+    /// let gmctx = GematriaContext::default();
+    /// let limits = SearchLimits::new().with_max_results(1);
+    /// let found = gmctx.search_matching_values_limited(&[70], "יין סוד", &limits);
+    ///
+    /// assert_eq!(found.items()[0].word(), "יין");
+    /// assert!(found.is_truncated());
+    /// ```
+    pub fn search_matching_values_limited(
+        &self,
+        target_values: &[GematriaValue],
+        text: &str,
+        limits: &SearchLimits,
+    ) -> Limited<Match> {
+        let guard = LimitGuard::start(limits);
+        let mut items = Vec::new();
+        for (index, (range, word)) in self.tokenizer.tokenize_with_ranges(text).enumerate() {
+            if guard.exceeded(items.len()) {
+                return Limited {
+                    items,
+                    truncated: true,
+                };
+            }
+
+            let result = self.calculate_value(word);
+            if target_values.contains(&result.value()) {
+                items.push(
+                    Match::new(result.word().to_string(), result.value(), index)
+                        .with_byte_range(range),
+                );
+            }
+        }
+
+        Limited {
+            items,
+            truncated: false,
+        }
+    }
+
+    /// Searches for phrases, runs of consecutive words, whose total value is the target value.
+    ///
+    /// Each match holds the words of the phrase joined by a space, the index of its first word
+    /// and the byte range from its first word to its last one. Words without a value extend
+    /// the phrases they follow into further matches.
+    /// The depth limit bounds the number of words in a phrase.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// use gematria_rs::{GematriaContext, SearchLimits};
+    ///
+    /// let gmctx = GematriaContext::default();
+    /// let found = gmctx.find_phrases(68, "א ב גג ס", &SearchLimits::new());
+    ///
+    /// let phrases: Vec<&str> = found.items().iter().map(|m| m.word()).collect();
+    /// assert_eq!(phrases, vec!["ב גג ס"]);
+    /// assert_eq!(found.items()[0].byte_range(), 3..13);
+    /// ```
+    pub fn find_phrases(
+        &self,
+        target_value: GematriaValue,
+        text: &str,
+        limits: &SearchLimits,
+    ) -> Limited<Match> {
+        self.find_phrases_of(target_value, text, 1, limits)
+    }
+
+    /// Searches for the phrases of at least `min_words` words whose total value is the target value,
+    /// see [`GematriaContext::find_phrases`].
+    pub(crate) fn find_phrases_of(
+        &self,
+        target_value: GematriaValue,
+        text: &str,
+        min_words: usize,
+        limits: &SearchLimits,
+    ) -> Limited<Match> {
+        let guard = LimitGuard::start(limits);
+        let words: Vec<(String, GematriaValue, Range<usize>)> = self
+            .tokenizer
+            .tokenize_with_ranges(text)
+            .map(|(range, word)| {
+                let result = self.calculate_value(word);
+                (result.word().to_string(), result.value(), range)
+            })
+            .collect();
+        let max_depth = limits.max_depth.unwrap_or(words.len());
+
+        let mut items = Vec::new();
+        let mut truncated = false;
+        for start in 0..words.len() {
+            if guard.exceeded(items.len()) {
+                return Limited {
+                    items,
+                    truncated: true,
+                };
+            }
+
+            let mut total: u64 = 0;
+            for (depth, (_, value, _)) in words[start..].iter().enumerate() {
+                if depth == max_depth {
+                    // The phrase could still have reached the target with more words
+                    truncated = true;
+                    break;
+                }
+
+                // Values are never negative, so longer phrases only exceed the target further
+                total = total.saturating_add(*value);
+                if total > target_value {
+                    break;
+                }
+                if total == target_value && depth + 1 >= min_words {
+                    let phrase: Vec<&str> = words[start..=start + depth]
+                        .iter()
+                        .map(|(word, _, _)| word.as_str())
+                        .collect();
+                    // The phrase spans from its first word to its last one
+                    let range = words[start].2.start..words[start + depth].2.end;
+                    items.push(Match::new(phrase.join(" "), total, start).with_byte_range(range));
+                }
+            }
+        }
+
+        Limited { items, truncated }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unbounded_search_is_complete() {
+        let gmctx = GematriaContext::default();
+        let found = gmctx.search_matching_values_limited(
+            &[70, 101],
+            "נכנס יין יצא סוד",
+            &SearchLimits::new(),
+        );
+
+        let words: Vec<&str> = found.items().iter().map(|m| m.word()).collect();
+        assert_eq!(words, vec!["יין", "יצא", "סוד"]);
+        assert!(!found.is_truncated());
+    }
+
+    #[test]
+    fn test_phrase_depth_and_time_limits() {
+        let gmctx = GematriaContext::default();
+        let text = "א א א ב";
+
+        let found = gmctx.find_phrases(3, text, &SearchLimits::new());
+        assert_eq!(found.items().len(), 2);
+        assert!(!found.is_truncated());
+
+        let found = gmctx.find_phrases(3, text, &SearchLimits::new().with_max_depth(2));
+        assert_eq!(found.items()[0].word(), "א ב");
+        assert_eq!(found.items()[0].index(), 2);
+        assert!(found.is_truncated());
+
+        let limits = SearchLimits::new().with_max_time(Duration::ZERO);
+        let found = gmctx.find_phrases(3, text, &limits);
+        assert!(found.items().is_empty());
+        assert!(found.is_truncated());
+    }
+}
@@ -1,7 +1,25 @@
 extern crate gematria_rs;
-use clap::{Parser, Subcommand, ValueEnum};
-use gematria_rs::{GematriaBuilder, GematriaMethod};
-use std::io::{self, Read};
+use clap::{
+    builder::{PossibleValue, StringValueParser, TypedValueParser},
+    error::ErrorKind,
+    Arg, Command, Parser, Subcommand, ValueEnum,
+};
+#[cfg(feature = "config-files")]
+use gematria_rs::ConfigFile;
+#[cfg(feature = "calendar")]
+use gematria_rs::HebrewDate;
+#[cfg(feature = "mmap")]
+use gematria_rs::MappedText;
+use gematria_rs::{
+    verify_reference_values_with, Cell, GematriaBuilder, GematriaMethod, OutputFormat,
+    Transliteration, REFERENCE_VALUES,
+};
+use std::{
+    ffi::OsStr,
+    fs::File,
+    io::{self, BufReader, Read},
+    path::{Path, PathBuf},
+};
 
 /// Simple program to calculate a gematric value from hebrew words or phrases
 #[derive(Parser, Debug)]
@@ -10,21 +28,55 @@ struct Cli {
     #[clap(subcommand)]
     command: Commands,
 
-    /// The gematria calculation method.
-    #[clap(short, long, value_enum)]
-    method: Option<GematriaMethods>,
+    /// A gematria system file (TOML, or JSON for a .json extension) configuring the calculations,
+    /// overridden by the other options.
+    #[cfg(feature = "config-files")]
+    #[clap(long, value_name = "PATH", global = true)]
+    config: Option<PathBuf>,
+
+    /// The gematria calculation method (e.g. mispar-gadol or "Mispar Gadol").
+    #[clap(short, long, value_parser = MethodParser)]
+    method: Option<GematriaMethod>,
 
     /// Enable caching for repeated calculations.
     #[clap(short = 'c', long)]
     enable_cache: bool,
 
+    /// Maximum number of cached values, enabling the cache (unbounded by default).
+    #[clap(long, value_name = "N")]
+    cache_capacity: Option<usize>,
+
+    /// A file persisting the cache between runs, loaded on startup and saved on exit (enables the cache).
+    #[clap(long, value_name = "PATH")]
+    cache_file: Option<PathBuf>,
+
     /// Preserve vowels in the words.
     #[clap(short = 'p', long)]
     preserve_vowels: bool,
 
+    /// The letter shift used by the avgad method (negative values shift backwards).
+    #[clap(long, allow_negative_numbers = true)]
+    shift: Option<i32>,
+
+    /// Transliterate Latin input (e.g. shalom) into Hebrew letters before calculation.
+    #[clap(short = 't', long)]
+    transliterate: bool,
+
+    /// An extra transliteration rule (e.g. th=ת or bereshit=בראשית), implies --transliterate.
+    #[clap(long = "translit-rule", value_name = "LATIN=HEBREW", value_parser = parse_rule)]
+    translit_rules: Vec<(String, String)>,
+
+    /// Read the text of a command from a file rather than from stdin (memory-mapped with the `mmap` feature).
+    #[clap(long, value_name = "PATH")]
+    file: Option<PathBuf>,
+
     /// Enable verbose outputs.
     #[clap(short = 'v', long)]
     verbose: bool,
+
+    /// The output format.
+    #[clap(short = 'f', long, value_enum, default_value = "plain")]
+    format: OutputFormats,
 }
 
 #[derive(Subcommand, Debug)]
@@ -45,91 +97,381 @@ enum Commands {
     GroupWords {
         /// The text to search within.
         text: Option<String>,
+        /// Cluster the words of every group which are anagrams of each other (shown joined by '/').
+        #[clap(short, long)]
+        anagrams: bool,
     },
+    /// Look up the words of a dictionary with a specific gematria value.
+    Lookup {
+        /// The gematria value to look up.
+        value: u64,
+        /// A dictionary file (words separated by whitespace or new lines), defaulted to the bundled word list.
+        #[clap(short, long)]
+        dictionary: Option<PathBuf>,
+    },
+    /// Show the occurrences of a word, or of the words with a gematria value, in their context.
+    Concordance {
+        /// The word to look for, or a gematria value.
+        query: String,
+        /// The text to search within.
+        text: Option<String>,
+        /// The number of words shown on each side of an occurrence.
+        #[clap(short, long, default_value_t = 3)]
+        window: usize,
+        /// Look for the words with the same gematria value as the queried word.
+        #[clap(short = 'a', long)]
+        by_value: bool,
+    },
+    /// Extract and value the Hebrew words of a multilingual text, reporting the skipped foreign content.
+    Extract {
+        /// The text to extract from.
+        text: Option<String>,
+        /// The number of samples shown for every kind of skipped content.
+        #[clap(short, long, default_value_t = 5)]
+        samples: usize,
+    },
+    /// Calculate the gematria value of every word read from the standard input, line by line.
+    Stream,
+    /// Recomputes the built-in reference values of every method, reporting any mismatch.
+    Verify,
+    /// Show today's Hebrew date and the gematria values of its day, month and year.
+    #[cfg(feature = "calendar")]
+    Today,
 }
 
 #[derive(ValueEnum, Clone, Debug)]
-enum GematriaMethods {
-    MisparHechrechi,
-    MisparGadol,
-    MisparKatan,
-    OtiyotBeMilui,
+enum OutputFormats {
+    Plain,
+    Table,
+    Json,
+    Csv,
+    Markdown,
+    Html,
+}
+
+/// The text of a command, given as an argument, read from stdin or from a file.
+enum Input {
+    Text(String),
+    #[cfg(feature = "mmap")]
+    Mapped(MappedText),
+}
+
+impl Input {
+    /// Gets the text of a command, exiting on a read error.
+    fn read(text: Option<String>, file: Option<&Path>) -> Self {
+        let input = match (text, file) {
+            (Some(text), _) => Ok(Input::Text(text)),
+            #[cfg(feature = "mmap")]
+            (None, Some(path)) => MappedText::open(path).map(Input::Mapped),
+            #[cfg(not(feature = "mmap"))]
+            (None, Some(path)) => std::fs::read_to_string(path).map(Input::Text),
+            (None, None) => {
+                let mut buffer = String::new();
+                io::stdin()
+                    .read_to_string(&mut buffer)
+                    .map(|_| Input::Text(buffer))
+            }
+        };
+        input.unwrap_or_else(|e| {
+            eprintln!("Error reading input: {}", e);
+            std::process::exit(1);
+        })
+    }
+
+    /// Gets the text.
+    fn as_str(&self) -> &str {
+        match self {
+            Input::Text(text) => text,
+            #[cfg(feature = "mmap")]
+            Input::Mapped(text) => text.as_str(),
+        }
+    }
 }
 
 fn main() {
     let cli = Cli::parse();
-    let mut builder = GematriaBuilder::new()
-        .with_cache(cli.enable_cache)
-        .with_vowels(cli.preserve_vowels);
+    #[cfg(feature = "config-files")]
+    let mut builder = match cli.config {
+        Some(ref path) => ConfigFile::load(path)
+            .map(ConfigFile::into_builder)
+            .unwrap_or_else(|e| {
+                eprintln!("Error reading the config {}: {}", path.display(), e);
+                std::process::exit(1);
+            }),
+        None => GematriaBuilder::new(),
+    };
+    #[cfg(not(feature = "config-files"))]
+    let mut builder = GematriaBuilder::new();
+
+    // The options override the loaded system, when given
+    if cli.enable_cache || cli.cache_file.is_some() {
+        builder = builder.with_cache(true);
+    }
+
+    if cli.preserve_vowels {
+        builder = builder.with_vowels(true);
+    }
 
     if let Some(m) = cli.method {
-        builder = builder.with_method(GematriaMethod::from(m));
+        builder = builder.with_method(m);
+    }
+
+    if let Some(capacity) = cli.cache_capacity {
+        builder = builder.with_cache_capacity(capacity);
+    }
+
+    if let Some(shift) = cli.shift {
+        builder = builder.with_shift(shift);
+    }
+
+    if cli.transliterate || !cli.translit_rules.is_empty() {
+        let transliteration = cli
+            .translit_rules
+            .iter()
+            .fold(Transliteration::new(), |scheme, (latin, hebrew)| {
+                scheme.with_rule(latin, hebrew)
+            });
+        builder = builder.with_transliteration(transliteration);
     }
 
-    let gematria_context = builder.init_gematria();
+    let gematria_context = builder.clone().init_gematria();
+    if let Some(ref path) = cli.cache_file {
+        // A missing cache is created on exit, a stale one is replaced
+        match gematria_context.load_cache(path) {
+            Err(e) if e.kind() == io::ErrorKind::InvalidData => {
+                eprintln!("Ignoring the cache {}: {}", path.display(), e)
+            }
+            Err(e) if e.kind() != io::ErrorKind::NotFound => {
+                eprintln!("Error reading the cache {}: {}", path.display(), e)
+            }
+            _ => {}
+        }
+    }
+    let renderer = OutputFormat::from(cli.format).renderer(cli.verbose);
     match cli.command {
         Commands::Calculate { text } => {
             let result = gematria_context.calculate_value(&text);
-
-            if cli.verbose {
-                println!("Gematria value for '{}': {}", text, result.value());
-            } else {
-                println!("{}", result.value());
-            }
+            print!("{}", renderer.render_value(&result));
         }
         Commands::SearchMatch { word, text } => {
             // Logic for searching within the text to find words matching the gematria value of 'word'
-            let text_to_search = match text {
-                Some(t) => t,
-                None => {
-                    let mut buffer = String::new();
-                    io::stdin()
-                        .read_to_string(&mut buffer)
-                        .expect("Failed to read from stdin");
-                    buffer
-                }
-            };
+            let input = Input::read(text, cli.file.as_deref());
+            let text_to_search = input.as_str();
 
-            let matching_words = gematria_context.search_matching_words(&word, &text_to_search);
+            let target_value = gematria_context.calculate_value(&word).value();
+            let mut matches =
+                gematria_context.search_matching_values_multi(&[target_value], text_to_search);
+            let matching_words = matches.remove(&target_value).unwrap_or_default();
 
-            for matching_word in matching_words {
-                println!("{}", matching_word);
+            print!("{}", renderer.render_matches(&matching_words));
+        }
+        Commands::GroupWords { text, anagrams } => {
+            let input = Input::read(text, cli.file.as_deref());
+            let text_to_search = input.as_str();
+            let groups = if anagrams {
+                gematria_context
+                    .group_words_with_anagrams(text_to_search)
+                    .map(|groups| {
+                        groups
+                            .into_iter()
+                            .map(|(value, clusters)| {
+                                let clusters = clusters.iter().map(|c| c.join("/")).collect();
+                                (value, clusters)
+                            })
+                            .collect()
+                    })
+            } else {
+                gematria_context.group_words(text_to_search)
+            };
+            match groups {
+                Ok(groups) => print!("{}", renderer.render_groups(&groups)),
+                Err(e) => eprintln!("Error grouping words: {}", e),
+            }
+        }
+        Commands::Lookup { value, dictionary } => {
+            let lexicon = match dictionary {
+                Some(path) => File::open(path)
+                    .and_then(|file| gematria_context.lexicon_from_reader(BufReader::new(file))),
+                #[cfg(feature = "wordlist")]
+                None => Ok(gematria_context.bundled_lexicon()),
+                #[cfg(not(feature = "wordlist"))]
+                None => Err(io::Error::new(
+                    io::ErrorKind::NotFound,
+                    "no dictionary given, and the bundled word list requires the `wordlist` feature",
+                )),
+            };
+            match lexicon {
+                Ok(lexicon) => {
+                    let words = lexicon.words_with_value(value).to_vec();
+                    print!("{}", renderer.render_groups(&[(value, words)]));
+                }
+                Err(e) => {
+                    eprintln!("Error reading dictionary: {}", e);
+                    std::process::exit(1);
+                }
             }
         }
-        Commands::GroupWords { text } => {
-            let text_to_search = match text {
-                Some(t) => t,
-                None => {
-                    let mut buffer = String::new();
-                    io::stdin()
-                        .read_to_string(&mut buffer)
-                        .expect("Failed to read from stdin");
-                    buffer
+        Commands::Concordance {
+            query,
+            text,
+            window,
+            by_value,
+        } => {
+            let input = Input::read(text, cli.file.as_deref());
+            let text_to_search = input.as_str();
+
+            let lines = match query.parse::<u64>() {
+                Ok(value) => gematria_context.concordance_of_value(&value, text_to_search, window),
+                Err(_) if by_value => {
+                    let value = gematria_context.calculate_value(&query).value();
+                    gematria_context.concordance_of_value(&value, text_to_search, window)
                 }
+                Err(_) => gematria_context.concordance_of_word(&query, text_to_search, window),
             };
-            match gematria_context.group_words_by_gematria(&text_to_search) {
-                Ok(groups) => {
-                    for (value, words) in groups {
-                        if cli.verbose {
-                            println!("Gematria value {:4}: {}", value, words.join(", "));
-                        } else {
-                            println!("{:4} -> {}", value, words.join(", "));
-                        }
+            print!("{}", renderer.render_concordance(&lines));
+        }
+        Commands::Extract { text, samples } => {
+            let input = Input::read(text, cli.file.as_deref());
+            let text_to_search = input.as_str();
+
+            let extraction = gematria_context.extract_hebrew(text_to_search, samples);
+            print!("{}", renderer.render_matches(extraction.words()));
+            // The report goes to the standard error, so the words can be piped
+            for skipped in extraction.skipped() {
+                eprintln!(
+                    "Skipped {}: {} characters, {} fragments ({})",
+                    skipped.kind(),
+                    skipped.characters(),
+                    skipped.fragments(),
+                    skipped.samples().join(", ")
+                );
+            }
+        }
+        Commands::Stream => {
+            let stdin = io::stdin();
+            for result in gematria_context.process_reader(stdin.lock()) {
+                match result {
+                    Ok(result) => print!("{}", renderer.render_value(&result)),
+                    Err(e) => {
+                        eprintln!("Error reading input: {}", e);
+                        std::process::exit(1);
                     }
                 }
-                Err(e) => eprintln!("Error reading file: {}", e),
             }
         }
+        #[cfg(feature = "calendar")]
+        Commands::Today => {
+            let date = HebrewDate::today();
+            let values = gematria_context.calculate_date(&date);
+            println!("{} ({} {} {})", date, date.day(), date.month(), date.year());
+
+            let rows: Vec<Vec<Cell>> = [
+                ("day", values.day()),
+                ("month", values.month()),
+                ("year", values.year()),
+            ]
+            .iter()
+            .map(|(component, result)| {
+                vec![
+                    Cell::Text(component.to_string()),
+                    Cell::Text(result.word().to_string()),
+                    Cell::Number(result.value()),
+                ]
+            })
+            .chain(std::iter::once(vec![
+                Cell::Text("total".to_string()),
+                Cell::Text(date.to_string()),
+                Cell::Number(values.total()),
+            ]))
+            .collect();
+            print!(
+                "{}",
+                renderer.render_table(&["component", "text", "value"], &rows)
+            );
+        }
+        Commands::Verify => {
+            // The references are recomputed with the loaded system and the options
+            let mismatches = verify_reference_values_with(&builder);
+            if mismatches.is_empty() {
+                println!("All {} reference values match", REFERENCE_VALUES.len());
+                return;
+            }
+
+            let rows: Vec<Vec<Cell>> = mismatches
+                .iter()
+                .map(|m| {
+                    vec![
+                        Cell::Text(m.reference().method().to_string()),
+                        Cell::Text(m.reference().word().to_string()),
+                        Cell::Number(m.reference().value()),
+                        Cell::Number(m.actual()),
+                    ]
+                })
+                .collect();
+            print!(
+                "{}",
+                renderer.render_table(&["method", "word", "expected", "actual"], &rows)
+            );
+            eprintln!(
+                "{} of {} reference values mismatch",
+                mismatches.len(),
+                REFERENCE_VALUES.len()
+            );
+            std::process::exit(1);
+        }
+    }
+
+    if let Some(ref path) = cli.cache_file {
+        if let Err(e) = gematria_context.save_cache(path) {
+            eprintln!("Error saving the cache {}: {}", path.display(), e);
+        }
+    }
+}
+
+// Parses a transliteration rule given as LATIN=HEBREW.
+fn parse_rule(rule: &str) -> Result<(String, String), String> {
+    match rule.split_once('=') {
+        Some((latin, hebrew)) if !latin.is_empty() => Ok((latin.to_string(), hebrew.to_string())),
+        _ => Err(format!("invalid rule `{}`, expected LATIN=HEBREW", rule)),
+    }
+}
+
+// Parses a method by any spelling of its name (see `GematriaMethod::from_str`),
+// listing the implemented methods as the possible values of the argument.
+#[derive(Clone)]
+struct MethodParser;
+
+impl TypedValueParser for MethodParser {
+    type Value = GematriaMethod;
+
+    fn parse_ref(
+        &self,
+        cmd: &Command,
+        arg: Option<&Arg>,
+        value: &OsStr,
+    ) -> Result<Self::Value, clap::Error> {
+        let value = StringValueParser::new().parse_ref(cmd, arg, value)?;
+        value.parse().map_err(|e| {
+            clap::Error::raw(ErrorKind::InvalidValue, format!("{}\n", e)).with_cmd(cmd)
+        })
+    }
+
+    fn possible_values(&self) -> Option<Box<dyn Iterator<Item = PossibleValue> + '_>> {
+        let values = GematriaMethod::all()
+            .map(|method| PossibleValue::new(method.name().to_lowercase().replace(' ', "-")));
+        Some(Box::new(values))
     }
 }
 
-impl From<GematriaMethods> for GematriaMethod {
-    fn from(method: GematriaMethods) -> Self {
-        match method {
-            GematriaMethods::MisparHechrechi => GematriaMethod::MisparHechrechi,
-            GematriaMethods::MisparGadol => GematriaMethod::MisparGadol,
-            GematriaMethods::MisparKatan => GematriaMethod::MisparKatan,
-            GematriaMethods::OtiyotBeMilui => GematriaMethod::OtiyotBeMilui,
+impl From<OutputFormats> for OutputFormat {
+    fn from(format: OutputFormats) -> Self {
+        match format {
+            OutputFormats::Plain => OutputFormat::Plain,
+            OutputFormats::Table => OutputFormat::Table,
+            OutputFormats::Json => OutputFormat::Json,
+            OutputFormats::Csv => OutputFormat::Csv,
+            OutputFormats::Markdown => OutputFormat::Markdown,
+            OutputFormats::Html => OutputFormat::Html,
         }
     }
 }
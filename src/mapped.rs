@@ -0,0 +1,100 @@
+// Memory-mapped texts, valuing large corpora without reading them into memory.
+
+use std::{fs::File, io, path::Path};
+
+use memmap2::Mmap;
+
+/// `MappedText` maps a UTF-8 text file into memory, so a corpus larger than memory is processed
+/// by every text API of [`crate::GematriaContext`] without reading it into a `String`.
+///
+/// The pages of the file are loaded by the operating system as the words are tokenized.
+/// The file must not be modified while it is mapped.
+///
+/// # Examples
+///
+/// ```no_run
+/// use gematria_rs::{GematriaContext, MappedText};
+///
+/// let text = MappedText::open("data/hebrew-all.txt")?;
+/// let gmctx = GematriaContext::default();
+/// let groups = gmctx.group_words(text.as_str())?;
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+#[derive(Debug)]
+pub struct MappedText {
+    // The mapped file, none for an empty file (which cannot be mapped).
+    mmap: Option<Mmap>,
+}
+
+impl MappedText {
+    /// Maps a text file, failing with [`io::ErrorKind::InvalidData`] if it is not valid UTF-8.
+    pub fn open<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        let file = File::open(path)?;
+        let mmap = match file.metadata()?.len() {
+            0 => None,
+            // The mapping is read only, and the file is documented as not to be modified while mapped
+            _ => Some(unsafe { Mmap::map(&file)? }),
+        };
+        let text = MappedText { mmap };
+        std::str::from_utf8(text.bytes())
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        Ok(text)
+    }
+
+    /// Gets the mapped text.
+    pub fn as_str(&self) -> &str {
+        // The bytes were validated as UTF-8 when mapped, and the mapping is read only
+        unsafe { std::str::from_utf8_unchecked(self.bytes()) }
+    }
+
+    /// Gets the length of the text, in bytes.
+    pub fn len(&self) -> usize {
+        self.bytes().len()
+    }
+
+    /// Checks whether the text is empty.
+    pub fn is_empty(&self) -> bool {
+        self.bytes().is_empty()
+    }
+
+    // Util function to get the mapped bytes.
+    fn bytes(&self) -> &[u8] {
+        self.mmap.as_deref().unwrap_or_default()
+    }
+}
+
+impl AsRef<str> for MappedText {
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::GematriaContext;
+
+    #[test]
+    fn test_mapped_text() {
+        let dir = std::env::temp_dir();
+        let path = dir.join(format!("gematria-mapped-{}.txt", std::process::id()));
+
+        std::fs::write(&path, "נכנס יין\nיצא סוד\n").unwrap();
+        let text = MappedText::open(&path).unwrap();
+        let gmctx = GematriaContext::default();
+        let groups = gmctx.group_words(text.as_str()).unwrap();
+        assert_eq!(
+            groups,
+            vec![(70, vec!["יין".to_string(), "סוד".to_string()])]
+        );
+        drop(text);
+
+        std::fs::write(&path, "").unwrap();
+        assert!(MappedText::open(&path).unwrap().is_empty());
+
+        std::fs::write(&path, [0xD7, 0x20]).unwrap();
+        let error = MappedText::open(&path).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+        std::fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,203 @@
+// Classes of Hebrew marks (cantillation, niqqud and punctuation) and their stripping from words.
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{GERESH, GERSHAYIM, MAQAF, PASEQ, SOF_PASUK};
+
+/// Enumerates the classes of Hebrew marks written along with the letters.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum HebrewMark {
+    /// Te'amim, the cantillation marks (U+0591 to U+05AF).
+    Cantillation,
+    /// Niqqud, the vowel points and other points such as the dagesh, shin dot and rafe.
+    Niqqud,
+    /// Hebrew punctuation: the maqaf, paseq, sof pasuk and nun hafukha.
+    Punctuation,
+    /// The geresh and gershayim, marking numerals and abbreviations.
+    Geresh,
+}
+
+impl HebrewMark {
+    /// Gets the class of a Hebrew mark, `None` for letters and other characters.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gematria_rs::HebrewMark;
+    ///
+    /// assert_eq!(HebrewMark::of('\u{0591}'), Some(HebrewMark::Cantillation));
+    /// assert_eq!(HebrewMark::of('\u{05B8}'), Some(HebrewMark::Niqqud));
+    /// assert_eq!(HebrewMark::of('־'), Some(HebrewMark::Punctuation));
+    /// assert_eq!(HebrewMark::of('״'), Some(HebrewMark::Geresh));
+    /// assert_eq!(HebrewMark::of('א'), None);
+    /// ```
+    pub fn of(c: char) -> Option<Self> {
+        match c {
+            '\u{0591}'..='\u{05AF}' => Some(HebrewMark::Cantillation),
+            MAQAF | PASEQ | SOF_PASUK | '\u{05C6}' => Some(HebrewMark::Punctuation),
+            '\u{05B0}'..='\u{05C7}' => Some(HebrewMark::Niqqud),
+            GERESH | GERSHAYIM => Some(HebrewMark::Geresh),
+            _ => None,
+        }
+    }
+}
+
+/// `MarkStripping` selects the classes of Hebrew marks removed from the words of results,
+/// see [`crate::GematriaBuilder::with_mark_stripping`].
+///
+/// By default cantillation, niqqud and punctuation are removed, while the geresh and gershayim
+/// are kept. Marks never contribute to the values of the letter methods, whichever are stripped.
+///
+/// # Examples
+///
+/// ```
+/// use gematria_rs::{GematriaBuilder, MarkStripping};
+///
+/// // Keeps the vowels of a verse, without its cantillation
+/// let gmctx = GematriaBuilder::new()
+///     .with_mark_stripping(MarkStripping::new().with_niqqud(false))
+///     .init_gematria();
+///
+/// let result = gmctx.calculate_value("בְּרֵאשִׁ֖ית");
+/// assert_eq!(result.word(), "בְּרֵאשִׁית");
+/// assert_eq!(result.value(), 913);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct MarkStripping {
+    // Whether cantillation marks are removed.
+    cantillation: bool,
+
+    // Whether niqqud is removed.
+    niqqud: bool,
+
+    // Whether Hebrew punctuation is removed.
+    punctuation: bool,
+
+    // Whether the geresh and gershayim are removed.
+    geresh: bool,
+}
+
+impl Default for MarkStripping {
+    fn default() -> Self {
+        MarkStripping {
+            cantillation: true,
+            niqqud: true,
+            punctuation: true,
+            geresh: false,
+        }
+    }
+}
+
+impl MarkStripping {
+    /// Creates the default stripping, removing cantillation, niqqud and punctuation.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a stripping keeping all the marks.
+    pub fn none() -> Self {
+        MarkStripping {
+            cantillation: false,
+            niqqud: false,
+            punctuation: false,
+            geresh: false,
+        }
+    }
+
+    /// Sets whether cantillation marks (te'amim) are removed.
+    pub fn with_cantillation(mut self, strip: bool) -> Self {
+        self.cantillation = strip;
+        self
+    }
+
+    /// Sets whether niqqud is removed.
+    pub fn with_niqqud(mut self, strip: bool) -> Self {
+        self.niqqud = strip;
+        self
+    }
+
+    /// Sets whether Hebrew punctuation (maqaf, paseq, sof pasuk and nun hafukha) is removed.
+    pub fn with_punctuation(mut self, strip: bool) -> Self {
+        self.punctuation = strip;
+        self
+    }
+
+    /// Sets whether the geresh and gershayim are removed.
+    pub fn with_geresh(mut self, strip: bool) -> Self {
+        self.geresh = strip;
+        self
+    }
+
+    /// Gets whether cantillation marks are removed.
+    pub fn cantillation(&self) -> bool {
+        self.cantillation
+    }
+
+    /// Gets whether niqqud is removed.
+    pub fn niqqud(&self) -> bool {
+        self.niqqud
+    }
+
+    /// Gets whether Hebrew punctuation is removed.
+    pub fn punctuation(&self) -> bool {
+        self.punctuation
+    }
+
+    /// Gets whether the geresh and gershayim are removed.
+    pub fn geresh(&self) -> bool {
+        self.geresh
+    }
+
+    /// Checks whether a character is removed.
+    pub fn strips(&self, c: char) -> bool {
+        match HebrewMark::of(c) {
+            Some(HebrewMark::Cantillation) => self.cantillation,
+            Some(HebrewMark::Niqqud) => self.niqqud,
+            Some(HebrewMark::Punctuation) => self.punctuation,
+            Some(HebrewMark::Geresh) => self.geresh,
+            None => false,
+        }
+    }
+
+    /// Removes the selected marks from a text.
+    pub fn strip(&self, text: &str) -> String {
+        text.chars().filter(|&c| !self.strips(c)).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_matches_vowel_removal() {
+        let stripping = MarkStripping::default();
+        for c in '\u{0590}'..='\u{05FF}' {
+            assert_eq!(
+                stripping.strips(c),
+                crate::is_hebrew_vowel(c),
+                "{:X}",
+                c as u32
+            );
+        }
+    }
+
+    #[test]
+    fn test_strip_classes() {
+        let verse = "בְּרֵאשִׁ֖ית בָּרָ֣א׃ אֶת־ה׳";
+        assert_eq!(
+            MarkStripping::none().with_cantillation(true).strip(verse),
+            "בְּרֵאשִׁית בָּרָא׃ אֶת־ה׳"
+        );
+        assert_eq!(
+            MarkStripping::new().with_punctuation(false).strip(verse),
+            "בראשית ברא׃ את־ה׳"
+        );
+        assert_eq!(
+            MarkStripping::new().with_geresh(true).strip(verse),
+            "בראשית ברא אתה"
+        );
+    }
+}
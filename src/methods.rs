@@ -1,10 +1,15 @@
 // Defines the core gematria calculation methods and traits for the Gematria-rs library.
 
-use crate::{CharMap, FullCharMap};
+use std::{fmt, str::FromStr};
+
+use crate::{
+    static_dispatch::standard_index, AchasBeta, Achbi, Avgad, AyakBachar, CharMap, FullCharMap,
+};
 
 /// Enumerates various gematria calculation methods.
 /// Includes traditional and specialized methods like Mispar Hechrechi and Otiyot BeMilui.
-#[derive(Debug, Clone, PartialEq, Eq, Hash, Copy)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum GematriaMethod {
     MisparHechrechi,
     MisparGadol,
@@ -14,15 +19,303 @@ pub enum GematriaMethod {
     MisparMeugal,
     MisparMusafi,
     OtiyotBeMilui,
+    Achbi,
+    Avgad,
+    AyakBachar,
+    MisparKidmi,
+    MisparPerati,
+    MisparHaMerubahHaKlali,
+    MisparKatanMispari,
+    MisparNeelam,
+    MiluiHaMilui,
+    AchasBeta,
+    MisparHaAkhor,
+    Nikud,
+    /// Greek isopsephy, see [`crate::Isopsephy`].
+    Isopsephy,
+    /// English ordinal values, see [`crate::EnglishOrdinal`].
+    EnglishOrdinal,
+    /// English Qabalah (the ALW cipher), see [`crate::EnglishQabalah`].
+    EnglishQabalah,
+    /// The Agrippa key for English, see [`crate::Agrippa`].
+    Agrippa,
+    /// A custom strategy, injected with [`crate::GematriaBuilder::with_strategy`].
+    Custom,
+}
+
+impl GematriaMethod {
+    /// Gets the value of a letter index (1 based, final forms are 23-27) under the method,
+    /// `None` for the methods valuing letters with runtime data (such as milui spellings) or positions,
+    /// and for the methods of other alphabets.
+    ///
+    /// Letter values, and with [`GematriaMethod::word_value`] word values, may so be computed at compile time.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gematria_rs::GematriaMethod;
+    ///
+    /// const TAV: Option<u32> = GematriaMethod::MisparPerati.letter_value(22);
+    /// assert_eq!(TAV, Some(160_000));
+    /// assert_eq!(GematriaMethod::MisparNeelam.letter_value(22), None);
+    /// ```
+    pub const fn letter_value(&self, letter_index: u32) -> Option<u32> {
+        if letter_index < 1 || letter_index > 27 {
+            // Other characters have no value, under the methods valuing letters at all
+            return match self.letter_value(1) {
+                Some(_) => Some(0),
+                None => None,
+            };
+        }
+        let value = match self {
+            GematriaMethod::MisparHechrechi
+            | GematriaMethod::MisparHaMerubahHaKlali
+            | GematriaMethod::MisparHaAkhor => MisparHechrechi.letter_value(letter_index),
+            GematriaMethod::MisparGadol => MisparGadol.letter_value(letter_index),
+            GematriaMethod::MisparKatan | GematriaMethod::MisparKatanMispari => {
+                MisparKatan.letter_value(letter_index)
+            }
+            GematriaMethod::MisparKidmi => MisparKidmi.letter_value(letter_index),
+            GematriaMethod::MisparPerati => MisparPerati.letter_value(letter_index),
+            GematriaMethod::Achbi => Achbi.letter_value(letter_index),
+            GematriaMethod::Avgad => Avgad::new(Avgad::DEFAULT_SHIFT).letter_value(letter_index),
+            GematriaMethod::AyakBachar => AyakBachar.letter_value(letter_index),
+            GematriaMethod::AchasBeta => AchasBeta.letter_value(letter_index),
+            _ => return None,
+        };
+        Some(value)
+    }
+
+    /// Calculates the value of a Hebrew word under the method, in constant expressions too,
+    /// as a [`crate::GematriaContext`] with the default settings would.
+    ///
+    /// Characters other than the Hebrew letters are ignored, and the word is valued as a single word
+    /// (positions are not reset on spaces). `None` for the methods without a [`GematriaMethod::letter_value`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gematria_rs::GematriaMethod;
+    ///
+    /// const SHALOM: Option<u64> = GematriaMethod::MisparHechrechi.word_value("שלום");
+    /// assert_eq!(SHALOM, Some(376));
+    /// assert_eq!(GematriaMethod::MisparHaAkhor.word_value("שלום"), Some(538));
+    /// ```
+    pub const fn word_value(&self, word: &str) -> Option<u64> {
+        let bytes = word.as_bytes();
+        let mut total: u64 = 0;
+        let mut position: u64 = 0;
+        let mut i = 0;
+        while i < bytes.len() {
+            // The Hebrew letters (U+05D0 to U+05EA) are encoded as 0xD7 0x90 to 0xD7 0xAA
+            if bytes[i] == 0xD7
+                && i + 1 < bytes.len()
+                && bytes[i + 1] >= 0x90
+                && bytes[i + 1] <= 0xAA
+            {
+                let c = 0x05D0 + (bytes[i + 1] - 0x90) as u32;
+                let index = match char::from_u32(c) {
+                    Some(c) => match standard_index(c) {
+                        Some(index) => index,
+                        None => 0,
+                    },
+                    None => 0,
+                };
+                let value = match self.letter_value(index) {
+                    Some(value) => value as u64,
+                    None => return None,
+                };
+                position += 1;
+                let value = match self {
+                    GematriaMethod::MisparHaAkhor => value * position,
+                    _ => value,
+                };
+                total = total.saturating_add(value);
+                i += 2;
+            } else {
+                i += 1;
+            }
+        }
+        let total = match self {
+            GematriaMethod::MisparKatanMispari => reduce_to_single_digit(total),
+            GematriaMethod::MisparHaMerubahHaKlali => total.saturating_mul(total),
+            _ => total,
+        };
+        Some(total)
+    }
+
+    /// All the methods with a built-in implementation, see [`GematriaMethod::all`].
+    pub const IMPLEMENTED: [GematriaMethod; 20] = [
+        GematriaMethod::MisparHechrechi,
+        GematriaMethod::MisparGadol,
+        GematriaMethod::MisparKatan,
+        GematriaMethod::OtiyotBeMilui,
+        GematriaMethod::Achbi,
+        GematriaMethod::Avgad,
+        GematriaMethod::AyakBachar,
+        GematriaMethod::MisparKidmi,
+        GematriaMethod::MisparPerati,
+        GematriaMethod::MisparHaMerubahHaKlali,
+        GematriaMethod::MisparKatanMispari,
+        GematriaMethod::MisparNeelam,
+        GematriaMethod::MiluiHaMilui,
+        GematriaMethod::AchasBeta,
+        GematriaMethod::MisparHaAkhor,
+        GematriaMethod::Nikud,
+        GematriaMethod::Isopsephy,
+        GematriaMethod::EnglishOrdinal,
+        GematriaMethod::EnglishQabalah,
+        GematriaMethod::Agrippa,
+    ];
+
+    /// Iterates over all the methods with a built-in implementation.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gematria_rs::GematriaMethod;
+    ///
+    /// let names: Vec<String> = GematriaMethod::all().map(|m| m.to_string()).collect();
+    /// assert_eq!(names[0], "Mispar Hechrechi");
+    /// assert!(!names.contains(&"Custom".to_string()));
+    /// ```
+    pub fn all() -> impl Iterator<Item = GematriaMethod> {
+        Self::IMPLEMENTED.into_iter()
+    }
+
+    /// Gets the friendly name of the method, as displayed.
+    pub fn name(&self) -> &'static str {
+        match self {
+            GematriaMethod::MisparHechrechi => "Mispar Hechrechi",
+            GematriaMethod::MisparGadol => "Mispar Gadol",
+            GematriaMethod::MisparKatan => "Mispar Katan",
+            GematriaMethod::MisparSiduri => "Mispar Siduri",
+            GematriaMethod::MisparBoneh => "Mispar Boneh",
+            GematriaMethod::MisparMeugal => "Mispar Meugal",
+            GematriaMethod::MisparMusafi => "Mispar Musafi",
+            GematriaMethod::OtiyotBeMilui => "Otiyot BeMilui",
+            GematriaMethod::Achbi => "Achbi",
+            GematriaMethod::Avgad => "Avgad",
+            GematriaMethod::AyakBachar => "Ayak Bachar",
+            GematriaMethod::MisparKidmi => "Mispar Kidmi",
+            GematriaMethod::MisparPerati => "Mispar Perati",
+            GematriaMethod::MisparHaMerubahHaKlali => "Mispar HaMerubah HaKlali",
+            GematriaMethod::MisparKatanMispari => "Mispar Katan Mispari",
+            GematriaMethod::MisparNeelam => "Mispar Neelam",
+            GematriaMethod::MiluiHaMilui => "Milui HaMilui",
+            GematriaMethod::AchasBeta => "Achas Beta",
+            GematriaMethod::MisparHaAkhor => "Mispar HaAkhor",
+            GematriaMethod::Nikud => "Nikud",
+            GematriaMethod::Isopsephy => "Isopsephy",
+            GematriaMethod::EnglishOrdinal => "English Ordinal",
+            GematriaMethod::EnglishQabalah => "English Qabalah",
+            GematriaMethod::Agrippa => "Agrippa",
+            GematriaMethod::Custom => "Custom",
+        }
+    }
+}
+
+impl fmt::Display for GematriaMethod {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// The error returned when parsing an unknown (or not implemented) [`GematriaMethod`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseMethodError {
+    // The input which failed to parse.
+    input: String,
+}
+
+impl ParseMethodError {
+    /// Gets the input which failed to parse.
+    pub fn input(&self) -> &str {
+        &self.input
+    }
+}
+
+impl fmt::Display for ParseMethodError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let names: Vec<&str> = GematriaMethod::all().map(|m| m.name()).collect();
+        write!(
+            f,
+            "unknown gematria method '{}', expected one of: {}",
+            self.input,
+            names.join(", ")
+        )
+    }
+}
+
+impl std::error::Error for ParseMethodError {}
+
+impl FromStr for GematriaMethod {
+    type Err = ParseMethodError;
+
+    /// Parses an implemented method, ignoring case, spaces, dashes and underscores,
+    /// so "Mispar Gadol", "mispar-gadol" and "MisparGadol" are all accepted.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let normalize = |name: &str| -> String {
+            name.chars()
+                .filter(|c| c.is_alphanumeric())
+                .flat_map(char::to_lowercase)
+                .collect()
+        };
+        let input = normalize(s);
+        GematriaMethod::all()
+            .find(|m| normalize(m.name()) == input)
+            .ok_or_else(|| ParseMethodError {
+                input: s.to_string(),
+            })
+    }
 }
 
 /// A trait defining the common functionality for gematria calculations.
-pub trait GematriaCalculation {
+///
+/// Strategies are `Send` and `Sync`, so contexts may be shared between threads.
+pub trait GematriaCalculation: Send + Sync {
     /// Calculates the gematria value for a given letter index.
     fn calculate_value(&self, letter_index: u32) -> u32;
 
     /// Returns the type of gematria calculation method.
     fn method_type(&self) -> GematriaMethod;
+
+    /// Calculates the value of a letter at a given position (1 based) within its word.
+    ///
+    /// Defaults to the value of the letter regardless of its position, positional methods override it.
+    fn calculate_positional_value(&self, letter_index: u32, _position: usize) -> u64 {
+        self.calculate_value(letter_index) as u64
+    }
+
+    /// Calculates the value of a character which is not a Hebrew letter, such as a vowel point.
+    ///
+    /// Defaults to `None`, as most methods only value letters, so such characters are ignored.
+    fn calculate_symbol_value(&self, _symbol: char) -> Option<u32> {
+        None
+    }
+
+    /// Word level post-processing hook, applied on the sum of the letter values of a word.
+    ///
+    /// Defaults to the sum itself, methods defined on the whole word (rather than per letter) override it.
+    fn finalize_total(&self, total: u64) -> u64 {
+        total
+    }
+
+    /// Same as [`GematriaCalculation::finalize_total`], `None` when the total overflows a `u64`.
+    ///
+    /// Defaults to the finalized total, methods whose finalization may overflow override it.
+    fn checked_finalize_total(&self, total: u64) -> Option<u64> {
+        Some(self.finalize_total(total))
+    }
+
+    /// Calculates the value of a letter when the final forms count as 500 to 900,
+    /// see [`crate::SofitPolicy::Extended`].
+    ///
+    /// Defaults to the value of the letter, methods valuing the final forms as their regular letters
+    /// while deriving from the standard values override it.
+    fn calculate_extended_value(&self, letter_index: u32) -> u32 {
+        self.calculate_value(letter_index)
+    }
 }
 
 /// Calculates the standard gematria value for a given Hebrew letter based on its index.
@@ -73,15 +366,28 @@ pub trait GematriaCalculation {
 ///
 /// More information about the standard gematria system can be found on the Wikipedia page:
 /// [Gematria - Wikipedia](https://en.wikipedia.org/wiki/Gematria#Standard_encoding_(Mispar_Hechrechi))
-pub fn std_gematria_value(letter_index: &u32) -> u32 {
-    10u32.pow((letter_index - 1) / 9) * (((letter_index - 1) % 9) + 1)
+pub const fn std_gematria_value(letter_index: &u32) -> u32 {
+    10u32.pow((*letter_index - 1) / 9) * (((*letter_index - 1) % 9) + 1)
+}
+
+/// Maps the final forms (ך, ם, ן, ף, ץ) to the index of their regular letter.
+pub(crate) const fn base_letter_index(letter_index: u32) -> u32 {
+    match letter_index {
+        23 => 11, // ך
+        24 => 13, // ם
+        25 => 14, // ן
+        26 => 17, // ף
+        27 => 18, // ץ
+        index => index,
+    }
 }
 
 #[derive(Clone)]
 pub struct MisparHechrechi;
 
-impl GematriaCalculation for MisparHechrechi {
-    fn calculate_value(&self, letter_index: u32) -> u32 {
+impl MisparHechrechi {
+    /// Gets the value of a letter index, in constant expressions too.
+    pub const fn letter_value(&self, letter_index: u32) -> u32 {
         match letter_index {
             // Unique handling for final forms
             23 => 20, // ך
@@ -93,17 +399,29 @@ impl GematriaCalculation for MisparHechrechi {
             index => std_gematria_value(&index),
         }
     }
+}
+
+impl GematriaCalculation for MisparHechrechi {
+    fn calculate_value(&self, letter_index: u32) -> u32 {
+        self.letter_value(letter_index)
+    }
 
     fn method_type(&self) -> GematriaMethod {
         GematriaMethod::MisparHechrechi
     }
+
+    fn calculate_extended_value(&self, letter_index: u32) -> u32 {
+        // The standard values continue with the final forms as 500 to 900
+        std_gematria_value(&letter_index)
+    }
 }
 
 #[derive(Clone)]
 pub struct MisparGadol;
 
-impl GematriaCalculation for MisparGadol {
-    fn calculate_value(&self, letter_index: u32) -> u32 {
+impl MisparGadol {
+    /// Gets the value of a letter index, in constant expressions too.
+    pub const fn letter_value(&self, letter_index: u32) -> u32 {
         match letter_index {
             // Unique handling for final forms
             23 => 500, // ך
@@ -115,6 +433,12 @@ impl GematriaCalculation for MisparGadol {
             index => std_gematria_value(&index),
         }
     }
+}
+
+impl GematriaCalculation for MisparGadol {
+    fn calculate_value(&self, letter_index: u32) -> u32 {
+        self.letter_value(letter_index)
+    }
 
     fn method_type(&self) -> GematriaMethod {
         GematriaMethod::MisparGadol
@@ -124,8 +448,9 @@ impl GematriaCalculation for MisparGadol {
 #[derive(Clone)]
 pub struct MisparKatan;
 
-impl GematriaCalculation for MisparKatan {
-    fn calculate_value(&self, letter_index: u32) -> u32 {
+impl MisparKatan {
+    /// Gets the value of a letter index, in constant expressions too.
+    pub const fn letter_value(&self, letter_index: u32) -> u32 {
         let value = match letter_index {
             // Unique handling for final forms
             23 => 500, // ך
@@ -138,7 +463,13 @@ impl GematriaCalculation for MisparKatan {
         };
 
         // Reduce the value to a single digit
-        Self::reduce_to_single_digit(value)
+        reduce_to_single_digit(value as u64) as u32
+    }
+}
+
+impl GematriaCalculation for MisparKatan {
+    fn calculate_value(&self, letter_index: u32) -> u32 {
+        self.letter_value(letter_index)
     }
 
     fn method_type(&self) -> GematriaMethod {
@@ -146,38 +477,219 @@ impl GematriaCalculation for MisparKatan {
     }
 }
 
-impl MisparKatan {
-    fn reduce_to_single_digit(mut value: u32) -> u32 {
-        while value >= 10 {
-            value = value
-                .to_string()
-                .chars()
-                .map(|d| d.to_digit(10).unwrap())
-                .sum();
+/// Represents the Mispar Katan Mispari (integral reduced value) method, where the letters are valued
+/// as in [`MisparKatan`] and the total of the word is then reduced to a single digit
+/// (e.g. שלום = 3 + 3 + 6 + 6 = 18 = 1 + 8 = 9).
+#[derive(Clone)]
+pub struct MisparKatanMispari;
+
+impl GematriaCalculation for MisparKatanMispari {
+    fn calculate_value(&self, letter_index: u32) -> u32 {
+        MisparKatan.calculate_value(letter_index)
+    }
+
+    fn method_type(&self) -> GematriaMethod {
+        GematriaMethod::MisparKatanMispari
+    }
+
+    fn finalize_total(&self, total: u64) -> u64 {
+        reduce_to_single_digit(total)
+    }
+}
+
+/// Reduces a value to a single digit by repeatedly summing its digits.
+pub(crate) const fn reduce_to_single_digit(mut value: u64) -> u64 {
+    while value >= 10 {
+        let mut digits_sum = 0;
+        while value > 0 {
+            digits_sum += value % 10;
+            value /= 10;
+        }
+        value = digits_sum;
+    }
+    value
+}
+
+/// Composable modifiers applied on the total value of a word, on top of any base method.
+///
+/// Modifiers are applied in the order they were added to the [`crate::GematriaBuilder`].
+///
+/// # Examples
+///
+/// ```
+/// use gematria_rs::{GematriaBuilder, Modifier};
+///
+/// let gmctx = GematriaBuilder::new()
+///     .with_modifier(Modifier::PlusKolel)
+///     .with_modifier(Modifier::ReduceToSingleDigit)
+///     .init_gematria();
+///
+/// // שלום = 376, plus kolel = 377, reduced = 3 + 7 + 7 = 17 = 1 + 7 = 8
+/// assert_eq!(gmctx.calculate_value("שלום").value(), 8);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Modifier {
+    /// Reduces the total to a single digit by repeatedly summing its digits.
+    ReduceToSingleDigit,
+    /// Takes the remainder of the total divided by N, a modulo of 0 leaves the total unchanged.
+    Modulo(u64),
+    /// Adds 1 to the total ("im hakolel").
+    PlusKolel,
+    /// Squares the total.
+    SquareTotal,
+}
+
+impl Modifier {
+    /// Applies the modifier on a total value, saturating at `u64::MAX` on overflow.
+    pub fn apply(&self, value: u64) -> u64 {
+        self.checked_apply(value).unwrap_or(u64::MAX)
+    }
+
+    /// Applies the modifier on a total value, `None` when the result overflows a `u64`.
+    pub fn checked_apply(&self, value: u64) -> Option<u64> {
+        match self {
+            Modifier::ReduceToSingleDigit => Some(reduce_to_single_digit(value)),
+            Modifier::Modulo(n) => Some(value.checked_rem(*n).unwrap_or(value)),
+            Modifier::PlusKolel => value.checked_add(1),
+            Modifier::SquareTotal => value.checked_mul(value),
+        }
+    }
+}
+
+/// Enumerates the "im hakolel" adjustments added to the value of a text, after the [`Modifier`]s.
+///
+/// The adjustment applied is recorded on the [`crate::GematriaResult`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Kolel {
+    /// Adds 1 for the text as a whole.
+    One,
+    /// Adds the number of words of the text.
+    Words,
+    /// Adds the number of letters of the text.
+    Letters,
+}
+
+/// Represents the Mispar Kidmi method, where each letter is valued as the sum of the standard values
+/// of all the letters up to and including it (e.g. ד = 1 + 2 + 3 + 4 = 10).
+/// Final forms are valued as their regular letters.
+#[derive(Clone)]
+pub struct MisparKidmi;
+
+impl MisparKidmi {
+    /// Gets the value of a letter index, in constant expressions too.
+    pub const fn letter_value(&self, letter_index: u32) -> u32 {
+        let mut value = 0;
+        let mut index = 1;
+        while index <= base_letter_index(letter_index) {
+            value += std_gematria_value(&index);
+            index += 1;
         }
         value
     }
 }
 
+impl GematriaCalculation for MisparKidmi {
+    fn calculate_value(&self, letter_index: u32) -> u32 {
+        self.letter_value(letter_index)
+    }
+
+    fn method_type(&self) -> GematriaMethod {
+        GematriaMethod::MisparKidmi
+    }
+
+    fn calculate_extended_value(&self, letter_index: u32) -> u32 {
+        (1..=letter_index)
+            .map(|index| std_gematria_value(&index))
+            .sum()
+    }
+}
+
+/// Represents the Mispar HaPerati method, where each letter is valued as the square of its
+/// standard value (e.g. ב = 4, ת = 160000). Final forms are valued as their regular letters.
+#[derive(Clone)]
+pub struct MisparPerati;
+
+impl MisparPerati {
+    /// Gets the value of a letter index, in constant expressions too.
+    pub const fn letter_value(&self, letter_index: u32) -> u32 {
+        MisparHechrechi.letter_value(letter_index).pow(2)
+    }
+}
+
+impl GematriaCalculation for MisparPerati {
+    fn calculate_value(&self, letter_index: u32) -> u32 {
+        self.letter_value(letter_index)
+    }
+
+    fn method_type(&self) -> GematriaMethod {
+        GematriaMethod::MisparPerati
+    }
+
+    fn calculate_extended_value(&self, letter_index: u32) -> u32 {
+        MisparHechrechi
+            .calculate_extended_value(letter_index)
+            .pow(2)
+    }
+}
+
+/// Represents the Mispar HaMerubah HaKlali method, where the value of a word is the square of its
+/// standard value (e.g. שלום = 376² = 141376).
+#[derive(Clone)]
+pub struct MisparHaMerubahHaKlali;
+
+impl GematriaCalculation for MisparHaMerubahHaKlali {
+    fn calculate_value(&self, letter_index: u32) -> u32 {
+        MisparHechrechi.calculate_value(letter_index)
+    }
+
+    fn method_type(&self) -> GematriaMethod {
+        GematriaMethod::MisparHaMerubahHaKlali
+    }
+
+    fn calculate_extended_value(&self, letter_index: u32) -> u32 {
+        MisparHechrechi.calculate_extended_value(letter_index)
+    }
+
+    fn finalize_total(&self, total: u64) -> u64 {
+        total.saturating_mul(total)
+    }
+
+    fn checked_finalize_total(&self, total: u64) -> Option<u64> {
+        total.checked_mul(total)
+    }
+}
+
 /// Represents the Otiyot BeMilui method where each letter is represented by its full spelling.
+///
+/// With a depth above 1 the letters of the spelling are themselves expanded to their full spelling,
+/// e.g. the Milui HaMilui (double filling) method has a depth of 2.
 pub struct OtyiotBeMilui {
     filled_letters: FullCharMap,
     char_to_index: CharMap,
+    depth: u32,
 }
 
 impl GematriaCalculation for OtyiotBeMilui {
     fn calculate_value(&self, letter_index: u32) -> u32 {
         let mut val = 0;
-        // Convert index to character first
+        // Convert index to character first, only letters with a full spelling are valued
         if let Some(letter) = self.index_to_char(letter_index) {
-            val = self.explode_full_letters(letter)
+            if self.filled_letters.contains_key(&letter) {
+                val = self.explode_full_letters(letter, self.depth)
+            }
         };
 
         val
     }
 
     fn method_type(&self) -> GematriaMethod {
-        GematriaMethod::OtiyotBeMilui
+        if self.depth > 1 {
+            GematriaMethod::MiluiHaMilui
+        } else {
+            GematriaMethod::OtiyotBeMilui
+        }
     }
 }
 
@@ -186,34 +698,176 @@ impl OtyiotBeMilui {
         Self {
             filled_letters: full_map,
             char_to_index: index_map,
+            depth: 1,
         }
     }
 
-    fn explode_full_letters(&self, charcter: char) -> u32 {
-        if let Some(filled_form) = self.filled_letters.get(&charcter) {
+    /// Sets how many times letters are expanded to their full spelling, a depth of 0 is treated as 1.
+    pub fn with_depth(mut self, depth: u32) -> Self {
+        self.depth = depth.max(1);
+        self
+    }
+
+    fn explode_full_letters(&self, charcter: char, depth: u32) -> u32 {
+        let index = match self.char_to_index.get(&charcter) {
+            Some(&index) => index,
+            None => return 0,
+        };
+        if depth == 0 {
+            // Final forms are valued as their regular letters
+            return std_gematria_value(&base_letter_index(index));
+        }
+
+        // Final forms within a spelling are expanded as their regular letters
+        let letter = self
+            .index_to_char(base_letter_index(index))
+            .unwrap_or(charcter);
+        if let Some(filled_form) = self.filled_letters.get(&letter) {
             filled_form
                 .iter()
-                .map(|&c| {
-                    match self.char_to_index.get(&c).unwrap() {
-                        // Unique handling for final forms
-                        23 => 20, // ך
-                        24 => 40, // ם
-                        25 => 50, // ן
-                        26 => 80, // ף
-                        27 => 90, // ץ
-                        // Standard calculation for other letters
-                        index => std_gematria_value(index),
-                    }
-                })
+                .map(|&c| self.explode_full_letters(c, depth - 1))
                 .sum()
         } else {
             0
         }
     }
 
+    // Only the letters with a full spelling are looked up, so other characters sharing their index are skipped
     fn index_to_char(&self, index: u32) -> Option<char> {
-        self.char_to_index
-            .iter()
-            .find_map(|(&c, &i)| if i == index { Some(c) } else { None })
+        self.char_to_index.iter().find_map(|(&c, &i)| {
+            if i == index && self.filled_letters.contains_key(&c) {
+                Some(c)
+            } else {
+                None
+            }
+        })
+    }
+}
+
+/// Represents the Mispar HaAkhor (positional weighting) method, where the standard value of each letter
+/// is multiplied by its position in the word (e.g. שלום = 300×1 + 30×2 + 6×3 + 40×4 = 538).
+#[derive(Clone)]
+pub struct MisparHaAkhor;
+
+impl GematriaCalculation for MisparHaAkhor {
+    fn calculate_value(&self, letter_index: u32) -> u32 {
+        MisparHechrechi.calculate_value(letter_index)
+    }
+
+    fn method_type(&self) -> GematriaMethod {
+        GematriaMethod::MisparHaAkhor
+    }
+
+    fn calculate_extended_value(&self, letter_index: u32) -> u32 {
+        MisparHechrechi.calculate_extended_value(letter_index)
+    }
+
+    fn calculate_positional_value(&self, letter_index: u32, position: usize) -> u64 {
+        self.calculate_value(letter_index) as u64 * position as u64
+    }
+}
+
+/// Represents the Nikud (vowel points) method, where the vowel points of a pointed word are valued
+/// rather than its letters.
+///
+/// Following the tradition of valuing the vowels by their shape, a dot is valued as י (10)
+/// and a line as ו (6), e.g. kamatz (ָ) = 16, patach (ַ) = 6 and segol (ֶ) = 30.
+/// Composite vowels (chataf) are the sum of their parts. Letters, the dagesh (and so the shuruk),
+/// the shin and sin dots and the cantillation marks are not valued.
+#[derive(Clone)]
+pub struct Nikud;
+
+impl Nikud {
+    /// Gets the value of a vowel point, `None` for other characters.
+    pub fn vowel_value(vowel: char) -> Option<u32> {
+        let value = match vowel {
+            '\u{05B0}' => 20, // Shva
+            '\u{05B1}' => 50, // Chataf segol
+            '\u{05B2}' => 26, // Chataf patach
+            '\u{05B3}' => 36, // Chataf kamatz
+            '\u{05B4}' => 10, // Chirik
+            '\u{05B5}' => 20, // Tsere
+            '\u{05B6}' => 30, // Segol
+            '\u{05B7}' => 6,  // Patach
+            '\u{05B8}' => 16, // Kamatz
+            '\u{05B9}' => 10, // Cholam
+            '\u{05BA}' => 10, // Cholam chaser for vav
+            '\u{05BB}' => 30, // Kubutz
+            '\u{05C7}' => 16, // Kamatz katan
+            _ => return None,
+        };
+        Some(value)
+    }
+}
+
+impl GematriaCalculation for Nikud {
+    fn calculate_value(&self, _letter_index: u32) -> u32 {
+        0
+    }
+
+    fn method_type(&self) -> GematriaMethod {
+        GematriaMethod::Nikud
+    }
+
+    fn calculate_symbol_value(&self, symbol: char) -> Option<u32> {
+        Nikud::vowel_value(symbol)
+    }
+}
+
+/// Enumerates the traditional milui spellings of the Tetragrammaton, which differ in the spelling of ה and ו.
+///
+/// Used with [`crate::GematriaBuilder::with_milui_tradition`] to select the spellings of
+/// the [`GematriaMethod::OtiyotBeMilui`] family of methods.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum MiluiTradition {
+    /// ע״ב (72): ה is spelled הי and ו is spelled ויו.
+    Av,
+    /// ס״ג (63): ה is spelled הי and ו is spelled ואו.
+    Sag,
+    /// מ״ה (45): ה is spelled הא and ו is spelled ואו.
+    Mah,
+    /// ב״ן (52): ה is spelled הה and ו is spelled וו.
+    Ban,
+}
+
+impl MiluiTradition {
+    /// Gets the spellings of ה and ו in this tradition.
+    pub fn spellings(&self) -> [(char, &'static str); 2] {
+        match self {
+            MiluiTradition::Av => [('ה', "הי"), ('ו', "ויו")],
+            MiluiTradition::Sag => [('ה', "הי"), ('ו', "ואו")],
+            MiluiTradition::Mah => [('ה', "הא"), ('ו', "ואו")],
+            MiluiTradition::Ban => [('ה', "הה"), ('ו', "וו")],
+        }
+    }
+}
+
+/// Represents the Mispar Ne'elam (hidden milui) method, where each letter is valued by its full
+/// spelling minus the letter itself (e.g. א = אלף - א = 110).
+///
+/// Final forms are treated as their regular letters.
+pub struct MisparNeelam {
+    milui: OtyiotBeMilui,
+}
+
+impl MisparNeelam {
+    pub fn new(full_map: FullCharMap, index_map: CharMap) -> Self {
+        Self {
+            milui: OtyiotBeMilui::new(full_map, index_map),
+        }
+    }
+}
+
+impl GematriaCalculation for MisparNeelam {
+    fn calculate_value(&self, letter_index: u32) -> u32 {
+        let index = base_letter_index(letter_index);
+        self.milui
+            .calculate_value(index)
+            .saturating_sub(std_gematria_value(&index))
+    }
+
+    fn method_type(&self) -> GematriaMethod {
+        GematriaMethod::MisparNeelam
     }
 }
@@ -0,0 +1,247 @@
+// Milestones fired while streaming text through a gematria context.
+
+use std::{
+    collections::BTreeSet,
+    io::{self, BufRead},
+};
+
+use crate::{GematriaContext, GematriaResult};
+
+/// Enumerates the kinds of milestones a [`MilestoneTracker`] reports.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MilestoneKind {
+    /// The running total of the whole stream reached (or passed) a target value.
+    RunningTotal,
+    /// The total of a verse is exactly a target value.
+    VerseTotal,
+    /// The value of a word is exactly a target value.
+    Word,
+}
+
+/// `Milestone` describes a target value hit while streaming.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Milestone {
+    // The kind of milestone.
+    kind: MilestoneKind,
+
+    // The target value which was hit.
+    target: u64,
+
+    // The word hitting the target, for word milestones.
+    word: Option<String>,
+
+    // The index of the last word observed (0 based).
+    token_index: usize,
+
+    // The index of the verse being processed (0 based).
+    verse_index: usize,
+}
+
+/// `MilestoneTracker` follows the words flowing through a stream and invokes a callback
+/// whenever a running total, a verse total or a single word hits one of the target values.
+///
+/// # Examples
+///
+/// ```
+/// use gematria_rs::{GematriaContext, MilestoneKind, MilestoneTracker};
+///
+/// let gmctx = GematriaContext::default();
+/// let mut hits = Vec::new();
+/// let mut tracker = MilestoneTracker::new([70, 250], |m| hits.push((m.kind(), m.target())));
+///
+/// let text = "נכנס יין\nיצא סוד\n";
+/// gmctx.stream_with_milestones(text.as_bytes(), &mut tracker)?;
+/// drop(tracker);
+///
+/// assert_eq!(
+///     hits,
+///     vec![
+///         (MilestoneKind::RunningTotal, 70),
+///         (MilestoneKind::Word, 70),
+///         (MilestoneKind::RunningTotal, 250),
+///         (MilestoneKind::VerseTotal, 250),
+///         (MilestoneKind::Word, 70),
+///     ]
+/// );
+/// # Ok::<(), std::io::Error>(())
+/// ```
+pub struct MilestoneTracker<F> {
+    // The watched target values.
+    targets: BTreeSet<u64>,
+
+    // The total of all the observed words.
+    running_total: u64,
+
+    // The total of the current verse.
+    verse_total: u64,
+
+    // The number of observed words.
+    tokens: usize,
+
+    // The number of completed verses.
+    verses: usize,
+
+    // The callback invoked on every milestone.
+    callback: F,
+}
+
+impl Milestone {
+    /// Gets the kind of milestone.
+    pub fn kind(&self) -> MilestoneKind {
+        self.kind
+    }
+
+    /// Gets the target value which was hit.
+    pub fn target(&self) -> u64 {
+        self.target
+    }
+
+    /// Gets the word hitting the target, for [`MilestoneKind::Word`] milestones.
+    pub fn word(&self) -> Option<&str> {
+        self.word.as_deref()
+    }
+
+    /// Gets the index of the last word observed when the milestone was hit.
+    pub fn token_index(&self) -> usize {
+        self.token_index
+    }
+
+    /// Gets the index of the verse in which the milestone was hit.
+    pub fn verse_index(&self) -> usize {
+        self.verse_index
+    }
+}
+
+impl<F: FnMut(&Milestone)> MilestoneTracker<F> {
+    /// Creates a tracker watching the given target values.
+    pub fn new<I: IntoIterator<Item = u64>>(targets: I, callback: F) -> Self {
+        Self {
+            targets: targets.into_iter().collect(),
+            running_total: 0,
+            verse_total: 0,
+            tokens: 0,
+            verses: 0,
+            callback,
+        }
+    }
+
+    /// Gets the running total of all the observed words.
+    pub fn running_total(&self) -> u64 {
+        self.running_total
+    }
+
+    /// Observes the result of the next word of the stream.
+    pub fn observe(&mut self, result: &GematriaResult) {
+        let value = result.value();
+        if self.targets.contains(&value) {
+            self.fire(
+                MilestoneKind::Word,
+                value,
+                Some(result.word().to_string()),
+                self.tokens,
+            );
+        }
+
+        let previous = self.running_total;
+        self.running_total = self.running_total.saturating_add(value);
+        self.verse_total = self.verse_total.saturating_add(value);
+
+        // Every target passed by the running total is reported once
+        let passed: Vec<u64> = self
+            .targets
+            .iter()
+            .copied()
+            .filter(|&t| previous < t && t <= self.running_total)
+            .collect();
+        for target in passed {
+            self.fire(MilestoneKind::RunningTotal, target, None, self.tokens);
+        }
+
+        self.tokens += 1;
+    }
+
+    /// Marks the end of the current verse, checking its total.
+    pub fn end_verse(&mut self) {
+        if self.targets.contains(&self.verse_total) {
+            // The last word of the verse was already counted (0 for a stream without words)
+            let token_index = self.tokens.saturating_sub(1);
+            self.fire(
+                MilestoneKind::VerseTotal,
+                self.verse_total,
+                None,
+                token_index,
+            );
+        }
+
+        self.verse_total = 0;
+        self.verses += 1;
+    }
+
+    fn fire(&mut self, kind: MilestoneKind, target: u64, word: Option<String>, token_index: usize) {
+        let milestone = Milestone {
+            kind,
+            target,
+            word,
+            token_index,
+            verse_index: self.verses,
+        };
+        (self.callback)(&milestone);
+    }
+}
+
+impl GematriaContext {
+    /// Streams a text line by line through a [`MilestoneTracker`], with memory bounded by the longest line.
+    ///
+    /// Each line is treated as a verse. Returns the running total of the whole stream.
+    pub fn stream_with_milestones<R, F>(
+        &self,
+        reader: R,
+        tracker: &mut MilestoneTracker<F>,
+    ) -> io::Result<u64>
+    where
+        R: BufRead,
+        F: FnMut(&Milestone),
+    {
+        for line in reader.lines() {
+            let line = line?;
+            for word in self.tokenizer.tokenize(&line) {
+                tracker.observe(&self.calculate_value(word));
+            }
+            tracker.end_verse();
+        }
+
+        Ok(tracker.running_total())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_running_total_passes_many_targets() {
+        let gmctx = GematriaContext::default();
+        let mut hits = Vec::new();
+        let mut tracker = MilestoneTracker::new([1, 376, 400], |m: &Milestone| {
+            hits.push((m.kind(), m.target(), m.token_index()))
+        });
+
+        let total = gmctx
+            .stream_with_milestones("שָׁלוֹם\nא".as_bytes(), &mut tracker)
+            .unwrap();
+        drop(tracker);
+
+        assert_eq!(total, 377);
+        assert_eq!(
+            hits,
+            vec![
+                (MilestoneKind::Word, 376, 0),
+                (MilestoneKind::RunningTotal, 1, 0),
+                (MilestoneKind::RunningTotal, 376, 0),
+                (MilestoneKind::VerseTotal, 376, 0),
+                (MilestoneKind::Word, 1, 1),
+                (MilestoneKind::VerseTotal, 1, 1),
+            ]
+        );
+    }
+}
@@ -0,0 +1,64 @@
+// Unicode normalization of texts before calculation.
+
+use std::borrow::Cow;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use unicode_normalization::{is_nfc, is_nfkc, UnicodeNormalization};
+
+/// Enumerates the Unicode normalization forms applied on texts before calculation,
+/// see [`crate::GematriaBuilder::with_normalization`].
+///
+/// Both forms compose letters and points consistently, so decomposed (NFD) and precomposed input
+/// yield the same values. Hebrew presentation forms with points (such as שׁ, U+FB2A) are decomposed
+/// into their letter and points by both forms.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Normalization {
+    /// Canonical composition (NFC).
+    Nfc,
+    /// Compatibility composition (NFKC), which also maps compatibility characters
+    /// such as the wide letters (U+FB21 to U+FB28) and the alef-lamed ligature (ﭏ) to plain letters.
+    Nfkc,
+}
+
+impl Normalization {
+    /// Normalizes a text, borrowing it when it is already normalized.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gematria_rs::Normalization;
+    ///
+    /// assert_eq!(Normalization::Nfkc.apply("\u{FB4F}"), "אל");
+    /// assert_eq!(Normalization::Nfc.apply("\u{FB2A}"), "ש\u{05C1}");
+    /// ```
+    pub fn apply<'t>(&self, text: &'t str) -> Cow<'t, str> {
+        match self {
+            Normalization::Nfc if is_nfc(text) => Cow::Borrowed(text),
+            Normalization::Nfc => Cow::Owned(text.nfc().collect()),
+            Normalization::Nfkc if is_nfkc(text) => Cow::Borrowed(text),
+            Normalization::Nfkc => Cow::Owned(text.nfkc().collect()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::GematriaBuilder;
+
+    #[test]
+    fn test_normalized_values_are_equal() {
+        // Shin with a shin dot and a qamats, precomposed and in two orders of marks
+        let forms = ["\u{FB2A}\u{05B8}", "ש\u{05C1}\u{05B8}", "ש\u{05B8}\u{05C1}"];
+        let gmctx = GematriaBuilder::new()
+            .with_normalization(Normalization::Nfc)
+            .with_vowels(true)
+            .init_gematria();
+
+        let results: Vec<_> = forms.iter().map(|f| gmctx.calculate_value(f)).collect();
+        assert!(results.iter().all(|r| r.value() == 300));
+        assert!(results.iter().all(|r| r.word() == results[0].word()));
+    }
+}
@@ -0,0 +1,662 @@
+// Hebrew numerals utilities, such as validating the conventional ("kosher") spelling of numerals.
+
+use std::fmt;
+
+/// The Hebrew punctuation geresh (׳), marking a single letter numeral.
+pub const GERESH: char = '\u{05F3}';
+
+/// The Hebrew punctuation gershayim (״), placed before the last letter of a numeral.
+pub const GERSHAYIM: char = '\u{05F4}';
+
+/// Discouraged letter sequences, their conventional alternative, the reason to avoid them
+/// and whether they are only discouraged at the end of a numeral.
+///
+/// שד is only avoided as the numeral 304: the alternative to שמד (שדמ) spells it within a larger numeral.
+const DISCOURAGED_SEQUENCES: [(&str, &str, &str, bool); 6] = [
+    ("יה", "טו", "spells a divine name", false),
+    ("יו", "טז", "spells a divine name", false),
+    ("שמד", "שדמ", "spells 'destruction'", false),
+    ("רצח", "רחצ", "spells 'murder'", false),
+    ("רע", "ער", "spells 'evil'", false),
+    ("שד", "דש", "spells 'demon'", true),
+];
+
+/// `NumeralIssue` describes a discouraged letter sequence found in a Hebrew numeral.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NumeralIssue {
+    // The discouraged sequence.
+    sequence: &'static str,
+
+    // The position of the sequence within the letters of the numeral (0 based).
+    position: usize,
+
+    // The conventional alternative for the sequence.
+    suggestion: &'static str,
+
+    // Why the sequence is discouraged.
+    reason: &'static str,
+}
+
+impl NumeralIssue {
+    /// Gets the discouraged sequence.
+    pub fn sequence(&self) -> &str {
+        self.sequence
+    }
+
+    /// Gets the position of the sequence within the letters of the numeral, ignoring punctuation.
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    /// Gets the conventional alternative for the sequence.
+    pub fn suggestion(&self) -> &str {
+        self.suggestion
+    }
+
+    /// Gets the reason the sequence is discouraged.
+    pub fn reason(&self) -> &str {
+        self.reason
+    }
+}
+
+// Util function to check whether a character marks a numeral (geresh, gershayim or quotes).
+fn is_numeral_mark(c: char) -> bool {
+    is_geresh_mark(c) || matches!(c, GERSHAYIM | '"' | '\u{201D}')
+}
+
+// Util function to check whether a character is a geresh, or a quote used as one.
+fn is_geresh_mark(c: char) -> bool {
+    matches!(c, GERESH | '\'' | '\u{2019}')
+}
+
+/// Validates a Hebrew numeral, flagging discouraged letter sequences such as יה (15) or רע (270).
+///
+/// Numeral marks (geresh, gershayim and quotes) are ignored.
+///
+/// # Examples
+///
+/// ```
+/// use gematria_rs::validate_numeral;
+///
+/// let issues = validate_numeral("תשע״ה");
+/// assert!(issues.is_empty());
+///
+/// let issues = validate_numeral("י״ה");
+/// assert_eq!(issues[0].sequence(), "יה");
+/// assert_eq!(issues[0].suggestion(), "טו");
+/// ```
+pub fn validate_numeral(numeral: &str) -> Vec<NumeralIssue> {
+    let letters: Vec<char> = numeral.chars().filter(|&c| !is_numeral_mark(c)).collect();
+    let mut issues = Vec::new();
+    let mut position = 0;
+    while position < letters.len() {
+        let found = DISCOURAGED_SEQUENCES.iter().find(|(sequence, _, _, tail)| {
+            let len = sequence.chars().count();
+            letters.len() >= position + len
+                && (!tail || letters.len() == position + len)
+                && sequence
+                    .chars()
+                    .eq(letters[position..position + len].iter().copied())
+        });
+        match found {
+            Some(&(sequence, suggestion, reason, _)) => {
+                issues.push(NumeralIssue {
+                    sequence,
+                    position,
+                    suggestion,
+                    reason,
+                });
+                position += sequence.chars().count();
+            }
+            None => position += 1,
+        }
+    }
+
+    issues
+}
+
+/// Rewrites a Hebrew numeral using the conventional alternatives to discouraged sequences.
+///
+/// When the numeral is marked (with a geresh, gershayim or quotes), the result is marked with
+/// a gershayim before its last letter, or a geresh after a single letter.
+///
+/// # Examples
+///
+/// ```
+/// use gematria_rs::kosher_numeral;
+///
+/// assert_eq!(kosher_numeral("י״ה"), "ט״ו");
+/// assert_eq!(kosher_numeral("תשרעד"), "תשערד");
+/// assert_eq!(kosher_numeral("כ״ז"), "כ״ז");
+/// ```
+pub fn kosher_numeral(numeral: &str) -> String {
+    let mut letters: Vec<char> = numeral.chars().filter(|&c| !is_numeral_mark(c)).collect();
+    for issue in validate_numeral(numeral) {
+        for (offset, c) in issue.suggestion.chars().enumerate() {
+            letters[issue.position + offset] = c;
+        }
+    }
+
+    let mut kosher: String = letters.iter().collect();
+    if numeral.chars().any(is_numeral_mark) {
+        mark_numeral(&mut kosher);
+    }
+
+    kosher
+}
+
+/// Enumerates how the thousands of a numeral are written.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Thousands {
+    /// The thousands are written as a numeral followed by a geresh, e.g. ה׳תשפ״ד for 5784.
+    #[default]
+    Geresh,
+    /// The thousands are omitted, as is customary for years, e.g. תשפ״ד for 5784.
+    Omit,
+}
+
+/// `NumeralOptions` configures how numbers are written as Hebrew numerals.
+///
+/// By default thousands are written with a geresh, numerals are marked with a geresh or gershayim,
+/// and discouraged letter sequences (such as רע for 270) are replaced by their conventional alternative.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct NumeralOptions {
+    // How the thousands are written.
+    thousands: Thousands,
+
+    // Whether the numeral is marked with a geresh or gershayim.
+    punctuation: bool,
+
+    // Whether discouraged letter sequences are replaced.
+    kosher: bool,
+}
+
+impl Default for NumeralOptions {
+    fn default() -> Self {
+        NumeralOptions {
+            thousands: Thousands::Geresh,
+            punctuation: true,
+            kosher: true,
+        }
+    }
+}
+
+impl NumeralOptions {
+    /// Creates the default options.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets how the thousands are written.
+    pub fn with_thousands(mut self, thousands: Thousands) -> Self {
+        self.thousands = thousands;
+        self
+    }
+
+    /// Sets whether the numeral is marked with a geresh or gershayim.
+    pub fn with_punctuation(mut self, punctuation: bool) -> Self {
+        self.punctuation = punctuation;
+        self
+    }
+
+    /// Sets whether discouraged letter sequences are replaced, 15 and 16 are always written טו and טז.
+    pub fn with_kosher_sequences(mut self, kosher: bool) -> Self {
+        self.kosher = kosher;
+        self
+    }
+}
+
+const UNITS: [&str; 10] = ["", "א", "ב", "ג", "ד", "ה", "ו", "ז", "ח", "ט"];
+const TENS: [&str; 10] = ["", "י", "כ", "ל", "מ", "נ", "ס", "ע", "פ", "צ"];
+const HUNDREDS: [&str; 10] = ["", "ק", "ר", "ש", "ת", "תק", "תר", "תש", "תת", "תתק"];
+
+/// Writes a number as a Hebrew numeral, with the default [`NumeralOptions`].
+///
+/// Zero has no Hebrew numeral and is written as an empty string.
+///
+/// # Examples
+///
+/// ```
+/// use gematria_rs::to_hebrew_numeral;
+///
+/// assert_eq!(to_hebrew_numeral(5784), "ה׳תשפ״ד");
+/// assert_eq!(to_hebrew_numeral(15), "ט״ו");
+/// assert_eq!(to_hebrew_numeral(5), "ה׳");
+/// ```
+pub fn to_hebrew_numeral(number: u32) -> String {
+    to_hebrew_numeral_with(number, &NumeralOptions::default())
+}
+
+/// Writes a number as a Hebrew numeral, with the given options.
+///
+/// # Examples
+///
+/// ```
+/// use gematria_rs::{to_hebrew_numeral_with, NumeralOptions, Thousands};
+///
+/// let years = NumeralOptions::new().with_thousands(Thousands::Omit);
+/// assert_eq!(to_hebrew_numeral_with(5784, &years), "תשפ״ד");
+/// assert_eq!(to_hebrew_numeral_with(5744, &years), "תשד״מ");
+/// ```
+pub fn to_hebrew_numeral_with(number: u32, options: &NumeralOptions) -> String {
+    let mut numeral = String::new();
+    if options.thousands == Thousands::Geresh && number >= 1000 {
+        numeral.push_str(&to_hebrew_numeral_with(
+            number / 1000,
+            &options.with_punctuation(false),
+        ));
+        if options.punctuation {
+            numeral.push(GERESH);
+        }
+    }
+
+    let mut letters = numeral_letters(number % 1000);
+    if options.kosher {
+        letters = kosher_numeral(&letters);
+    }
+    if options.punctuation {
+        mark_numeral(&mut letters);
+    }
+    numeral.push_str(&letters);
+
+    numeral
+}
+
+/// `ParseNumeralError` is returned when a string is not a valid Hebrew numeral.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseNumeralError {
+    // The input which failed to parse.
+    input: String,
+
+    // Why the input is not a valid numeral.
+    reason: &'static str,
+}
+
+impl ParseNumeralError {
+    fn new(input: &str, reason: &'static str) -> Self {
+        ParseNumeralError {
+            input: input.to_string(),
+            reason,
+        }
+    }
+
+    /// Gets the input which failed to parse.
+    pub fn input(&self) -> &str {
+        &self.input
+    }
+
+    /// Gets the reason the input is not a valid numeral.
+    pub fn reason(&self) -> &str {
+        self.reason
+    }
+}
+
+impl fmt::Display for ParseNumeralError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "invalid Hebrew numeral '{}': {}",
+            self.input, self.reason
+        )
+    }
+}
+
+impl std::error::Error for ParseNumeralError {}
+
+/// Parses a Hebrew numeral into a number, the inverse of [`to_hebrew_numeral`].
+///
+/// Geresh, gershayim, quote characters and spaces are accepted in any position. A geresh followed by more
+/// letters marks thousands (ה׳תשפ״ד is 5784). Letters must be in the standard order, apart from the
+/// conventional alternatives to discouraged sequences (such as טו for 15 or ער for 270),
+/// and final forms count as their regular letters. A lone letter with a geresh is read as units,
+/// so א׳ is 1 rather than 1000.
+///
+/// # Examples
+///
+/// ```
+/// use gematria_rs::parse_hebrew_numeral;
+///
+/// assert_eq!(parse_hebrew_numeral("תשפ״ד"), Ok(784));
+/// assert_eq!(parse_hebrew_numeral("כ\"ז"), Ok(27));
+/// assert_eq!(parse_hebrew_numeral("ה'תשפ\"ד"), Ok(5784));
+/// assert!(parse_hebrew_numeral("אכ").is_err());
+/// ```
+pub fn parse_hebrew_numeral(numeral: &str) -> Result<u32, ParseNumeralError> {
+    let numeral = numeral.trim();
+    // A geresh followed by letters separates the thousands
+    let split = numeral.char_indices().find(|&(i, c)| {
+        is_geresh_mark(c)
+            && numeral[i + c.len_utf8()..]
+                .chars()
+                .any(|c| !is_numeral_mark(c))
+    });
+    let (thousands, rest) = match split {
+        Some((i, c)) => (Some(&numeral[..i]), &numeral[i + c.len_utf8()..]),
+        None => (None, numeral),
+    };
+
+    let mut number = parse_group(numeral, rest)?;
+    if let Some(thousands) = thousands {
+        number += parse_group(numeral, thousands)? * 1000;
+    }
+
+    Ok(number)
+}
+
+/// The thousands implied by a Hebrew year written without them (תשפ״ד is 5784).
+pub const IMPLICIT_YEAR_THOUSANDS: u32 = 5000;
+
+/// The word following the thousands of a year of whole thousands (ה׳ אלפים is 5000), see [`format_hebrew_year`].
+const THOUSANDS_WORD: &str = "אלפים";
+
+/// The word for a single thousand (א׳ אלף is 1000).
+const THOUSAND_WORD: &str = "אלף";
+
+/// Writes a Hebrew calendar year as a Hebrew numeral, omitting the implicit 5000s.
+///
+/// Years outside the sixth millennium are written with their thousands. Whole thousands, which have
+/// no letters left, are followed by the word אלפים (or אלף), as ה׳ alone is the year 5005.
+///
+/// # Examples
+///
+/// ```
+/// use gematria_rs::format_hebrew_year;
+///
+/// assert_eq!(format_hebrew_year(5784), "תשפ״ד");
+/// assert_eq!(format_hebrew_year(5770), "תש״ע");
+/// assert_eq!(format_hebrew_year(4999), "ד׳תתקצ״ט");
+/// assert_eq!(format_hebrew_year(5000), "ה׳ אלפים");
+/// ```
+pub fn format_hebrew_year(year: u32) -> String {
+    if year >= 1000 && year.is_multiple_of(1000) {
+        let thousands = year / 1000;
+        let word = match thousands {
+            1 => THOUSAND_WORD,
+            _ => THOUSANDS_WORD,
+        };
+        let numeral =
+            to_hebrew_numeral_with(thousands, &NumeralOptions::new().with_punctuation(false));
+        return format!("{}{} {}", numeral, GERESH, word);
+    }
+
+    let implicit = (IMPLICIT_YEAR_THOUSANDS + 1..IMPLICIT_YEAR_THOUSANDS + 1000).contains(&year);
+    let thousands = if implicit {
+        Thousands::Omit
+    } else {
+        Thousands::Geresh
+    };
+    to_hebrew_numeral_with(year, &NumeralOptions::new().with_thousands(thousands))
+}
+
+/// Parses a Hebrew calendar year, adding the implicit 5000s when the thousands are omitted.
+///
+/// Whole thousands are read from their thousands followed by the word אלפים or אלף, see [`format_hebrew_year`].
+///
+/// # Examples
+///
+/// ```
+/// use gematria_rs::parse_hebrew_year;
+///
+/// assert_eq!(parse_hebrew_year("תשפ\"ד"), Ok(5784));
+/// assert_eq!(parse_hebrew_year("ה׳תשפ״ד"), Ok(5784));
+/// assert_eq!(parse_hebrew_year("ד׳תתקצ״ט"), Ok(4999));
+/// assert_eq!(parse_hebrew_year("ה׳ אלפים"), Ok(5000));
+/// ```
+pub fn parse_hebrew_year(year: &str) -> Result<u32, ParseNumeralError> {
+    let trimmed = year.trim();
+    if let Some((thousands, word)) = trimmed.rsplit_once(char::is_whitespace) {
+        if word == THOUSANDS_WORD || word == THOUSAND_WORD {
+            let thousands = parse_hebrew_numeral(thousands)
+                .map_err(|_| ParseNumeralError::new(year, "invalid thousands"))?;
+            return thousands
+                .checked_mul(1000)
+                .ok_or_else(|| ParseNumeralError::new(year, "too many thousands"));
+        }
+    }
+
+    let number = parse_hebrew_numeral(year)?;
+    if number < 1000 {
+        Ok(number + IMPLICIT_YEAR_THOUSANDS)
+    } else {
+        Ok(number)
+    }
+}
+
+/// Gets the number denoted by a word marked as a numeral (with a geresh, gershayim or quotes),
+/// `None` for unmarked words and marked words which are not valid numerals (such as most abbreviations).
+pub(crate) fn marked_numeral(word: &str) -> Option<u32> {
+    if !word.chars().any(is_numeral_mark) {
+        return None;
+    }
+    parse_hebrew_numeral(word).ok()
+}
+
+/// Removes the geresh, gershayim and quote marks placed between the letters of the words of a text,
+/// as in the abbreviations צה״ל or רמב״ם. Returns `None` when no gershayim is placed between letters.
+pub(crate) fn strip_abbreviation_marks(text: &str) -> Option<String> {
+    // Vowel points may surround the marks of vocalized words
+    let letters: Vec<(usize, char)> = text
+        .char_indices()
+        .filter(|&(_, c)| !crate::is_hebrew_vowel(c))
+        .collect();
+    let inner: Vec<usize> = letters
+        .windows(3)
+        .filter(|w| {
+            is_numeral_mark(w[1].1)
+                && regular_letter(w[0].1).is_some()
+                && regular_letter(w[2].1).is_some()
+        })
+        .map(|w| w[1].0)
+        .collect();
+    if !inner
+        .iter()
+        .any(|&i| !text[i..].starts_with(is_geresh_mark))
+    {
+        return None;
+    }
+
+    Some(
+        text.char_indices()
+            .filter(|(i, _)| !inner.contains(i))
+            .map(|(_, c)| c)
+            .collect(),
+    )
+}
+
+// Util function to parse a group of letters below 1000, ignoring numeral marks.
+fn parse_group(input: &str, group: &str) -> Result<u32, ParseNumeralError> {
+    let letters = group
+        .chars()
+        .filter(|&c| !is_numeral_mark(c) && !c.is_whitespace())
+        .map(|c| {
+            regular_letter(c).ok_or_else(|| ParseNumeralError::new(input, "not a Hebrew letter"))
+        })
+        .collect::<Result<String, _>>()?;
+    if letters.is_empty() {
+        return Err(ParseNumeralError::new(input, "no letters"));
+    }
+
+    let value: u32 = letters.chars().map(letter_value).sum();
+    if value >= 1000 {
+        return Err(ParseNumeralError::new(input, "letters are out of order"));
+    }
+    let standard = numeral_letters(value);
+    if letters != standard && letters != kosher_numeral(&standard) {
+        return Err(ParseNumeralError::new(input, "letters are out of order"));
+    }
+
+    Ok(value)
+}
+
+// Util function to get the regular form of a Hebrew letter, `None` for other characters.
+fn regular_letter(c: char) -> Option<char> {
+    match c {
+        'ך' => Some('כ'),
+        'ם' => Some('מ'),
+        'ן' => Some('נ'),
+        'ף' => Some('פ'),
+        'ץ' => Some('צ'),
+        'א'..='ת' => Some(c),
+        _ => None,
+    }
+}
+
+// Util function to get the value of a regular Hebrew letter.
+fn letter_value(c: char) -> u32 {
+    let find = |table: &[&str; 10]| table.iter().position(|l| l.chars().eq([c]));
+    find(&UNITS)
+        .or_else(|| find(&TENS).map(|i| i * 10))
+        .or_else(|| find(&HUNDREDS).map(|i| i * 100))
+        .unwrap_or_default() as u32
+}
+
+// Util function to write a number below 1000 as letters, with the traditional טו and טז.
+fn numeral_letters(number: u32) -> String {
+    let (hundreds, rest) = (number / 100, number % 100);
+    let rest = match rest {
+        15 => "טו".to_string(),
+        16 => "טז".to_string(),
+        _ => format!(
+            "{}{}",
+            TENS[(rest / 10) as usize],
+            UNITS[(rest % 10) as usize]
+        ),
+    };
+
+    format!("{}{}", HUNDREDS[hundreds as usize], rest)
+}
+
+// Util function to add a gershayim before the last letter, or a geresh after a single letter.
+fn mark_numeral(letters: &mut String) {
+    match letters.char_indices().last() {
+        Some((0, _)) => letters.push(GERESH),
+        Some((last, _)) => letters.insert(last, GERSHAYIM),
+        None => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_numeral() {
+        let issues = validate_numeral("תשמ\"ד");
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].sequence(), "שמד");
+        assert_eq!(issues[0].position(), 1);
+
+        let issues = validate_numeral("ריו");
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].position(), 1);
+        assert_eq!(issues[0].suggestion(), "טז");
+
+        assert!(validate_numeral("תשפ״ד").is_empty());
+        assert!(validate_numeral("ט״ו").is_empty());
+        // שד is only discouraged as the numeral 304
+        assert_eq!(validate_numeral("ש״ד")[0].suggestion(), "דש");
+        assert!(validate_numeral("תשד״מ").is_empty());
+    }
+
+    #[test]
+    fn test_kosher_numeral() {
+        assert_eq!(kosher_numeral("יו"), "טז");
+        assert_eq!(kosher_numeral("ה'"), "ה׳");
+        assert_eq!(kosher_numeral("תשמ\"ד"), "תשד״מ");
+        assert_eq!(kosher_numeral("רצ\"ח"), "רח״צ");
+        assert_eq!(kosher_numeral("תשד״מ"), "תשד״מ");
+
+        for number in 1..1000 {
+            let numeral = numeral_letters(number);
+            let kosher = kosher_numeral(&numeral);
+            assert!(validate_numeral(&kosher).is_empty(), "{}", kosher);
+            assert_eq!(kosher_numeral(&kosher), kosher, "{}", number);
+        }
+    }
+
+    #[test]
+    fn test_to_hebrew_numeral() {
+        assert_eq!(to_hebrew_numeral(0), "");
+        assert_eq!(to_hebrew_numeral(16), "ט״ז");
+        assert_eq!(to_hebrew_numeral(270), "ע״ר");
+        assert_eq!(to_hebrew_numeral(999), "תתקצ״ט");
+        assert_eq!(to_hebrew_numeral(1000), "א׳");
+        assert_eq!(to_hebrew_numeral(15015), "טו׳ט״ו");
+
+        let plain = NumeralOptions::new()
+            .with_punctuation(false)
+            .with_kosher_sequences(false);
+        assert_eq!(to_hebrew_numeral_with(5770, &plain), "התשע");
+        assert_eq!(to_hebrew_numeral_with(270, &plain), "רע");
+        assert_eq!(to_hebrew_numeral_with(115, &plain), "קטו");
+    }
+
+    #[test]
+    fn test_parse_hebrew_numeral() {
+        assert_eq!(parse_hebrew_numeral("ט״ו"), Ok(15));
+        assert_eq!(parse_hebrew_numeral("ע״ר"), Ok(270));
+        assert_eq!(parse_hebrew_numeral("תתקצ\u{201D}ט"), Ok(999));
+        assert_eq!(parse_hebrew_numeral("ה׳"), Ok(5));
+        assert_eq!(parse_hebrew_numeral("ה׳ תש״ם"), Ok(5740));
+        assert_eq!(
+            parse_hebrew_numeral("5784"),
+            Err(ParseNumeralError::new("5784", "not a Hebrew letter"))
+        );
+        assert!(parse_hebrew_numeral("התשפד").is_err());
+
+        let err = parse_hebrew_numeral("יה").unwrap_err();
+        assert_eq!(err.reason(), "letters are out of order");
+        assert!(parse_hebrew_numeral("׳״").is_err());
+        assert!(parse_hebrew_numeral("תתתת").is_err());
+        // Whole thousands are written as their units, e.g. א׳ for both 1 and 1000
+        for number in (1..20000).filter(|n| n % 1000 != 0) {
+            assert_eq!(parse_hebrew_numeral(&to_hebrew_numeral(number)), Ok(number));
+        }
+    }
+
+    #[test]
+    fn test_hebrew_year() {
+        assert_eq!(format_hebrew_year(5744), "תשד״מ");
+        assert_eq!(format_hebrew_year(5000), "ה׳ אלפים");
+        assert_eq!(format_hebrew_year(1000), "א׳ אלף");
+        assert_eq!(format_hebrew_year(6001), "ו׳א׳");
+        assert_eq!(parse_hebrew_year("תש\"ם"), Ok(5740));
+        assert!(parse_hebrew_year("5784").is_err());
+        for year in 5001..6000 {
+            assert_eq!(parse_hebrew_year(&format_hebrew_year(year)), Ok(year));
+        }
+        // Whole thousands are not read as the units of the sixth millennium
+        for year in [1000, 5000, 6000] {
+            assert_eq!(parse_hebrew_year(&format_hebrew_year(year)), Ok(year));
+        }
+        assert_eq!(parse_hebrew_year("ה׳"), Ok(5005));
+        assert!(parse_hebrew_year("אכ אלפים").is_err());
+    }
+
+    #[test]
+    fn test_strip_abbreviation_marks() {
+        assert_eq!(strip_abbreviation_marks("צה״ל"), Some("צהל".to_string()));
+        assert_eq!(
+            strip_abbreviation_marks("הרמב\"ם וחז״ל"),
+            Some("הרמבם וחזל".to_string())
+        );
+        assert_eq!(strip_abbreviation_marks("רַמְבַּ״ם"), Some("רַמְבַּם".to_string()));
+        // Quotes around words and a lone geresh are not abbreviation marks
+        assert_eq!(strip_abbreviation_marks("\"שלום\""), None);
+        assert_eq!(strip_abbreviation_marks("ג׳ירפה"), None);
+    }
+
+    #[test]
+    fn test_numerals_are_gematria_inverse() {
+        let gmctx = crate::GematriaContext::default();
+        for number in 1..1000 {
+            let numeral = to_hebrew_numeral(number);
+            assert_eq!(
+                gmctx.calculate_value(&numeral).value(),
+                number as u64,
+                "{}",
+                numeral
+            );
+        }
+    }
+}
@@ -0,0 +1,80 @@
+// Paleo-Hebrew letters (the Phoenician block, U+10900 to U+1091F), as found in transcribed inscriptions.
+
+use std::borrow::Cow;
+
+use crate::HEBREW_LETTERS;
+
+/// The first and last Paleo-Hebrew letters, alf (𐤀) and taw (𐤕), in the order of the Hebrew alphabet.
+const PALEO_ALF: u32 = 0x10900;
+const PALEO_TAW: u32 = 0x10915;
+
+// Util function to get the Hebrew letter of a Paleo-Hebrew letter.
+fn paleo_letter(c: char) -> Option<char> {
+    match c as u32 {
+        index @ PALEO_ALF..=PALEO_TAW => Some(HEBREW_LETTERS[(index - PALEO_ALF) as usize]),
+        _ => None,
+    }
+}
+
+/// Checks whether a character is a Paleo-Hebrew letter.
+pub(crate) fn is_paleo_letter(c: char) -> bool {
+    paleo_letter(c).is_some()
+}
+
+/// Replaces the Paleo-Hebrew letters of a text (U+10900 to U+10915) with their Hebrew letters,
+/// borrowing the text when it has none.
+///
+/// The Paleo-Hebrew script has no final forms, so its letters map to the regular letters (indices 1 to 22).
+/// The word separator (𐤟) separates words, and the number signs (U+10916 to U+1091B) are not letters.
+///
+/// Contexts apply this mapping on every text before calculation, so inscriptions transcribed
+/// in Paleo-Hebrew are valued as their letters.
+///
+/// # Examples
+///
+/// ```
+/// use gematria_rs::{transcribe_paleo_hebrew, GematriaContext};
+///
+/// assert_eq!(transcribe_paleo_hebrew("𐤔𐤋𐤌"), "שלמ");
+///
+/// let gmctx = GematriaContext::default();
+/// assert_eq!(gmctx.calculate_value("𐤃𐤅𐤃").value(), 14);
+/// ```
+pub fn transcribe_paleo_hebrew(text: &str) -> Cow<'_, str> {
+    if !text.chars().any(is_paleo_letter) {
+        return Cow::Borrowed(text);
+    }
+
+    Cow::Owned(text.chars().map(|c| paleo_letter(c).unwrap_or(c)).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{GematriaBuilder, GematriaContext, GematriaMethod};
+
+    #[test]
+    fn test_paleo_letters_are_valued() {
+        let gmctx = GematriaContext::default();
+        let values: Vec<u64> = ('\u{10900}'..='\u{10915}')
+            .map(|c| gmctx.calculate_value(&c.to_string()).value())
+            .collect();
+        let expected: Vec<u64> = HEBREW_LETTERS[..22]
+            .iter()
+            .map(|&c| gmctx.calculate_char_value(c))
+            .collect();
+        assert_eq!(values, expected);
+        // Number signs are not letters
+        assert_eq!(gmctx.calculate_value("\u{10916}").value(), 0);
+
+        // Inscriptions are split on the word separator, and have no final forms
+        let gmctx = GematriaBuilder::new()
+            .with_method(GematriaMethod::MisparGadol)
+            .init_gematria();
+        let text = "𐤌𐤋𐤊𐤟𐤉𐤔𐤓𐤀𐤋";
+        let matches = gmctx.search_matching_values(&90, text);
+        assert_eq!(matches.len(), 1);
+        assert_eq!(&text[matches[0].byte_range()], "𐤌𐤋𐤊");
+        assert_eq!(gmctx.search_matching_values(&541, text).len(), 1);
+    }
+}
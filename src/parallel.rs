@@ -0,0 +1,65 @@
+// Data parallelism over the words of a text, with rayon under the `parallel` feature.
+
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+/// Maps every item, in parallel with the `parallel` feature, keeping the order of the items.
+#[cfg(feature = "parallel")]
+pub(crate) fn map<T, R, F>(items: &[T], f: F) -> Vec<R>
+where
+    T: Sync,
+    R: Send,
+    F: Fn(&T) -> R + Sync + Send,
+{
+    items.par_iter().map(f).collect()
+}
+
+/// Maps every item, in parallel with the `parallel` feature, keeping the order of the items.
+#[cfg(not(feature = "parallel"))]
+pub(crate) fn map<T, R, F>(items: &[T], f: F) -> Vec<R>
+where
+    F: Fn(&T) -> R,
+{
+    items.iter().map(f).collect()
+}
+
+/// Maps and filters every item, in parallel with the `parallel` feature, keeping the order of the items.
+#[cfg(feature = "parallel")]
+pub(crate) fn filter_map<T, R, F>(items: &[T], f: F) -> Vec<R>
+where
+    T: Sync,
+    R: Send,
+    F: Fn(&T) -> Option<R> + Sync + Send,
+{
+    items.par_iter().filter_map(f).collect()
+}
+
+/// Maps and filters every item, in parallel with the `parallel` feature, keeping the order of the items.
+#[cfg(not(feature = "parallel"))]
+pub(crate) fn filter_map<T, R, F>(items: &[T], f: F) -> Vec<R>
+where
+    F: Fn(&T) -> Option<R>,
+{
+    items.iter().filter_map(f).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{CancellationToken, GematriaContext, NoProgress};
+
+    #[test]
+    fn test_results_follow_the_text_order() {
+        let gmctx = GematriaContext::default();
+        let text = "נכנס יין יצא סוד שלום לשום אב ג ".repeat(200);
+
+        let sequential: Vec<_> = gmctx.search_matching_values_iter(&70, &text).collect();
+        assert_eq!(gmctx.search_matching_values(&70, &text), sequential);
+        assert_eq!(sequential.len(), 400);
+        assert_eq!(
+            gmctx.group_words(&text).unwrap(),
+            gmctx
+                .group_words_with_progress(&text, &mut NoProgress, &CancellationToken::new())
+                .unwrap()
+        );
+    }
+}
@@ -0,0 +1,249 @@
+// Weekly Torah portions (parashot) and calculations scoped to a portion.
+
+use crate::{tanakh::join_verses, Error, GematriaContext, Match, Tanakh, Verse, VerseRef};
+
+/// `Parasha` is a weekly Torah portion, a range of verses within a single book.
+///
+/// Verse numbers follow the Hebrew numbering (e.g. Vayikra ends at Leviticus 5:26).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Parasha {
+    // The transliterated name of the portion.
+    name: &'static str,
+
+    // The book of the portion.
+    book: &'static str,
+
+    // The chapter and verse opening the portion.
+    start: (u32, u32),
+
+    // The chapter and verse closing the portion (inclusive).
+    end: (u32, u32),
+}
+
+impl Parasha {
+    const fn new(
+        name: &'static str,
+        book: &'static str,
+        start: (u32, u32),
+        end: (u32, u32),
+    ) -> Self {
+        Parasha {
+            name,
+            book,
+            start,
+            end,
+        }
+    }
+
+    /// Gets the transliterated name of the portion.
+    pub fn name(&self) -> &str {
+        self.name
+    }
+
+    /// Gets the book of the portion.
+    pub fn book(&self) -> &str {
+        self.book
+    }
+
+    /// Gets the reference of the verse opening the portion.
+    pub fn start(&self) -> VerseRef {
+        VerseRef::new(self.book, self.start.0, self.start.1)
+    }
+
+    /// Gets the reference of the verse closing the portion.
+    pub fn end(&self) -> VerseRef {
+        VerseRef::new(self.book, self.end.0, self.end.1)
+    }
+
+    /// Checks whether a verse belongs to the portion.
+    pub fn contains(&self, reference: &VerseRef) -> bool {
+        let position = (reference.chapter(), reference.verse());
+        reference.book() == self.book && self.start <= position && position <= self.end
+    }
+}
+
+/// The 54 weekly Torah portions, in order.
+pub const PARASHOT: [Parasha; 54] = [
+    Parasha::new("Bereshit", "Genesis", (1, 1), (6, 8)),
+    Parasha::new("Noach", "Genesis", (6, 9), (11, 32)),
+    Parasha::new("Lech-Lecha", "Genesis", (12, 1), (17, 27)),
+    Parasha::new("Vayera", "Genesis", (18, 1), (22, 24)),
+    Parasha::new("Chayei Sara", "Genesis", (23, 1), (25, 18)),
+    Parasha::new("Toldot", "Genesis", (25, 19), (28, 9)),
+    Parasha::new("Vayetzei", "Genesis", (28, 10), (32, 3)),
+    Parasha::new("Vayishlach", "Genesis", (32, 4), (36, 43)),
+    Parasha::new("Vayeshev", "Genesis", (37, 1), (40, 23)),
+    Parasha::new("Miketz", "Genesis", (41, 1), (44, 17)),
+    Parasha::new("Vayigash", "Genesis", (44, 18), (47, 27)),
+    Parasha::new("Vayechi", "Genesis", (47, 28), (50, 26)),
+    Parasha::new("Shemot", "Exodus", (1, 1), (6, 1)),
+    Parasha::new("Vaera", "Exodus", (6, 2), (9, 35)),
+    Parasha::new("Bo", "Exodus", (10, 1), (13, 16)),
+    Parasha::new("Beshalach", "Exodus", (13, 17), (17, 16)),
+    Parasha::new("Yitro", "Exodus", (18, 1), (20, 23)),
+    Parasha::new("Mishpatim", "Exodus", (21, 1), (24, 18)),
+    Parasha::new("Terumah", "Exodus", (25, 1), (27, 19)),
+    Parasha::new("Tetzaveh", "Exodus", (27, 20), (30, 10)),
+    Parasha::new("Ki Tisa", "Exodus", (30, 11), (34, 35)),
+    Parasha::new("Vayakhel", "Exodus", (35, 1), (38, 20)),
+    Parasha::new("Pekudei", "Exodus", (38, 21), (40, 38)),
+    Parasha::new("Vayikra", "Leviticus", (1, 1), (5, 26)),
+    Parasha::new("Tzav", "Leviticus", (6, 1), (8, 36)),
+    Parasha::new("Shmini", "Leviticus", (9, 1), (11, 47)),
+    Parasha::new("Tazria", "Leviticus", (12, 1), (13, 59)),
+    Parasha::new("Metzora", "Leviticus", (14, 1), (15, 33)),
+    Parasha::new("Achrei Mot", "Leviticus", (16, 1), (18, 30)),
+    Parasha::new("Kedoshim", "Leviticus", (19, 1), (20, 27)),
+    Parasha::new("Emor", "Leviticus", (21, 1), (24, 23)),
+    Parasha::new("Behar", "Leviticus", (25, 1), (26, 2)),
+    Parasha::new("Bechukotai", "Leviticus", (26, 3), (27, 34)),
+    Parasha::new("Bamidbar", "Numbers", (1, 1), (4, 20)),
+    Parasha::new("Nasso", "Numbers", (4, 21), (7, 89)),
+    Parasha::new("Beha'alotcha", "Numbers", (8, 1), (12, 16)),
+    Parasha::new("Sh'lach", "Numbers", (13, 1), (15, 41)),
+    Parasha::new("Korach", "Numbers", (16, 1), (18, 32)),
+    Parasha::new("Chukat", "Numbers", (19, 1), (22, 1)),
+    Parasha::new("Balak", "Numbers", (22, 2), (25, 9)),
+    Parasha::new("Pinchas", "Numbers", (25, 10), (30, 1)),
+    Parasha::new("Matot", "Numbers", (30, 2), (32, 42)),
+    Parasha::new("Masei", "Numbers", (33, 1), (36, 13)),
+    Parasha::new("Devarim", "Deuteronomy", (1, 1), (3, 22)),
+    Parasha::new("Vaetchanan", "Deuteronomy", (3, 23), (7, 11)),
+    Parasha::new("Eikev", "Deuteronomy", (7, 12), (11, 25)),
+    Parasha::new("Re'eh", "Deuteronomy", (11, 26), (16, 17)),
+    Parasha::new("Shoftim", "Deuteronomy", (16, 18), (21, 9)),
+    Parasha::new("Ki Teitzei", "Deuteronomy", (21, 10), (25, 19)),
+    Parasha::new("Ki Tavo", "Deuteronomy", (26, 1), (29, 8)),
+    Parasha::new("Nitzavim", "Deuteronomy", (29, 9), (30, 20)),
+    Parasha::new("Vayeilech", "Deuteronomy", (31, 1), (31, 30)),
+    Parasha::new("Ha'azinu", "Deuteronomy", (32, 1), (32, 52)),
+    Parasha::new("Vezot Haberakhah", "Deuteronomy", (33, 1), (34, 12)),
+];
+
+/// Finds a portion by name, ignoring case, spaces and punctuation (e.g. "lech lecha" or "Haazinu").
+///
+/// # Examples
+///
+/// ```
+/// use gematria_rs::{find_parasha, VerseRef};
+///
+/// let parasha = find_parasha("lech lecha").unwrap();
+/// assert_eq!(parasha.start(), VerseRef::new("Genesis", 12, 1));
+/// assert!(find_parasha("Shabbat").is_none());
+/// ```
+pub fn find_parasha(name: &str) -> Option<&'static Parasha> {
+    let key = normalize_name(name);
+    PARASHOT.iter().find(|p| normalize_name(p.name) == key)
+}
+
+/// Finds the portion a verse belongs to.
+pub fn parasha_of(reference: &VerseRef) -> Option<&'static Parasha> {
+    PARASHOT.iter().find(|p| p.contains(reference))
+}
+
+// Util function to normalize a portion name, keeping lowercase alphanumerics only.
+fn normalize_name(name: &str) -> String {
+    name.chars()
+        .filter(|c| c.is_alphanumeric())
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+impl Tanakh {
+    /// Gets the verses of a portion.
+    pub fn parasha_verses<'a>(
+        &'a self,
+        parasha: &'a Parasha,
+    ) -> impl Iterator<Item = &'a Verse> + 'a {
+        self.verses()
+            .iter()
+            .filter(move |v| parasha.contains(v.reference()))
+    }
+
+    /// Calculates the total of a portion, `None` if none of its verses are loaded.
+    pub fn parasha_total(&self, gmctx: &GematriaContext, parasha: &Parasha) -> Option<u64> {
+        self.parasha_verses(parasha)
+            .map(|v| gmctx.calculate_value(v.text()).value())
+            .reduce(|a, b| a + b)
+    }
+
+    /// Groups the words of a portion by their value, as [`GematriaContext::group_words`] does.
+    pub fn parasha_groups(
+        &self,
+        gmctx: &GematriaContext,
+        parasha: &Parasha,
+    ) -> Result<Vec<(u64, Vec<String>)>, Error> {
+        gmctx.group_words(&join_verses(self.parasha_verses(parasha)))
+    }
+
+    /// Searches the verses of a portion for words with the given value, with the reference of every match.
+    pub fn parasha_search_matching_values(
+        &self,
+        gmctx: &GematriaContext,
+        parasha: &Parasha,
+        value: &u64,
+    ) -> Vec<(VerseRef, Match)> {
+        self.parasha_verses(parasha)
+            .flat_map(|v| {
+                gmctx
+                    .search_matching_values(value, v.text())
+                    .into_iter()
+                    .map(move |m| (v.reference().clone(), m))
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parashot_are_contiguous() {
+        for pair in PARASHOT.windows(2) {
+            let (previous, next) = (&pair[0], &pair[1]);
+            if previous.book == next.book {
+                assert!(
+                    previous.end < next.start,
+                    "{} overlaps {}",
+                    previous.name,
+                    next.name
+                );
+            } else {
+                assert_eq!(next.start, (1, 1), "{} opens its book", next.name);
+            }
+        }
+        assert_eq!(
+            parasha_of(&VerseRef::new("Numbers", 30, 1)).unwrap().name(),
+            "Pinchas"
+        );
+        assert_eq!(parasha_of(&VerseRef::new("Joshua", 1, 1)), None);
+    }
+
+    #[test]
+    fn test_parasha_scoped_calculations() {
+        let tanakh = Tanakh::parse(
+            "Genesis\t6\t8\tיין סוד\n\
+             Genesis\t6\t9\tסוד\n\
+             Genesis\t11\t32\tיין\n\
+             Genesis\t12\t1\tיין\n",
+        )
+        .unwrap();
+        let gmctx = GematriaContext::default();
+        let noach = find_parasha("Noach").unwrap();
+
+        assert_eq!(tanakh.parasha_verses(noach).count(), 2);
+        assert_eq!(tanakh.parasha_total(&gmctx, noach), Some(140));
+        assert_eq!(
+            tanakh.parasha_groups(&gmctx, noach).unwrap(),
+            vec![(70, vec!["סוד".to_string(), "יין".to_string()])]
+        );
+        let hits = tanakh.parasha_search_matching_values(&gmctx, noach, &70);
+        let references: Vec<String> = hits.iter().map(|(r, _)| r.to_string()).collect();
+        assert_eq!(references, ["Genesis 6:9", "Genesis 11:32"]);
+        assert_eq!(
+            tanakh.parasha_total(&gmctx, find_parasha("Vayikra").unwrap()),
+            None
+        );
+    }
+}
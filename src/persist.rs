@@ -0,0 +1,165 @@
+// Defines the versioned on-disk header shared by every persisted gematria index.
+
+use std::io::{self, Read, Write};
+
+/// Magic bytes opening every persisted index.
+pub const INDEX_MAGIC: [u8; 4] = *b"GMIX";
+
+/// The current on-disk layout version written by this crate.
+pub const INDEX_FORMAT_VERSION: u16 = 3;
+
+/// The oldest on-disk layout version this crate is able to read.
+pub const MIN_SUPPORTED_INDEX_FORMAT_VERSION: u16 = 3;
+
+/// `IndexHeader` is the fixed, versioned header written in front of persisted indexes.
+///
+/// The layout is stable and always little endian:
+///
+/// | bytes | content                                  |
+/// |-------|------------------------------------------|
+/// | 4     | magic bytes `GMIX`                       |
+/// | 2     | format version (`u16`)                   |
+/// | 1     | length `n` of the writer crate version   |
+/// | n     | writer crate version (UTF-8)             |
+///
+/// Reading a header checks the magic bytes and refuses layouts newer than
+/// [`INDEX_FORMAT_VERSION`] or older than [`MIN_SUPPORTED_INDEX_FORMAT_VERSION`],
+/// so an index is never silently misread by another crate version.
+///
+/// # Examples
+///
+/// ```
+/// use gematria_rs::IndexHeader;
+///
+/// let mut bytes = Vec::new();
+/// IndexHeader::current().write_to(&mut bytes)?;
+///
+/// let header = IndexHeader::read_from(&mut bytes.as_slice())?;
+/// assert_eq!(header, IndexHeader::current());
+/// # Ok::<(), std::io::Error>(())
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IndexHeader {
+    // The on-disk layout version.
+    format_version: u16,
+
+    // The version of the crate which wrote the index.
+    crate_version: String,
+}
+
+impl IndexHeader {
+    /// Creates the header describing the layout written by this crate version.
+    pub fn current() -> Self {
+        Self {
+            format_version: INDEX_FORMAT_VERSION,
+            crate_version: env!("CARGO_PKG_VERSION").to_string(),
+        }
+    }
+
+    /// Gets the on-disk layout version.
+    pub fn format_version(&self) -> u16 {
+        self.format_version
+    }
+
+    /// Gets the version of the crate which wrote the index.
+    pub fn crate_version(&self) -> &str {
+        &self.crate_version
+    }
+
+    /// Writes the header to the given writer.
+    pub fn write_to<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        let crate_version = self.crate_version.as_bytes();
+        let len = u8::try_from(crate_version.len()).map_err(|_| {
+            io::Error::new(io::ErrorKind::InvalidInput, "crate version is too long")
+        })?;
+
+        writer.write_all(&INDEX_MAGIC)?;
+        writer.write_all(&self.format_version.to_le_bytes())?;
+        writer.write_all(&[len])?;
+        writer.write_all(crate_version)
+    }
+
+    /// Reads and validates a header from the given reader.
+    pub fn read_from<R: Read>(reader: &mut R) -> io::Result<Self> {
+        let mut magic = [0u8; 4];
+        reader.read_exact(&mut magic)?;
+        if magic != INDEX_MAGIC {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "not a gematria index (bad magic bytes)",
+            ));
+        }
+
+        let mut version = [0u8; 2];
+        reader.read_exact(&mut version)?;
+        let format_version = u16::from_le_bytes(version);
+
+        let mut len = [0u8; 1];
+        reader.read_exact(&mut len)?;
+        let mut crate_version = vec![0u8; len[0] as usize];
+        reader.read_exact(&mut crate_version)?;
+        let crate_version = String::from_utf8(crate_version)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+
+        if format_version > INDEX_FORMAT_VERSION {
+            return Err(io::Error::new(
+                io::ErrorKind::Unsupported,
+                format!(
+                    "index format v{} (written by gematria_rs {}) is newer than the supported v{}",
+                    format_version, crate_version, INDEX_FORMAT_VERSION
+                ),
+            ));
+        }
+        if format_version < MIN_SUPPORTED_INDEX_FORMAT_VERSION {
+            return Err(io::Error::new(
+                io::ErrorKind::Unsupported,
+                format!(
+                    "index format v{} (written by gematria_rs {}) is no longer supported",
+                    format_version, crate_version
+                ),
+            ));
+        }
+
+        Ok(Self {
+            format_version,
+            crate_version,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_header_layout() {
+        let mut bytes = Vec::new();
+        IndexHeader::current().write_to(&mut bytes).unwrap();
+
+        let version = env!("CARGO_PKG_VERSION");
+        assert_eq!(&bytes[..4], b"GMIX");
+        assert_eq!(&bytes[4..6], &INDEX_FORMAT_VERSION.to_le_bytes());
+        assert_eq!(bytes[6] as usize, version.len());
+        assert_eq!(&bytes[7..], version.as_bytes());
+    }
+
+    #[test]
+    fn test_header_rejects_newer_format() {
+        let mut bytes = Vec::new();
+        IndexHeader {
+            format_version: INDEX_FORMAT_VERSION + 1,
+            crate_version: "99.0.0".to_string(),
+        }
+        .write_to(&mut bytes)
+        .unwrap();
+
+        let err = IndexHeader::read_from(&mut bytes.as_slice()).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::Unsupported);
+    }
+
+    #[test]
+    fn test_header_rejects_bad_magic() {
+        let err = IndexHeader::read_from(&mut &b"JUNK\x01\x00\x00"[..]).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    }
+}
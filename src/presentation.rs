@@ -0,0 +1,123 @@
+// Hebrew presentation forms (U+FB1D to U+FB4F), as found in text copied from PDFs.
+
+use std::borrow::Cow;
+
+/// The Hebrew presentation forms and their letters and points.
+///
+/// Wide and alternative letters map to their plain letter, letters with points are decomposed
+/// into the letter followed by its points, and ligatures map to their letters.
+const PRESENTATION_FORMS: [(char, &str); 44] = [
+    ('\u{FB1D}', "י\u{05B4}"),
+    ('\u{FB1F}', "יי\u{05B7}"),
+    ('\u{FB20}', "ע"),
+    ('\u{FB21}', "א"),
+    ('\u{FB22}', "ד"),
+    ('\u{FB23}', "ה"),
+    ('\u{FB24}', "כ"),
+    ('\u{FB25}', "ל"),
+    ('\u{FB26}', "ם"),
+    ('\u{FB27}', "ר"),
+    ('\u{FB28}', "ת"),
+    ('\u{FB2A}', "ש\u{05C1}"),
+    ('\u{FB2B}', "ש\u{05C2}"),
+    ('\u{FB2C}', "ש\u{05BC}\u{05C1}"),
+    ('\u{FB2D}', "ש\u{05BC}\u{05C2}"),
+    ('\u{FB2E}', "א\u{05B7}"),
+    ('\u{FB2F}', "א\u{05B8}"),
+    ('\u{FB30}', "א\u{05BC}"),
+    ('\u{FB31}', "ב\u{05BC}"),
+    ('\u{FB32}', "ג\u{05BC}"),
+    ('\u{FB33}', "ד\u{05BC}"),
+    ('\u{FB34}', "ה\u{05BC}"),
+    ('\u{FB35}', "ו\u{05BC}"),
+    ('\u{FB36}', "ז\u{05BC}"),
+    ('\u{FB38}', "ט\u{05BC}"),
+    ('\u{FB39}', "י\u{05BC}"),
+    ('\u{FB3A}', "ך\u{05BC}"),
+    ('\u{FB3B}', "כ\u{05BC}"),
+    ('\u{FB3C}', "ל\u{05BC}"),
+    ('\u{FB3E}', "מ\u{05BC}"),
+    ('\u{FB40}', "נ\u{05BC}"),
+    ('\u{FB41}', "ס\u{05BC}"),
+    ('\u{FB43}', "ף\u{05BC}"),
+    ('\u{FB44}', "פ\u{05BC}"),
+    ('\u{FB46}', "צ\u{05BC}"),
+    ('\u{FB47}', "ק\u{05BC}"),
+    ('\u{FB48}', "ר\u{05BC}"),
+    ('\u{FB49}', "ש\u{05BC}"),
+    ('\u{FB4A}', "ת\u{05BC}"),
+    ('\u{FB4B}', "ו\u{05B9}"),
+    ('\u{FB4C}', "ב\u{05BF}"),
+    ('\u{FB4D}', "כ\u{05BF}"),
+    ('\u{FB4E}', "פ\u{05BF}"),
+    ('\u{FB4F}', "אל"),
+];
+
+// Util function to get the letters and points of a presentation form.
+fn presentation_form(c: char) -> Option<&'static str> {
+    PRESENTATION_FORMS
+        .iter()
+        .find(|(form, _)| *form == c)
+        .map(|(_, letters)| *letters)
+}
+
+/// Checks whether a character is a Hebrew presentation form of letters.
+pub(crate) fn is_presentation_form(c: char) -> bool {
+    matches!(c, '\u{FB1D}'..='\u{FB4F}') && presentation_form(c).is_some()
+}
+
+/// Replaces the Hebrew presentation forms of a text (U+FB1D to U+FB4F) with their letters and points,
+/// borrowing the text when it has none.
+///
+/// Contexts apply this mapping on every text before calculation, so presentation forms are valued
+/// as their letters instead of being skipped as unknown characters.
+///
+/// # Examples
+///
+/// ```
+/// use gematria_rs::{decompose_presentation_forms, GematriaContext};
+///
+/// assert_eq!(decompose_presentation_forms("\u{FB2A}לום"), "ש\u{05C1}לום");
+/// assert_eq!(decompose_presentation_forms("\u{FB4F}"), "אל");
+///
+/// let gmctx = GematriaContext::default();
+/// assert_eq!(gmctx.calculate_value("\u{FB2A}לו\u{FB26}").value(), 376);
+/// ```
+pub fn decompose_presentation_forms(text: &str) -> Cow<'_, str> {
+    if !text.chars().any(is_presentation_form) {
+        return Cow::Borrowed(text);
+    }
+
+    let mut decomposed = String::with_capacity(text.len());
+    for c in text.chars() {
+        match presentation_form(c) {
+            Some(letters) => decomposed.push_str(letters),
+            None => decomposed.push(c),
+        }
+    }
+    Cow::Owned(decomposed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{GematriaBuilder, GematriaContext};
+
+    #[test]
+    fn test_presentation_forms_are_valued() {
+        let gmctx = GematriaContext::default();
+        // Every form of letters is valued, the varika, the plus sign and unassigned code points are not
+        for c in '\u{FB1D}'..='\u{FB4F}' {
+            let value = gmctx.calculate_value(&c.to_string()).value();
+            assert_eq!(value > 0, is_presentation_form(c), "{:X}", c as u32);
+        }
+
+        // Wide letters, as justified in PDFs
+        assert_eq!(
+            gmctx.calculate_value("\u{FB21}\u{FB25}\u{FB26}").value(),
+            71
+        );
+        let vowels = GematriaBuilder::new().with_vowels(true).init_gematria();
+        assert_eq!(vowels.calculate_value("\u{FB31}").word(), "ב\u{05BC}");
+    }
+}
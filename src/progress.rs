@@ -0,0 +1,72 @@
+// Progress reporting and cancellation primitives for long running operations.
+
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    Arc,
+};
+
+/// A trait for receiving progress updates from long running operations.
+///
+/// Any `FnMut(usize, usize)` closure can be used as a `ProgressSink`, receiving the
+/// number of processed units and the total units of the operation. A total of 0 stands for
+/// an operation of unknown size, such as reading a stream.
+pub trait ProgressSink {
+    /// Called every time the operation advances, with the processed and total amount of work.
+    fn on_progress(&mut self, processed: usize, total: usize);
+}
+
+impl<F> ProgressSink for F
+where
+    F: FnMut(usize, usize),
+{
+    fn on_progress(&mut self, processed: usize, total: usize) {
+        self(processed, total)
+    }
+}
+
+/// A [`ProgressSink`] that ignores every update.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NoProgress;
+
+impl ProgressSink for NoProgress {
+    fn on_progress(&mut self, _processed: usize, _total: usize) {}
+}
+
+/// `CancellationToken` is a cheap, cloneable flag used to abort long running operations.
+///
+/// Clones share the same underlying flag, so a token can be handed to an operation
+/// and cancelled from another thread (e.g. when a client disconnects).
+///
+/// # Examples
+///
+/// ```
+/// use gematria_rs::CancellationToken;
+///
+/// let token = CancellationToken::new();
+/// let handle = token.clone();
+/// assert!(!token.is_cancelled());
+///
+/// handle.cancel();
+/// assert!(token.is_cancelled());
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct CancellationToken {
+    cancelled: Arc<AtomicBool>,
+}
+
+impl CancellationToken {
+    /// Creates a new, non cancelled token.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Requests cancellation of every operation observing this token.
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
+
+    /// Whether cancellation was requested.
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Relaxed)
+    }
+}
@@ -0,0 +1,137 @@
+// Built-in reference values, used to sanity-check the calculation methods.
+
+use crate::{GematriaBuilder, GematriaMethod, GematriaValue};
+
+/// `ReferenceValue` is a known gematria value of a word, using a given method with default settings.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ReferenceValue {
+    // The calculation method.
+    method: GematriaMethod,
+
+    // The reference word.
+    word: &'static str,
+
+    // The expected value.
+    value: GematriaValue,
+}
+
+impl ReferenceValue {
+    const fn new(method: GematriaMethod, word: &'static str, value: GematriaValue) -> Self {
+        ReferenceValue {
+            method,
+            word,
+            value,
+        }
+    }
+
+    /// Gets the calculation method.
+    pub fn method(&self) -> GematriaMethod {
+        self.method
+    }
+
+    /// Gets the reference word.
+    pub fn word(&self) -> &'static str {
+        self.word
+    }
+
+    /// Gets the expected value.
+    pub fn value(&self) -> GematriaValue {
+        self.value
+    }
+}
+
+/// The reference values of every built-in method.
+pub const REFERENCE_VALUES: [ReferenceValue; 24] = [
+    ReferenceValue::new(GematriaMethod::MisparHechrechi, "שלום", 376),
+    ReferenceValue::new(GematriaMethod::MisparHechrechi, "יהוה", 26),
+    ReferenceValue::new(GematriaMethod::MisparGadol, "שלום", 936),
+    ReferenceValue::new(GematriaMethod::MisparKatan, "שלום", 18),
+    ReferenceValue::new(GematriaMethod::OtiyotBeMilui, "א", 111),
+    ReferenceValue::new(GematriaMethod::OtiyotBeMilui, "יהוה", 54),
+    ReferenceValue::new(GematriaMethod::Achbi, "א", 20),
+    ReferenceValue::new(GematriaMethod::Avgad, "אבג", 9),
+    ReferenceValue::new(GematriaMethod::AyakBachar, "א", 10),
+    ReferenceValue::new(GematriaMethod::MisparKidmi, "ג", 6),
+    ReferenceValue::new(GematriaMethod::MisparPerati, "אב", 5),
+    ReferenceValue::new(GematriaMethod::MisparHaMerubahHaKlali, "אב", 9),
+    ReferenceValue::new(GematriaMethod::MisparKatanMispari, "שלום", 9),
+    ReferenceValue::new(GematriaMethod::MisparNeelam, "א", 110),
+    ReferenceValue::new(GematriaMethod::MiluiHaMilui, "ה", 117),
+    ReferenceValue::new(GematriaMethod::AchasBeta, "שלום", 347),
+    ReferenceValue::new(GematriaMethod::MisparHaAkhor, "שלום", 538),
+    ReferenceValue::new(GematriaMethod::Nikud, "אָ", 16),
+    ReferenceValue::new(GematriaMethod::Nikud, "שָׁלוֹם", 26),
+    ReferenceValue::new(GematriaMethod::Isopsephy, "λόγος", 373),
+    ReferenceValue::new(GematriaMethod::Isopsephy, "Ἰησοῦς", 888),
+    ReferenceValue::new(GematriaMethod::EnglishOrdinal, "gematria", 74),
+    ReferenceValue::new(GematriaMethod::EnglishQabalah, "gematria", 118),
+    ReferenceValue::new(GematriaMethod::Agrippa, "gematria", 233),
+];
+
+/// `ReferenceMismatch` describes a reference value which was not reproduced.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ReferenceMismatch {
+    // The reference value.
+    reference: ReferenceValue,
+
+    // The value actually calculated.
+    actual: GematriaValue,
+}
+
+impl ReferenceMismatch {
+    /// Gets the reference value.
+    pub fn reference(&self) -> &ReferenceValue {
+        &self.reference
+    }
+
+    /// Gets the value actually calculated.
+    pub fn actual(&self) -> GematriaValue {
+        self.actual
+    }
+}
+
+/// Recomputes all the [`REFERENCE_VALUES`], returning the ones which do not match.
+///
+/// # Examples
+///
+/// ```
+/// use gematria_rs::verify_reference_values;
+///
+/// assert!(verify_reference_values().is_empty());
+/// ```
+pub fn verify_reference_values() -> Vec<ReferenceMismatch> {
+    verify_reference_values_with(&GematriaBuilder::new())
+}
+
+/// Recomputes all the [`REFERENCE_VALUES`] with the settings of a builder (e.g. a system loaded from a file),
+/// under the method of every reference, returning the ones which do not match.
+///
+/// # Examples
+///
+/// ```
+/// use gematria_rs::{verify_reference_values_with, GematriaBuilder, Kolel, REFERENCE_VALUES};
+///
+/// let builder = GematriaBuilder::new().with_kolel(Kolel::One);
+/// assert_eq!(verify_reference_values_with(&builder).len(), REFERENCE_VALUES.len());
+/// ```
+pub fn verify_reference_values_with(builder: &GematriaBuilder) -> Vec<ReferenceMismatch> {
+    REFERENCE_VALUES
+        .iter()
+        .filter_map(|reference| {
+            let actual = builder
+                .clone()
+                .with_method(reference.method)
+                .init_gematria()
+                .calculate_value(reference.word)
+                .value();
+            if actual == reference.value {
+                None
+            } else {
+                Some(ReferenceMismatch {
+                    reference: *reference,
+                    actual,
+                })
+            }
+        })
+        .collect()
+}
@@ -0,0 +1,420 @@
+// Output renderers used to display gematria results in various formats.
+
+use crate::{ConcordanceLine, GematriaResult, Match};
+
+/// A single value displayed by an [`OutputRenderer`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Cell {
+    /// A textual value.
+    Text(String),
+    /// A numeric value.
+    Number(u64),
+    /// A list of textual values.
+    List(Vec<String>),
+}
+
+/// Enumerates the built-in output formats.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum OutputFormat {
+    #[default]
+    Plain,
+    Table,
+    Json,
+    Csv,
+    Markdown,
+    Html,
+}
+
+/// A trait defining how results are displayed.
+///
+/// Implementors only need to render a table of rows, results are converted into rows
+/// by the provided methods, which may be overridden for a format specific display.
+/// Every rendered output ends with a new line, unless there is nothing to display.
+///
+/// # Examples
+///
+/// ```
+/// use gematria_rs::{GematriaContext, OutputFormat};
+///
+/// let gmctx = GematriaContext::default();
+/// let renderer = OutputFormat::Csv.renderer(false);
+/// let output = renderer.render_value(&gmctx.calculate_value("שלום"));
+///
+/// assert_eq!(output, "word,value,method\nשלום,376,MisparHechrechi\n");
+/// ```
+pub trait OutputRenderer {
+    /// Renders rows of cells under the given column headers.
+    fn render_table(&self, headers: &[&str], rows: &[Vec<Cell>]) -> String;
+
+    /// Renders the result of a single calculation.
+    fn render_value(&self, result: &GematriaResult) -> String {
+        self.render_table(
+            &["word", "value", "method"],
+            &[vec![
+                Cell::Text(result.word().to_string()),
+                Cell::Number(result.value()),
+                Cell::Text(format!("{:?}", result.method())),
+            ]],
+        )
+    }
+
+    /// Renders the matches of a search.
+    fn render_matches(&self, matches: &[Match]) -> String {
+        let rows: Vec<Vec<Cell>> = matches
+            .iter()
+            .map(|m| {
+                vec![
+                    Cell::Text(m.word().to_string()),
+                    Cell::Number(m.value()),
+                    Cell::Number(m.index() as u64),
+                ]
+            })
+            .collect();
+        self.render_table(&["word", "value", "index"], &rows)
+    }
+
+    /// Renders words grouped by their gematria value.
+    fn render_groups(&self, groups: &[(u64, Vec<String>)]) -> String {
+        let rows: Vec<Vec<Cell>> = groups
+            .iter()
+            .map(|(value, words)| vec![Cell::Number(*value), Cell::List(words.clone())])
+            .collect();
+        self.render_table(&["value", "words"], &rows)
+    }
+
+    /// Renders the lines of a concordance.
+    fn render_concordance(&self, lines: &[ConcordanceLine]) -> String {
+        let rows: Vec<Vec<Cell>> = lines
+            .iter()
+            .map(|line| {
+                vec![
+                    Cell::Text(line.before().to_string()),
+                    Cell::Text(line.matched().word().to_string()),
+                    Cell::Text(line.after().to_string()),
+                    Cell::Number(line.matched().index() as u64),
+                ]
+            })
+            .collect();
+        self.render_table(&["before", "word", "after", "index"], &rows)
+    }
+}
+
+impl OutputFormat {
+    /// Creates the renderer of this format, `verbose` only affects the plain format.
+    pub fn renderer(&self, verbose: bool) -> Box<dyn OutputRenderer> {
+        match self {
+            OutputFormat::Plain => Box::new(PlainRenderer::new(verbose)),
+            OutputFormat::Table => Box::new(TableRenderer),
+            OutputFormat::Json => Box::new(JsonRenderer),
+            OutputFormat::Csv => Box::new(CsvRenderer),
+            OutputFormat::Markdown => Box::new(MarkdownRenderer),
+            OutputFormat::Html => Box::new(HtmlRenderer),
+        }
+    }
+}
+
+impl Cell {
+    // Util function to display a cell as plain text.
+    fn text(&self) -> String {
+        match self {
+            Cell::Text(t) => t.clone(),
+            Cell::Number(n) => n.to_string(),
+            Cell::List(l) => l.join(", "),
+        }
+    }
+}
+
+/// Renders results as plain text lines, the default output of the CLI.
+#[derive(Debug, Clone, Default)]
+pub struct PlainRenderer {
+    verbose: bool,
+}
+
+impl PlainRenderer {
+    pub fn new(verbose: bool) -> Self {
+        Self { verbose }
+    }
+}
+
+impl OutputRenderer for PlainRenderer {
+    fn render_table(&self, _headers: &[&str], rows: &[Vec<Cell>]) -> String {
+        rows.iter()
+            .map(|row| {
+                let cells: Vec<String> = row.iter().map(Cell::text).collect();
+                format!("{}\n", cells.join(" "))
+            })
+            .collect()
+    }
+
+    fn render_value(&self, result: &GematriaResult) -> String {
+        if self.verbose {
+            format!(
+                "Gematria value for '{}': {}\n",
+                result.word(),
+                result.value()
+            )
+        } else {
+            format!("{}\n", result.value())
+        }
+    }
+
+    fn render_matches(&self, matches: &[Match]) -> String {
+        matches.iter().map(|m| format!("{}\n", m.word())).collect()
+    }
+
+    fn render_concordance(&self, lines: &[ConcordanceLine]) -> String {
+        lines
+            .iter()
+            .map(|line| {
+                let kwic = format!(
+                    "{} [{}] {}",
+                    line.before(),
+                    line.matched().word(),
+                    line.after()
+                );
+                if self.verbose {
+                    format!("{:4}: {}\n", line.matched().index(), kwic.trim())
+                } else {
+                    format!("{}\n", kwic.trim())
+                }
+            })
+            .collect()
+    }
+
+    fn render_groups(&self, groups: &[(u64, Vec<String>)]) -> String {
+        groups
+            .iter()
+            .map(|(value, words)| {
+                if self.verbose {
+                    format!("Gematria value {:4}: {}\n", value, words.join(", "))
+                } else {
+                    format!("{:4} -> {}\n", value, words.join(", "))
+                }
+            })
+            .collect()
+    }
+}
+
+/// Renders results as an aligned text table.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TableRenderer;
+
+impl OutputRenderer for TableRenderer {
+    fn render_table(&self, headers: &[&str], rows: &[Vec<Cell>]) -> String {
+        let rows: Vec<Vec<String>> = rows
+            .iter()
+            .map(|row| row.iter().map(Cell::text).collect())
+            .collect();
+        let widths: Vec<usize> = headers
+            .iter()
+            .enumerate()
+            .map(|(i, h)| {
+                rows.iter()
+                    .map(|row| row[i].chars().count())
+                    .chain(std::iter::once(h.chars().count()))
+                    .max()
+                    .unwrap_or_default()
+            })
+            .collect();
+
+        let line = |cells: Vec<&str>| -> String {
+            let padded: Vec<String> = cells
+                .iter()
+                .zip(&widths)
+                .map(|(c, w)| format!("{}{}", c, " ".repeat(w - c.chars().count())))
+                .collect();
+            format!("{}\n", padded.join(" | ").trim_end())
+        };
+
+        let mut output = line(headers.to_vec());
+        let separator: Vec<String> = widths.iter().map(|w| "-".repeat(*w)).collect();
+        output.push_str(&format!("{}\n", separator.join("-+-")));
+        for row in &rows {
+            output.push_str(&line(row.iter().map(String::as_str).collect()));
+        }
+
+        output
+    }
+}
+
+/// Renders results as a JSON array of objects.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct JsonRenderer;
+
+impl JsonRenderer {
+    fn escape(text: &str) -> String {
+        let mut escaped = String::with_capacity(text.len() + 2);
+        escaped.push('"');
+        for c in text.chars() {
+            match c {
+                '"' => escaped.push_str("\\\""),
+                '\\' => escaped.push_str("\\\\"),
+                '\n' => escaped.push_str("\\n"),
+                '\r' => escaped.push_str("\\r"),
+                '\t' => escaped.push_str("\\t"),
+                c if (c as u32) < 0x20 => escaped.push_str(&format!("\\u{:04x}", c as u32)),
+                c => escaped.push(c),
+            }
+        }
+        escaped.push('"');
+        escaped
+    }
+
+    fn cell(cell: &Cell) -> String {
+        match cell {
+            Cell::Text(t) => Self::escape(t),
+            Cell::Number(n) => n.to_string(),
+            Cell::List(l) => {
+                let items: Vec<String> = l.iter().map(|i| Self::escape(i)).collect();
+                format!("[{}]", items.join(","))
+            }
+        }
+    }
+}
+
+impl OutputRenderer for JsonRenderer {
+    fn render_table(&self, headers: &[&str], rows: &[Vec<Cell>]) -> String {
+        let objects: Vec<String> = rows
+            .iter()
+            .map(|row| {
+                let fields: Vec<String> = headers
+                    .iter()
+                    .zip(row)
+                    .map(|(h, c)| format!("{}:{}", Self::escape(h), Self::cell(c)))
+                    .collect();
+                format!("{{{}}}", fields.join(","))
+            })
+            .collect();
+        format!("[{}]\n", objects.join(","))
+    }
+}
+
+/// Renders results as comma separated values, with a header line.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct CsvRenderer;
+
+impl CsvRenderer {
+    fn escape(text: &str) -> String {
+        if text.contains([',', '"', '\n', '\r']) {
+            format!("\"{}\"", text.replace('"', "\"\""))
+        } else {
+            text.to_string()
+        }
+    }
+}
+
+impl OutputRenderer for CsvRenderer {
+    fn render_table(&self, headers: &[&str], rows: &[Vec<Cell>]) -> String {
+        let mut output = format!("{}\n", headers.join(","));
+        for row in rows {
+            let cells: Vec<String> = row
+                .iter()
+                .map(|c| match c {
+                    Cell::List(l) => Self::escape(&l.join(" ")),
+                    c => Self::escape(&c.text()),
+                })
+                .collect();
+            output.push_str(&format!("{}\n", cells.join(",")));
+        }
+
+        output
+    }
+}
+
+/// Renders results as a Markdown table.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct MarkdownRenderer;
+
+impl OutputRenderer for MarkdownRenderer {
+    fn render_table(&self, headers: &[&str], rows: &[Vec<Cell>]) -> String {
+        let mut output = format!("| {} |\n", headers.join(" | "));
+        output.push_str(&format!("|{}\n", "---|".repeat(headers.len())));
+        for row in rows {
+            let cells: Vec<String> = row.iter().map(|c| c.text().replace('|', "\\|")).collect();
+            output.push_str(&format!("| {} |\n", cells.join(" | ")));
+        }
+
+        output
+    }
+}
+
+/// Renders results as an HTML table.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct HtmlRenderer;
+
+impl HtmlRenderer {
+    fn escape(text: &str) -> String {
+        text.replace('&', "&amp;")
+            .replace('<', "&lt;")
+            .replace('>', "&gt;")
+            .replace('"', "&quot;")
+    }
+}
+
+impl OutputRenderer for HtmlRenderer {
+    fn render_table(&self, headers: &[&str], rows: &[Vec<Cell>]) -> String {
+        let mut output = String::from("<table>\n<thead><tr>");
+        for header in headers {
+            output.push_str(&format!("<th>{}</th>", Self::escape(header)));
+        }
+        output.push_str("</tr></thead>\n<tbody>\n");
+        for row in rows {
+            output.push_str("<tr>");
+            for cell in row {
+                output.push_str(&format!("<td>{}</td>", Self::escape(&cell.text())));
+            }
+            output.push_str("</tr>\n");
+        }
+        output.push_str("</tbody>\n</table>\n");
+
+        output
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn groups() -> Vec<(u64, Vec<String>)> {
+        vec![(70, vec!["יין".to_string(), "סוד".to_string()])]
+    }
+
+    #[test]
+    fn test_plain_groups() {
+        assert_eq!(
+            PlainRenderer::new(false).render_groups(&groups()),
+            "  70 -> יין, סוד\n"
+        );
+        assert_eq!(PlainRenderer::new(false).render_matches(&[]), "");
+    }
+
+    #[test]
+    fn test_json_groups() {
+        assert_eq!(
+            JsonRenderer.render_groups(&groups()),
+            "[{\"value\":70,\"words\":[\"יין\",\"סוד\"]}]\n"
+        );
+        assert_eq!(JsonRenderer::escape("a\"b"), "\"a\\\"b\"");
+    }
+
+    #[test]
+    fn test_table_matches() {
+        let matches = vec![Match::new("סוד".to_string(), 70, 3)];
+        assert_eq!(
+            TableRenderer.render_matches(&matches),
+            "word | value | index\n-----+-------+------\nסוד  | 70    | 3\n"
+        );
+    }
+
+    #[test]
+    fn test_markdown_and_html_groups() {
+        assert_eq!(
+            MarkdownRenderer.render_groups(&groups()),
+            "| value | words |\n|---|---|\n| 70 | יין, סוד |\n"
+        );
+        assert_eq!(
+            HtmlRenderer.render_groups(&groups()),
+            "<table>\n<thead><tr><th>value</th><th>words</th></tr></thead>\n<tbody>\n<tr><td>70</td><td>יין, סוד</td></tr>\n</tbody>\n</table>\n"
+        );
+    }
+}
@@ -0,0 +1,230 @@
+// Client fetching Hebrew texts by reference from the Sefaria API.
+
+use std::{io, time::Duration};
+
+use serde_json::Value;
+
+/// The default base URL of the Sefaria API.
+pub const SEFARIA_API_URL: &str = "https://www.sefaria.org/api";
+
+/// `SefariaText` is a Hebrew text fetched from Sefaria, cleaned from markup and split into verses.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct SefariaText {
+    // The normalized reference of the text, as returned by Sefaria.
+    reference: String,
+
+    // The cleaned text of every verse (or segment).
+    verses: Vec<String>,
+}
+
+impl SefariaText {
+    /// Parses a response of the Sefaria texts API (v3), using the first returned version.
+    ///
+    /// HTML tags, entities and bracketed section marks (such as `{פ}`) are removed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gematria_rs::SefariaText;
+    ///
+    /// let text = SefariaText::from_json(
+    ///     r#"{"ref": "Genesis 1:1", "versions": [{"text": "בְּרֵאשִׁ֖ית בָּרָ֣א<br>אֱלֹהִ֑ים&nbsp;{פ}"}]}"#,
+    /// )?;
+    ///
+    /// assert_eq!(text.reference(), "Genesis 1:1");
+    /// assert_eq!(text.verses(), ["בְּרֵאשִׁ֖ית בָּרָ֣א אֱלֹהִ֑ים"]);
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    pub fn from_json(content: &str) -> io::Result<Self> {
+        let response: Value = serde_json::from_str(content)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        let text = response
+            .get("versions")
+            .and_then(|versions| versions.get(0))
+            .and_then(|version| version.get("text"))
+            .ok_or_else(|| {
+                let message = response
+                    .get("error")
+                    .and_then(Value::as_str)
+                    .unwrap_or("response without any text version");
+                io::Error::new(io::ErrorKind::InvalidData, message.to_string())
+            })?;
+
+        let mut verses = Vec::new();
+        collect_verses(text, &mut verses);
+        Ok(SefariaText {
+            reference: response
+                .get("ref")
+                .and_then(Value::as_str)
+                .unwrap_or_default()
+                .to_string(),
+            verses,
+        })
+    }
+
+    /// Gets the normalized reference of the text.
+    pub fn reference(&self) -> &str {
+        &self.reference
+    }
+
+    /// Gets the cleaned text of every verse.
+    pub fn verses(&self) -> &[String] {
+        &self.verses
+    }
+
+    /// Gets the whole text, one verse per line, ready for a [`GematriaContext`](crate::GematriaContext).
+    pub fn text(&self) -> String {
+        self.verses.join("\n")
+    }
+}
+
+/// `SefariaClient` fetches Hebrew texts by reference (e.g. `"Genesis 1:1-10"`) from the Sefaria API.
+///
+/// # Examples
+///
+/// ```no_run
+/// use gematria_rs::{GematriaContext, SefariaClient};
+///
+/// let client = SefariaClient::new();
+/// let text = client.fetch("Genesis 1:1-10")?;
+///
+/// let gmctx = GematriaContext::default();
+/// for (index, result) in gmctx.calculate_verses(&text.text()) {
+///     println!("{}: {}", index + 1, result.value());
+/// }
+/// # Ok::<(), std::io::Error>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct SefariaClient {
+    // The HTTP agent.
+    agent: ureq::Agent,
+
+    // The base URL of the API.
+    base_url: String,
+}
+
+impl Default for SefariaClient {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SefariaClient {
+    /// Creates a client of the public Sefaria API, with a 30 seconds timeout.
+    pub fn new() -> Self {
+        SefariaClient {
+            agent: ureq::AgentBuilder::new()
+                .timeout(Duration::from_secs(30))
+                .build(),
+            base_url: SEFARIA_API_URL.to_string(),
+        }
+    }
+
+    /// Sets the base URL of the API, e.g. for a mirror.
+    pub fn with_base_url(mut self, base_url: &str) -> Self {
+        self.base_url = base_url.trim_end_matches('/').to_string();
+        self
+    }
+
+    /// Fetches the Hebrew text of a reference.
+    pub fn fetch(&self, reference: &str) -> io::Result<SefariaText> {
+        let url = self.text_url(reference);
+        let response = self.agent.get(&url).call().map_err(|err| match err {
+            ureq::Error::Status(code, _) => io::Error::other(format!(
+                "Sefaria request for '{}' failed with status {}",
+                reference, code
+            )),
+            err => io::Error::other(err),
+        })?;
+
+        SefariaText::from_json(&response.into_string()?)
+    }
+
+    fn text_url(&self, reference: &str) -> String {
+        format!(
+            "{}/v3/texts/{}?version=hebrew&return_format=text_only",
+            self.base_url,
+            encode_path(reference)
+        )
+    }
+}
+
+// Util function to flatten the (possibly nested) verses of a text, cleaning every verse.
+fn collect_verses(text: &Value, verses: &mut Vec<String>) {
+    match text {
+        Value::String(verse) => {
+            let verse = clean_verse(verse);
+            if !verse.is_empty() {
+                verses.push(verse);
+            }
+        }
+        Value::Array(items) => items.iter().for_each(|item| collect_verses(item, verses)),
+        _ => {}
+    }
+}
+
+// Util function to remove HTML tags, entities and bracketed marks, and collapse whitespace.
+fn clean_verse(verse: &str) -> String {
+    let mut cleaned = String::with_capacity(verse.len());
+    let mut closing = None;
+    for c in verse.chars() {
+        match closing {
+            Some(end) => {
+                if c == end {
+                    closing = None;
+                    cleaned.push(' ');
+                }
+            }
+            None => match c {
+                '<' => closing = Some('>'),
+                '&' => closing = Some(';'),
+                '{' => closing = Some('}'),
+                c => cleaned.push(c),
+            },
+        }
+    }
+
+    cleaned.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+// Util function to percent encode a reference as a URL path segment.
+fn encode_path(segment: &str) -> String {
+    segment
+        .bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' | b':' | b',' => {
+                (b as char).to_string()
+            }
+            b => format!("%{:02X}", b),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_nested_verses() {
+        let text = SefariaText::from_json(
+            r#"{"ref": "Genesis 1:31-2:1", "versions": [{"text": [["<b>וַיַּ֤רְא</b>"], ["וַיְכֻלּ֛וּ", ""]]}]}"#,
+        )
+        .unwrap();
+
+        assert_eq!(text.verses(), ["וַיַּ֤רְא", "וַיְכֻלּ֛וּ"]);
+        assert_eq!(text.text(), "וַיַּ֤רְא\nוַיְכֻלּ֛וּ");
+
+        let err = SefariaText::from_json(r#"{"error": "Unknown reference"}"#).unwrap_err();
+        assert_eq!(err.to_string(), "Unknown reference");
+    }
+
+    #[test]
+    fn test_text_url() {
+        let client = SefariaClient::new().with_base_url("http://localhost/api/");
+
+        assert_eq!(
+            client.text_url("Genesis 1:1-10"),
+            "http://localhost/api/v3/texts/Genesis%201:1-10?version=hebrew&return_format=text_only"
+        );
+    }
+}
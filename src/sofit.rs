@@ -0,0 +1,174 @@
+// Policies for the values of the final letters (sofit), overriding the defaults of the methods.
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use std::sync::Arc;
+
+use crate::{AlphabetSystem, GematriaCalculation, GematriaMethod};
+
+/// Enumerates how the final forms (ך, ם, ן, ף, ץ) are valued, see [`crate::GematriaBuilder::with_sofit_policy`].
+///
+/// Traditions differ on whether the final forms count as their regular letters or as 500 to 900,
+/// a policy overrides the default of any method.
+///
+/// # Examples
+///
+/// ```
+/// use gematria_rs::{GematriaBuilder, GematriaMethod, SofitPolicy};
+///
+/// let gmctx = GematriaBuilder::new()
+///     .with_sofit_policy(SofitPolicy::Extended)
+///     .init_gematria();
+/// assert_eq!(gmctx.calculate_value("שלום").value(), 936);
+///
+/// let gmctx = GematriaBuilder::new()
+///     .with_method(GematriaMethod::MisparGadol)
+///     .with_sofit_policy(SofitPolicy::Regular)
+///     .init_gematria();
+/// assert_eq!(gmctx.calculate_value("שלום").value(), 376);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum SofitPolicy {
+    /// The final forms are valued as defined by the method.
+    #[default]
+    Method,
+    /// The final forms are valued as their regular letters (ך as כ).
+    Regular,
+    /// The final forms count as 500 to 900, as in [`GematriaMethod::MisparGadol`],
+    /// for the methods deriving from the standard values. Other methods (such as the ciphers) keep their values.
+    Extended,
+}
+
+/// A strategy valuing the final forms under a [`SofitPolicy`], other letters are valued by the wrapped strategy.
+pub(crate) struct SofitStrategy {
+    // The wrapped strategy.
+    strategy: Box<dyn GematriaCalculation>,
+
+    // The policy applied on the final forms.
+    policy: SofitPolicy,
+
+    // The alphabet defining the final forms.
+    alphabet: Arc<dyn AlphabetSystem>,
+}
+
+impl SofitStrategy {
+    /// Wraps a strategy under a policy on the final forms of an alphabet,
+    /// returning it as is under [`SofitPolicy::Method`].
+    pub(crate) fn wrap(
+        strategy: Box<dyn GematriaCalculation>,
+        policy: SofitPolicy,
+        alphabet: &Arc<dyn AlphabetSystem>,
+    ) -> Box<dyn GematriaCalculation> {
+        match policy {
+            SofitPolicy::Method => strategy,
+            policy => Box::new(SofitStrategy {
+                strategy,
+                policy,
+                alphabet: Arc::clone(alphabet),
+            }),
+        }
+    }
+}
+
+impl GematriaCalculation for SofitStrategy {
+    fn calculate_value(&self, letter_index: u32) -> u32 {
+        let Some(base) = self.alphabet.final_form_base(letter_index) else {
+            return self.strategy.calculate_value(letter_index);
+        };
+        match self.policy {
+            SofitPolicy::Method => self.strategy.calculate_value(letter_index),
+            SofitPolicy::Regular => self.strategy.calculate_value(base),
+            SofitPolicy::Extended => self.strategy.calculate_extended_value(letter_index),
+        }
+    }
+
+    fn method_type(&self) -> GematriaMethod {
+        self.strategy.method_type()
+    }
+
+    fn calculate_positional_value(&self, letter_index: u32, position: usize) -> u64 {
+        let Some(base) = self.alphabet.final_form_base(letter_index) else {
+            return self
+                .strategy
+                .calculate_positional_value(letter_index, position);
+        };
+        match self.policy {
+            SofitPolicy::Method => self
+                .strategy
+                .calculate_positional_value(letter_index, position),
+            SofitPolicy::Regular => self.strategy.calculate_positional_value(base, position),
+            SofitPolicy::Extended => {
+                let value = self.strategy.calculate_value(letter_index) as u64;
+                let positional = self
+                    .strategy
+                    .calculate_positional_value(letter_index, position);
+                // Positional methods scale the value of the letter, so the extended value is scaled alike
+                match value {
+                    0 => positional,
+                    value => positional / value * self.calculate_value(letter_index) as u64,
+                }
+            }
+        }
+    }
+
+    fn calculate_symbol_value(&self, symbol: char) -> Option<u32> {
+        self.strategy.calculate_symbol_value(symbol)
+    }
+
+    fn finalize_total(&self, total: u64) -> u64 {
+        self.strategy.finalize_total(total)
+    }
+
+    fn checked_finalize_total(&self, total: u64) -> Option<u64> {
+        self.strategy.checked_finalize_total(total)
+    }
+
+    fn calculate_extended_value(&self, letter_index: u32) -> u32 {
+        self.strategy.calculate_extended_value(letter_index)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::GematriaBuilder;
+
+    #[test]
+    fn test_policies_compose_with_methods() {
+        let value = |method, policy| {
+            GematriaBuilder::new()
+                .with_method(method)
+                .with_sofit_policy(policy)
+                .init_gematria()
+                .calculate_value("מלך")
+                .value()
+        };
+
+        assert_eq!(
+            value(GematriaMethod::MisparHechrechi, SofitPolicy::Method),
+            90
+        );
+        assert_eq!(
+            value(GematriaMethod::MisparHechrechi, SofitPolicy::Extended),
+            570
+        );
+        assert_eq!(value(GematriaMethod::MisparGadol, SofitPolicy::Regular), 90);
+        assert_eq!(value(GematriaMethod::MisparKatan, SofitPolicy::Regular), 9);
+        assert_eq!(
+            value(GematriaMethod::MisparPerati, SofitPolicy::Extended),
+            252_500
+        );
+        // ך is third: 40 + 30 * 2 + 500 * 3
+        assert_eq!(
+            value(GematriaMethod::MisparHaAkhor, SofitPolicy::Extended),
+            1600
+        );
+        // Ciphers keep their values under the extended policy
+        assert_eq!(
+            value(GematriaMethod::Achbi, SofitPolicy::Extended),
+            value(GematriaMethod::Achbi, SofitPolicy::Method)
+        );
+    }
+}
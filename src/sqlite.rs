@@ -0,0 +1,568 @@
+// SQLite storage of corpus indexes, for corpora too large for memory or shared between processes.
+
+use std::{
+    io::{self, BufRead},
+    path::Path,
+};
+
+use rusqlite::{params, Connection, OpenFlags, OptionalExtension};
+
+use crate::{
+    CancellationToken, GematriaContext, GematriaError, GematriaMethod, GematriaValue, Match,
+    NoProgress, ProgressSink,
+};
+
+/// The schema version of SQLite indexes, stored as the `user_version` of the database.
+pub const SQLITE_SCHEMA_VERSION: u32 = 3;
+
+// Creates the tables of an index, dropping any previous content.
+// Values are stored as big endian blobs, as SQLite integers are signed and blobs compare in numeric order.
+const SCHEMA: &str = "
+DROP TABLE IF EXISTS word_values;
+DROP TABLE IF EXISTS tokens;
+DROP TABLE IF EXISTS spellings;
+DROP TABLE IF EXISTS words;
+CREATE TABLE words (
+    id INTEGER PRIMARY KEY,
+    word TEXT NOT NULL UNIQUE
+);
+CREATE TABLE spellings (
+    id INTEGER PRIMARY KEY,
+    spelling TEXT NOT NULL UNIQUE,
+    word_id INTEGER NOT NULL REFERENCES words(id)
+);
+CREATE INDEX spellings_word ON spellings(word_id);
+CREATE TABLE tokens (
+    position INTEGER PRIMARY KEY,
+    spelling_id INTEGER NOT NULL REFERENCES spellings(id),
+    start INTEGER NOT NULL,
+    end INTEGER NOT NULL
+);
+CREATE INDEX tokens_spelling ON tokens(spelling_id);
+CREATE TABLE word_values (
+    method TEXT NOT NULL,
+    spelling_id INTEGER NOT NULL REFERENCES spellings(id),
+    value BLOB NOT NULL,
+    PRIMARY KEY (method, spelling_id)
+);
+CREATE INDEX word_values_value ON word_values(method, value);
+PRAGMA user_version = 3;
+";
+
+/// `SqliteIndex` is a corpus index stored in a SQLite database, answering the same queries
+/// as [`GematriaIndex`](crate::GematriaIndex) without holding the corpus in memory.
+///
+/// An index is written once with [`GematriaContext::index_into_sqlite`], streaming the corpus,
+/// and can then be opened read only by many processes at once (e.g. the CLI and a web server).
+///
+/// # Examples
+///
+/// ```
+/// use gematria_rs::{GematriaContext, GematriaMethod, SqliteIndex};
+///
+/// let gmctx = GematriaContext::default();
+/// let mut index = SqliteIndex::create_in_memory()?;
+/// gmctx.index_into_sqlite(
+///     "נכנס יין\nיצא סוד".as_bytes(),
+///     &[GematriaMethod::MisparHechrechi],
+///     &mut index,
+/// )?;
+///
+/// assert_eq!(index.words_with_value(GematriaMethod::MisparHechrechi, 70)?, ["יין", "סוד"]);
+/// assert_eq!(index.positions("סוד")?, [3]);
+/// # Ok::<(), std::io::Error>(())
+/// ```
+pub struct SqliteIndex {
+    // The connection to the database.
+    conn: Connection,
+}
+
+impl SqliteIndex {
+    /// Creates an empty index in the database at the given path, dropping any previous index.
+    pub fn create<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        Self::init(Connection::open(path).map_err(sqlite_error)?)
+    }
+
+    /// Creates an empty index in an in-memory database.
+    pub fn create_in_memory() -> io::Result<Self> {
+        Self::init(Connection::open_in_memory().map_err(sqlite_error)?)
+    }
+
+    /// Opens an existing index read only, so it can be shared by concurrent processes.
+    ///
+    /// Fails with [`io::ErrorKind::Unsupported`] for an index of another schema version.
+    pub fn open<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        let conn = Connection::open_with_flags(
+            path,
+            OpenFlags::SQLITE_OPEN_READ_ONLY | OpenFlags::SQLITE_OPEN_NO_MUTEX,
+        )
+        .map_err(sqlite_error)?;
+        let version: u32 = conn
+            .query_row("PRAGMA user_version", [], |row| row.get(0))
+            .map_err(sqlite_error)?;
+        if version != SQLITE_SCHEMA_VERSION {
+            return Err(io::Error::new(
+                io::ErrorKind::Unsupported,
+                format!(
+                    "index schema v{} is not the supported v{}",
+                    version, SQLITE_SCHEMA_VERSION
+                ),
+            ));
+        }
+
+        Ok(Self { conn })
+    }
+
+    fn init(conn: Connection) -> io::Result<Self> {
+        conn.execute_batch(SCHEMA).map_err(sqlite_error)?;
+        Ok(Self { conn })
+    }
+
+    /// Gets the number of words (tokens) of the corpus.
+    pub fn token_count(&self) -> io::Result<usize> {
+        self.conn
+            .query_row("SELECT COUNT(*) FROM tokens", [], |row| row.get(0))
+            .map_err(sqlite_error)
+    }
+
+    /// Gets the value of an indexed word, `None` if the word or the method were not indexed.
+    ///
+    /// The value is the value of the first spelling of the word, see [`GematriaIndex::value_of`](crate::GematriaIndex::value_of).
+    pub fn value_of(
+        &self,
+        method: GematriaMethod,
+        word: &str,
+    ) -> io::Result<Option<GematriaValue>> {
+        self.conn
+            .query_row(
+                "SELECT v.value FROM word_values v
+                 JOIN spellings s ON s.id = v.spelling_id
+                 JOIN words w ON w.id = s.word_id
+                 WHERE v.method = ?1 AND w.word = ?2 ORDER BY s.id LIMIT 1",
+                params![method_key(method), word],
+                |row| row.get(0).map(GematriaValue::from_be_bytes),
+            )
+            .optional()
+            .map_err(sqlite_error)
+    }
+
+    /// Gets the token indices of an indexed word within the corpus.
+    pub fn positions(&self, word: &str) -> io::Result<Vec<usize>> {
+        let mut stmt = self
+            .conn
+            .prepare_cached(
+                "SELECT t.position FROM tokens t
+                 JOIN spellings s ON s.id = t.spelling_id
+                 JOIN words w ON w.id = s.word_id
+                 WHERE w.word = ?1 ORDER BY t.position",
+            )
+            .map_err(sqlite_error)?;
+        let rows = stmt
+            .query_map(params![word], |row| row.get(0))
+            .map_err(sqlite_error)?;
+        rows.collect::<Result<_, _>>().map_err(sqlite_error)
+    }
+
+    /// Gets the distinct words with the given value, in order of first appearance.
+    pub fn words_with_value(
+        &self,
+        method: GematriaMethod,
+        value: GematriaValue,
+    ) -> io::Result<Vec<String>> {
+        let mut stmt = self
+            .conn
+            .prepare_cached(
+                "SELECT w.word FROM word_values v
+                 JOIN spellings s ON s.id = v.spelling_id
+                 JOIN words w ON w.id = s.word_id
+                 WHERE v.method = ?1 AND v.value = ?2
+                 GROUP BY w.id ORDER BY MIN(s.id)",
+            )
+            .map_err(sqlite_error)?;
+        let rows = stmt
+            .query_map(params![method_key(method), value.to_be_bytes()], |row| {
+                row.get(0)
+            })
+            .map_err(sqlite_error)?;
+        rows.collect::<Result<_, _>>().map_err(sqlite_error)
+    }
+
+    /// Gets every occurrence of the words with the given value, in order of appearance in the corpus.
+    pub fn search_matching_values(
+        &self,
+        method: GematriaMethod,
+        value: GematriaValue,
+    ) -> io::Result<Vec<Match>> {
+        let mut stmt = self
+            .conn
+            .prepare_cached(
+                "SELECT w.word, t.position, t.start, t.end FROM word_values v
+                 JOIN spellings s ON s.id = v.spelling_id
+                 JOIN words w ON w.id = s.word_id
+                 JOIN tokens t ON t.spelling_id = v.spelling_id
+                 WHERE v.method = ?1 AND v.value = ?2 ORDER BY t.position",
+            )
+            .map_err(sqlite_error)?;
+        let rows = stmt
+            .query_map(params![method_key(method), value.to_be_bytes()], |row| {
+                Ok(Match::new(row.get(0)?, value, row.get(1)?)
+                    .with_byte_range(row.get(2)?..row.get(3)?))
+            })
+            .map_err(sqlite_error)?;
+        rows.collect::<Result<_, _>>().map_err(sqlite_error)
+    }
+
+    /// Groups the distinct words with matching values, as [`GematriaContext::group_words`] does.
+    pub fn group_words(
+        &self,
+        method: GematriaMethod,
+    ) -> io::Result<Vec<(GematriaValue, Vec<String>)>> {
+        let mut stmt = self
+            .conn
+            .prepare_cached(
+                "SELECT v.value, w.word FROM word_values v
+                 JOIN spellings s ON s.id = v.spelling_id
+                 JOIN words w ON w.id = s.word_id
+                 WHERE v.method = ?1 AND v.value IN (
+                     SELECT v.value FROM word_values v JOIN spellings s ON s.id = v.spelling_id
+                     WHERE v.method = ?1 GROUP BY v.value HAVING COUNT(DISTINCT s.word_id) > 1
+                 )
+                 GROUP BY v.value, w.id ORDER BY v.value, MIN(s.id)",
+            )
+            .map_err(sqlite_error)?;
+        let rows = stmt
+            .query_map(params![method_key(method)], |row| {
+                Ok((
+                    GematriaValue::from_be_bytes(row.get(0)?),
+                    row.get::<_, String>(1)?,
+                ))
+            })
+            .map_err(sqlite_error)?;
+
+        let mut groups: Vec<(GematriaValue, Vec<String>)> = Vec::new();
+        for row in rows {
+            let (value, word) = row.map_err(sqlite_error)?;
+            match groups.last_mut() {
+                Some((last, words)) if *last == value => words.push(word),
+                _ => groups.push((value, vec![word])),
+            }
+        }
+        // The sort is stable and groups are ordered by value
+        groups.sort_by_key(|(_, words)| std::cmp::Reverse(words.len()));
+        Ok(groups)
+    }
+}
+
+impl GematriaContext {
+    /// Streams a corpus line by line into a [`SqliteIndex`] under the given methods,
+    /// with memory bounded by the longest line.
+    ///
+    /// Any previous content of the index is replaced, in a single transaction.
+    /// Words are indexed and valued as [`GematriaContext::build_index`] does.
+    pub fn index_into_sqlite<R: BufRead>(
+        &self,
+        reader: R,
+        methods: &[GematriaMethod],
+        index: &mut SqliteIndex,
+    ) -> io::Result<()> {
+        self.index_into_sqlite_with_progress(
+            reader,
+            methods,
+            index,
+            &mut NoProgress,
+            &CancellationToken::new(),
+        )
+    }
+
+    /// Same as [`GematriaContext::index_into_sqlite`], reporting progress (in lines, of an unknown total)
+    /// to a [`ProgressSink`] and aborting with an [`io::ErrorKind::Interrupted`] error once the
+    /// [`CancellationToken`] is cancelled, in which case the previous content of the index is kept.
+    pub fn index_into_sqlite_with_progress<R: BufRead>(
+        &self,
+        mut reader: R,
+        methods: &[GematriaMethod],
+        index: &mut SqliteIndex,
+        progress: &mut dyn ProgressSink,
+        cancel: &CancellationToken,
+    ) -> io::Result<()> {
+        let strategies = self.method_strategies(methods)?;
+        let tx = index.conn.transaction().map_err(sqlite_error)?;
+        tx.execute_batch(
+            "DELETE FROM word_values; DELETE FROM tokens; DELETE FROM spellings; DELETE FROM words;",
+        )
+        .map_err(sqlite_error)?;
+        {
+            let mut find_spelling = tx
+                .prepare("SELECT id FROM spellings WHERE spelling = ?1")
+                .map_err(sqlite_error)?;
+            let mut insert_spelling = tx
+                .prepare("INSERT INTO spellings (spelling, word_id) VALUES (?1, ?2)")
+                .map_err(sqlite_error)?;
+            let mut find_word = tx
+                .prepare("SELECT id FROM words WHERE word = ?1")
+                .map_err(sqlite_error)?;
+            let mut insert_word = tx
+                .prepare("INSERT INTO words (word) VALUES (?1)")
+                .map_err(sqlite_error)?;
+            let mut insert_value = tx
+                .prepare("INSERT INTO word_values (method, spelling_id, value) VALUES (?1, ?2, ?3)")
+                .map_err(sqlite_error)?;
+            let mut insert_token = tx
+                .prepare(
+                    "INSERT INTO tokens (position, spelling_id, start, end) VALUES (?1, ?2, ?3, ?4)",
+                )
+                .map_err(sqlite_error)?;
+
+            let mut line = String::new();
+            let mut offset = 0;
+            let mut position: usize = 0;
+            let mut lines = 0;
+            loop {
+                // Dropping the transaction rolls it back
+                if cancel.is_cancelled() {
+                    return Err(GematriaError::Cancelled.into());
+                }
+                line.clear();
+                let read = reader.read_line(&mut line)?;
+                if read == 0 {
+                    break;
+                }
+
+                for (range, raw) in self.tokenizer.tokenize_with_ranges(&line) {
+                    let normalized = self.normalize(raw);
+                    let found: Option<i64> = find_spelling
+                        .query_row(params![normalized], |row| row.get(0))
+                        .optional()
+                        .map_err(sqlite_error)?;
+                    let id = match found {
+                        Some(id) => id,
+                        None => {
+                            let (_, abbreviation) = self.numeral_or_abbreviation(&normalized);
+                            let text = abbreviation.as_deref().unwrap_or(&normalized);
+                            let word = self.handle_vowels(text);
+                            let found: Option<i64> = find_word
+                                .query_row(params![word], |row| row.get(0))
+                                .optional()
+                                .map_err(sqlite_error)?;
+                            let word_id = match found {
+                                Some(word_id) => word_id,
+                                None => {
+                                    insert_word.execute(params![word]).map_err(sqlite_error)?;
+                                    tx.last_insert_rowid()
+                                }
+                            };
+                            insert_spelling
+                                .execute(params![normalized, word_id])
+                                .map_err(sqlite_error)?;
+                            let id = tx.last_insert_rowid();
+                            let letters = self.get_word_letters(text);
+                            for (method, built) in &strategies {
+                                let strategy = built
+                                    .as_deref()
+                                    .unwrap_or(self.calculation_strategy.as_ref());
+                                let value = self.calculate_letters_value(strategy, &letters, text);
+                                insert_value
+                                    .execute(params![method_key(*method), id, value.to_be_bytes()])
+                                    .map_err(sqlite_error)?;
+                            }
+                            id
+                        }
+                    };
+                    insert_token
+                        .execute(params![
+                            position,
+                            id,
+                            offset + range.start,
+                            offset + range.end
+                        ])
+                        .map_err(sqlite_error)?;
+                    position += 1;
+                }
+                offset += read;
+                lines += 1;
+                progress.on_progress(lines, 0);
+            }
+        }
+
+        tx.commit().map_err(sqlite_error)
+    }
+}
+
+// Util function to get the key of a method within the database.
+fn method_key(method: GematriaMethod) -> &'static str {
+    method.name()
+}
+
+// Util function to convert a SQLite error.
+fn sqlite_error(err: rusqlite::Error) -> io::Error {
+    io::Error::other(err)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sqlite_index_matches_memory_index() {
+        let text = "נכנס יין יצא סוד\nיין שָׁלוֹם־עולם שלום\n";
+        let methods = [GematriaMethod::MisparHechrechi, GematriaMethod::MisparGadol];
+        let gmctx = GematriaContext::default();
+        let memory = gmctx.build_index(text, &methods).unwrap();
+        let mut index = SqliteIndex::create_in_memory().unwrap();
+        gmctx
+            .index_into_sqlite(text.as_bytes(), &methods, &mut index)
+            .unwrap();
+
+        assert_eq!(index.token_count().unwrap(), memory.token_count());
+        assert_eq!(index.positions("יין").unwrap(), memory.positions("יין"));
+        assert_eq!(
+            index.value_of(GematriaMethod::MisparGadol, "יין").unwrap(),
+            Some(720)
+        );
+        assert_eq!(
+            index.value_of(GematriaMethod::MisparKatan, "יין").unwrap(),
+            None
+        );
+        for method in methods {
+            assert_eq!(
+                index.search_matching_values(method, 376).unwrap(),
+                memory.search_matching_values(method, 376)
+            );
+            assert_eq!(
+                index.group_words(method).unwrap(),
+                memory.group_words(method)
+            );
+        }
+    }
+
+    #[test]
+    fn test_sqlite_index_cancelled_midway() {
+        let gmctx = GematriaContext::default();
+        let methods = [GematriaMethod::MisparHechrechi];
+        let mut index = SqliteIndex::create_in_memory().unwrap();
+        gmctx
+            .index_into_sqlite("שלום".as_bytes(), &methods, &mut index)
+            .unwrap();
+
+        let text = "נכנס יין\nיצא סוד\n".repeat(10);
+        let cancel = CancellationToken::new();
+        let mut reports = Vec::new();
+        let mut sink = |lines, total| {
+            reports.push((lines, total));
+            if lines == 3 {
+                cancel.cancel();
+            }
+        };
+        let error = gmctx
+            .index_into_sqlite_with_progress(
+                text.as_bytes(),
+                &methods,
+                &mut index,
+                &mut sink,
+                &cancel,
+            )
+            .unwrap_err();
+
+        assert_eq!(error.kind(), io::ErrorKind::Interrupted);
+        assert_eq!(reports, [(1, 0), (2, 0), (3, 0)]);
+        // The previous content is kept
+        assert_eq!(index.token_count().unwrap(), 1);
+        assert_eq!(index.positions("שלום").unwrap(), [0]);
+    }
+
+    #[test]
+    fn test_sqlite_index_saturated_values() {
+        struct Saturating;
+
+        impl crate::GematriaCalculation for Saturating {
+            fn calculate_value(&self, letter_index: u32) -> u32 {
+                crate::std_gematria_value(&letter_index)
+            }
+
+            fn method_type(&self) -> GematriaMethod {
+                GematriaMethod::Custom
+            }
+
+            fn finalize_total(&self, total: u64) -> u64 {
+                if total > 300 {
+                    u64::MAX
+                } else {
+                    total
+                }
+            }
+        }
+
+        let text = "שלום אבא ישראל שלום אבא";
+        let methods = [GematriaMethod::Custom];
+        let gmctx = crate::GematriaBuilder::new()
+            .with_strategy(Box::new(Saturating))
+            .init_gematria();
+        let memory = gmctx.build_index(text, &methods).unwrap();
+        let mut index = SqliteIndex::create_in_memory().unwrap();
+        gmctx
+            .index_into_sqlite(text.as_bytes(), &methods, &mut index)
+            .unwrap();
+
+        assert_eq!(
+            index.value_of(GematriaMethod::Custom, "שלום").unwrap(),
+            Some(u64::MAX)
+        );
+        assert_eq!(
+            index
+                .words_with_value(GematriaMethod::Custom, u64::MAX)
+                .unwrap(),
+            ["שלום", "ישראל"]
+        );
+        assert_eq!(
+            index
+                .search_matching_values(GematriaMethod::Custom, u64::MAX)
+                .unwrap(),
+            memory.search_matching_values(GematriaMethod::Custom, u64::MAX)
+        );
+        assert_eq!(
+            index.group_words(GematriaMethod::Custom).unwrap(),
+            memory.group_words(GematriaMethod::Custom)
+        );
+    }
+
+    #[test]
+    fn test_sqlite_index_values_spellings_apart() {
+        let text = "שָׁלוֹם שלום שְׁלוֹם צה״ל
+שָׁלוֹם צהל";
+        let methods = [GematriaMethod::Nikud];
+        let gmctx = crate::GematriaBuilder::new()
+            .with_method(GematriaMethod::Nikud)
+            .with_abbreviations(true)
+            .init_gematria();
+        let memory = gmctx.build_index(text, &methods).unwrap();
+        let mut index = SqliteIndex::create_in_memory().unwrap();
+        gmctx
+            .index_into_sqlite(text.as_bytes(), &methods, &mut index)
+            .unwrap();
+
+        assert_eq!(index.positions("שלום").unwrap(), memory.positions("שלום"));
+        assert_eq!(
+            index.value_of(GematriaMethod::Nikud, "שלום").unwrap(),
+            memory.value_of(GematriaMethod::Nikud, "שלום")
+        );
+        for word in text.split_whitespace() {
+            let value = gmctx.calculate_value(word).value();
+            assert_eq!(
+                index
+                    .search_matching_values(GematriaMethod::Nikud, value)
+                    .unwrap(),
+                memory.search_matching_values(GematriaMethod::Nikud, value)
+            );
+            assert_eq!(
+                index
+                    .words_with_value(GematriaMethod::Nikud, value)
+                    .unwrap(),
+                memory.words_with_value(GematriaMethod::Nikud, value)
+            );
+        }
+        assert_eq!(
+            index.group_words(GematriaMethod::Nikud).unwrap(),
+            memory.group_words(GematriaMethod::Nikud)
+        );
+    }
+}
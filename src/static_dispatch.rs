@@ -0,0 +1,148 @@
+// Statically dispatched calculation: a calculator generic over its strategy, for hot loops.
+
+use crate::{tokenize, GematriaCalculation, GematriaValue};
+
+/// The indices of the Hebrew letters block (U+05D0 to U+05EA) in the standard alphabet,
+/// where the final forms follow their regular letters by code point.
+const STANDARD_BLOCK_INDICES: [u32; 27] = [
+    1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 23, 11, 12, 24, 13, 25, 14, 15, 16, 26, 17, 27, 18, 19, 20, 21,
+    22,
+];
+
+// Util function to get the index of a Hebrew letter in the standard alphabet.
+pub(crate) const fn standard_index(c: char) -> Option<u32> {
+    match c {
+        '\u{05D0}'..='\u{05EA}' => Some(STANDARD_BLOCK_INDICES[c as usize - 0x05D0]),
+        _ => None,
+    }
+}
+
+/// `StaticGematria` calculates values with a strategy known at compile time, so letters are valued
+/// without dynamic dispatch, and can be `const` constructed.
+///
+/// It values the Hebrew letters of the standard alphabet as written, with the methods whose strategy
+/// needs no runtime data (such as [`crate::MisparHechrechi`] or [`crate::MisparGadol`]).
+/// Texts are not normalized, and there is no cache, character map, modifier or kolel:
+/// use a [`crate::GematriaContext`] for these, or to select the method at runtime.
+///
+/// Words are always split on whitespace and on the maqaf, paseq and sof pasuk, as by the default
+/// [`crate::Tokenizer`]. A context configured with another tokenizer (such as extra delimiters,
+/// or a maqaf kept within words) may split a text differently, and so value the positional methods
+/// differently. Totals saturate at `u64::MAX`.
+///
+/// # Examples
+///
+/// ```
+/// use gematria_rs::{GematriaContext, MisparGadol, MisparHechrechi, StaticGematria};
+///
+/// const STANDARD: StaticGematria<MisparHechrechi> = StaticGematria::new(MisparHechrechi);
+///
+/// assert_eq!(STANDARD.calculate_value("שלום"), 376);
+/// assert_eq!(StaticGematria::new(MisparGadol).calculate_value("שלום"), 936);
+/// assert_eq!(
+///     STANDARD.calculate_value("בראשית ברא"),
+///     GematriaContext::default().calculate_value("בראשית ברא").value()
+/// );
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct StaticGematria<S: GematriaCalculation> {
+    // The strategy valuing the letters.
+    strategy: S,
+}
+
+impl<S: GematriaCalculation> StaticGematria<S> {
+    /// Creates a calculator valuing the letters with the given strategy.
+    pub const fn new(strategy: S) -> Self {
+        StaticGematria { strategy }
+    }
+
+    /// Gets the strategy valuing the letters.
+    pub fn strategy(&self) -> &S {
+        &self.strategy
+    }
+
+    /// Calculates the gematria value of a single Hebrew character.
+    pub fn calculate_char_value(&self, character: char) -> GematriaValue {
+        match standard_index(character) {
+            Some(index) => self.strategy.calculate_value(index) as u64,
+            None => self.strategy.calculate_symbol_value(character).unwrap_or(0) as u64,
+        }
+    }
+
+    /// Calculates the gematria value of a Hebrew word or phrase, valuing letters by their position within their word.
+    pub fn calculate_value(&self, text: &str) -> GematriaValue {
+        let total = tokenize(text).fold(0u64, |total, word| {
+            let mut position = 0;
+            word.chars().fold(total, |total, c| {
+                let value = match standard_index(c) {
+                    Some(index) => {
+                        position += 1;
+                        self.strategy.calculate_positional_value(index, position)
+                    }
+                    None => self.strategy.calculate_symbol_value(c).unwrap_or(0) as u64,
+                };
+                total.saturating_add(value)
+            })
+        });
+        self.strategy.finalize_total(total)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        GematriaBuilder, GematriaMethod, HebrewCharacterMap, MisparGadol, MisparKidmi, Nikud,
+    };
+
+    // A strategy valuing every letter at half of `u64::MAX`.
+    struct Huge;
+
+    impl GematriaCalculation for Huge {
+        fn calculate_value(&self, _letter_index: u32) -> u32 {
+            0
+        }
+
+        fn method_type(&self) -> GematriaMethod {
+            GematriaMethod::MisparHechrechi
+        }
+
+        fn calculate_positional_value(&self, _letter_index: u32, _position: usize) -> u64 {
+            u64::MAX / 2
+        }
+    }
+
+    #[test]
+    fn test_static_values_match_the_context() {
+        let map = HebrewCharacterMap::default();
+        for c in '\u{05D0}'..='\u{05EA}' {
+            assert_eq!(standard_index(c), map.index_of(c), "{}", c);
+        }
+
+        let text = "בְּרֵאשִׁית בָּרָא אֱלֹהִים";
+        let check = |method: GematriaMethod, value: GematriaValue| {
+            let gmctx = GematriaBuilder::new().with_method(method).init_gematria();
+            assert_eq!(gmctx.calculate_value(text).value(), value, "{}", method);
+        };
+        check(
+            GematriaMethod::MisparGadol,
+            StaticGematria::new(MisparGadol).calculate_value(text),
+        );
+        check(
+            GematriaMethod::MisparKidmi,
+            StaticGematria::new(MisparKidmi).calculate_value(text),
+        );
+        check(
+            GematriaMethod::Nikud,
+            StaticGematria::new(Nikud).calculate_value(text),
+        );
+    }
+
+    #[test]
+    fn test_static_values_saturate() {
+        let gematria = StaticGematria::new(Huge);
+        assert_eq!(gematria.calculate_value("אב"), u64::MAX - 1);
+        assert_eq!(gematria.calculate_value("אב ג"), u64::MAX);
+        assert_eq!(gematria.calculate_value("אבג"), u64::MAX);
+    }
+}
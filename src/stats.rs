@@ -0,0 +1,221 @@
+// Statistics over the distribution of gematria values in a text.
+
+use std::collections::{BTreeMap, HashSet};
+
+use crate::{GematriaContext, GematriaValue};
+
+/// `ValueStats` describes the distribution of the gematria values of the words of a text.
+///
+/// # Examples
+///
+/// ```
+/// use gematria_rs::GematriaContext;
+///
+/// let gmctx = GematriaContext::default();
+/// let stats = gmctx.value_stats("נכנס יין יצא סוד יין");
+///
+/// assert_eq!(stats.words(), 5);
+/// assert_eq!(stats.count(70), 3);
+/// assert_eq!(stats.distinct_values(), 3);
+/// assert_eq!(stats.mean(), Some(98.2));
+/// assert_eq!(stats.median(), Some(70.0));
+/// assert_eq!(stats.largest_class(), Some((70, &["יין".to_string(), "סוד".to_string()][..])));
+/// ```
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct ValueStats {
+    // Value to the number of words having it (including repetitions).
+    histogram: BTreeMap<GematriaValue, usize>,
+
+    // Value to the distinct words having it, in order of appearance.
+    classes: BTreeMap<GematriaValue, Vec<String>>,
+
+    // The number of words.
+    words: usize,
+}
+
+impl ValueStats {
+    /// Gets the histogram of the values, mapping every value to the number of words having it.
+    pub fn histogram(&self) -> &BTreeMap<GematriaValue, usize> {
+        &self.histogram
+    }
+
+    /// Gets the number of words having the given value.
+    pub fn count(&self, value: GematriaValue) -> usize {
+        self.histogram.get(&value).copied().unwrap_or_default()
+    }
+
+    /// Gets the number of words of the text.
+    pub fn words(&self) -> usize {
+        self.words
+    }
+
+    /// Gets the number of distinct values.
+    pub fn distinct_values(&self) -> usize {
+        self.histogram.len()
+    }
+
+    /// Gets the mean value of the words, `None` for a text without words.
+    pub fn mean(&self) -> Option<f64> {
+        if self.words == 0 {
+            return None;
+        }
+
+        let total: f64 = self
+            .histogram
+            .iter()
+            .map(|(&value, &count)| value as f64 * count as f64)
+            .sum();
+        Some(total / self.words as f64)
+    }
+
+    /// Gets the median value of the words (the mean of the two middle values for an even number of words),
+    /// `None` for a text without words.
+    pub fn median(&self) -> Option<f64> {
+        if self.words == 0 {
+            return None;
+        }
+
+        let lower = self.nth_value((self.words - 1) / 2)?;
+        let upper = self.nth_value(self.words / 2)?;
+        Some((lower as f64 + upper as f64) / 2.0)
+    }
+
+    /// Gets the largest equivalence class: the value shared by the most distinct words, and these words.
+    ///
+    /// Ties are broken by the lowest value, `None` for a text without words.
+    pub fn largest_class(&self) -> Option<(GematriaValue, &[String])> {
+        self.classes
+            .iter()
+            .rev()
+            .max_by_key(|(_, words)| words.len())
+            .map(|(&value, words)| (value, words.as_slice()))
+    }
+
+    /// Gets the `k` values shared by the most distinct words, with these words.
+    ///
+    /// Values are sorted by their number of distinct words (descending) and then by value.
+    pub fn top_classes(&self, k: usize) -> Vec<(GematriaValue, &[String])> {
+        let mut classes: Vec<(GematriaValue, &[String])> = self
+            .classes
+            .iter()
+            .map(|(&value, words)| (value, words.as_slice()))
+            .collect();
+        // The sort is stable and classes are ordered by value, so ties remain in order of value
+        classes.sort_by_key(|(_, words)| std::cmp::Reverse(words.len()));
+        classes.truncate(k);
+        classes
+    }
+
+    /// Gets the distinct words having the given value, in order of appearance.
+    pub fn class(&self, value: GematriaValue) -> &[String] {
+        self.classes
+            .get(&value)
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
+    // Gets the value of the n-th word (0 based) when sorted by value.
+    fn nth_value(&self, n: usize) -> Option<GematriaValue> {
+        let mut seen = 0;
+        for (&value, &count) in &self.histogram {
+            seen += count;
+            if n < seen {
+                return Some(value);
+            }
+        }
+        None
+    }
+}
+
+impl GematriaContext {
+    /// Gets the `k` values with the most distinct words in a text, with their words (in order of appearance).
+    ///
+    /// Values are sorted by their number of distinct words (descending) and then by value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gematria_rs::GematriaContext;
+    ///
+    /// let gmctx = GematriaContext::default();
+    /// let top = gmctx.top_values("נכנס יין יצא סוד יין", 2);
+    ///
+    /// assert_eq!(
+    ///     top,
+    ///     vec![
+    ///         (70, vec!["יין".to_string(), "סוד".to_string()]),
+    ///         (101, vec!["יצא".to_string()]),
+    ///     ]
+    /// );
+    /// ```
+    pub fn top_values(&self, text: &str, k: usize) -> Vec<(GematriaValue, Vec<String>)> {
+        self.value_stats(text)
+            .top_classes(k)
+            .into_iter()
+            .map(|(value, words)| (value, words.to_vec()))
+            .collect()
+    }
+
+    /// Calculates statistics over the distribution of the gematria values of the words of a text.
+    pub fn value_stats(&self, text: &str) -> ValueStats {
+        let mut stats = ValueStats::default();
+        // The words already in a class, keyed by value
+        let mut seen = HashSet::new();
+        for word in self.tokenizer.tokenize(text) {
+            let result = self.calculate_value(word);
+            *stats.histogram.entry(result.value()).or_default() += 1;
+            let class = stats.classes.entry(result.value()).or_default();
+            if seen.insert((result.value(), result.word().to_string())) {
+                class.push(result.word().to_string());
+            }
+            stats.words += 1;
+        }
+
+        stats
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_empty_stats() {
+        let stats = GematriaContext::default().value_stats(" \n");
+
+        assert_eq!(stats.words(), 0);
+        assert_eq!(stats.mean(), None);
+        assert_eq!(stats.median(), None);
+        assert_eq!(stats.largest_class(), None);
+    }
+
+    #[test]
+    fn test_median_and_ties() {
+        let stats = GematriaContext::default().value_stats("ב א ד ג ט");
+        assert_eq!(stats.median(), Some(3.0));
+
+        let stats = GematriaContext::default().value_stats("ב א ד ג");
+        assert_eq!(stats.median(), Some(2.5));
+        assert_eq!(stats.mean(), Some(2.5));
+        // Every class holds a single word, the lowest value wins
+        assert_eq!(stats.largest_class(), Some((1, &["א".to_string()][..])));
+        assert_eq!(stats.class(4), ["ד"]);
+    }
+
+    #[test]
+    fn test_top_values() {
+        let gmctx = GematriaContext::default();
+        let top = gmctx.top_values("ג אב ב ג י א", 3);
+
+        assert_eq!(
+            top,
+            vec![
+                (3, vec!["ג".to_string(), "אב".to_string()]),
+                (1, vec!["א".to_string()]),
+                (2, vec!["ב".to_string()]),
+            ]
+        );
+        assert_eq!(gmctx.top_values("ג אב", 10).len(), 1);
+        assert!(gmctx.top_values("ג אב", 0).is_empty());
+    }
+}
@@ -0,0 +1,82 @@
+// Streaming calculations over buffered readers, with memory bounded by the longest line.
+
+use std::io::{self, BufRead};
+
+use crate::{GematriaContext, GematriaResult};
+
+impl GematriaContext {
+    /// Calculates the gematria value of every word read from a reader, line by line.
+    ///
+    /// Only a single line is held in memory at a time, so huge files can be processed lazily.
+    /// Read errors are yielded as they occur, and reading stops at the end of the reader.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gematria_rs::GematriaContext;
+    ///
+    /// let gmctx = GematriaContext::default();
+    /// let values = gmctx
+    ///     .process_reader("נכנס יין\nיצא סוד\n".as_bytes())
+    ///     .map(|result| result.map(|r| r.value()))
+    ///     .collect::<std::io::Result<Vec<u64>>>()?;
+    ///
+    /// assert_eq!(values, vec![180, 70, 101, 70]);
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    pub fn process_reader<'a, R: BufRead + 'a>(
+        &'a self,
+        reader: R,
+    ) -> impl Iterator<Item = io::Result<GematriaResult>> + 'a {
+        reader.lines().flat_map(move |line| match line {
+            Ok(line) => self
+                .tokenizer
+                .tokenize(&line)
+                .map(|word| Ok(self.calculate_value(word)))
+                .collect::<Vec<_>>(),
+            Err(e) => vec![Err(e)],
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_process_reader_numerals() {
+        let gmctx = crate::GematriaBuilder::new()
+            .with_numerals(true)
+            .init_gematria();
+        let results: Vec<(u64, Option<u32>)> = gmctx
+            .process_reader("בשנת תשפ״ד ביום י\"ד\n".as_bytes())
+            .map(|r| r.map(|r| (r.value(), r.numeral())))
+            .collect::<io::Result<_>>()
+            .unwrap();
+
+        assert_eq!(
+            results,
+            vec![(752, None), (784, Some(784)), (58, None), (14, Some(14))]
+        );
+    }
+
+    #[test]
+    fn test_process_reader_errors() {
+        let gmctx = GematriaContext::default();
+        // Invalid UTF-8 fails the second line only
+        let input: &[u8] = b"\xd7\x90\n\xff\n\xd7\x91 \xd7\x92";
+        let results: Vec<io::Result<u64>> = gmctx
+            .process_reader(input)
+            .map(|r| r.map(|r| r.value()))
+            .collect();
+
+        assert_eq!(results.len(), 4);
+        assert_eq!(results[0].as_ref().unwrap(), &1);
+        assert_eq!(
+            results[1].as_ref().unwrap_err().kind(),
+            io::ErrorKind::InvalidData
+        );
+        assert_eq!(results[2].as_ref().unwrap(), &2);
+        assert_eq!(results[3].as_ref().unwrap(), &3);
+    }
+}
@@ -0,0 +1,402 @@
+// Tanakh corpus integration, loading scripture into books, chapters and verses.
+
+use std::{
+    fmt,
+    fs::File,
+    io::{self, BufRead, BufReader},
+    path::Path,
+};
+
+use crate::{AggregateIndex, AggregatePolicy, CorpusCounts, GematriaContext, Match, ValueStats};
+
+/// `VerseRef` is the reference of a verse: its book, chapter and verse numbers.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct VerseRef {
+    // The book name.
+    book: String,
+
+    // The chapter number (1 based).
+    chapter: u32,
+
+    // The verse number (1 based).
+    verse: u32,
+}
+
+impl VerseRef {
+    /// Creates a verse reference.
+    pub fn new(book: &str, chapter: u32, verse: u32) -> Self {
+        VerseRef {
+            book: book.to_string(),
+            chapter,
+            verse,
+        }
+    }
+
+    /// Gets the book name.
+    pub fn book(&self) -> &str {
+        &self.book
+    }
+
+    /// Gets the chapter number.
+    pub fn chapter(&self) -> u32 {
+        self.chapter
+    }
+
+    /// Gets the verse number.
+    pub fn verse(&self) -> u32 {
+        self.verse
+    }
+}
+
+impl fmt::Display for VerseRef {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}:{}", self.book, self.chapter, self.verse)
+    }
+}
+
+/// `Verse` is a single verse of scripture and its reference.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Verse {
+    // The reference of the verse.
+    reference: VerseRef,
+
+    // The text of the verse.
+    text: String,
+}
+
+impl Verse {
+    /// Gets the reference of the verse.
+    pub fn reference(&self) -> &VerseRef {
+        &self.reference
+    }
+
+    /// Gets the text of the verse.
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+}
+
+/// `Tanakh` holds a scripture text structured into books, chapters and verses,
+/// so gematria queries report the references of their results.
+///
+/// Texts are loaded from tab separated lines of `book`, `chapter`, `verse` and `text`,
+/// blank lines and lines starting with `#` are skipped.
+///
+/// # Examples
+///
+/// ```
+/// use gematria_rs::{GematriaContext, Tanakh};
+///
+/// let tanakh = Tanakh::parse(
+///     "Genesis\t1\t1\tבְּרֵאשִׁית בָּרָא אֱלֹהִים אֵת הַשָּׁמַיִם וְאֵת הָאָרֶץ\n\
+///      Genesis\t1\t2\tוְהָאָרֶץ הָיְתָה תֹהוּ וָבֹהוּ\n",
+/// )?;
+///
+/// let gmctx = GematriaContext::default();
+/// let hits = tanakh.search_matching_values(&gmctx, &296);
+/// let references: Vec<String> = hits.iter().map(|(r, _)| r.to_string()).collect();
+/// assert_eq!(references, ["Genesis 1:1"]);
+/// # Ok::<(), std::io::Error>(())
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Tanakh {
+    // The books, in the order they were first loaded.
+    books: Vec<String>,
+
+    // The verses, in the order they were loaded.
+    verses: Vec<Verse>,
+}
+
+impl Tanakh {
+    /// Parses a structured scripture text.
+    ///
+    /// Fails with [`io::ErrorKind::InvalidData`] on a malformed line.
+    pub fn parse(content: &str) -> io::Result<Self> {
+        Self::from_reader(content.as_bytes())
+    }
+
+    /// Reads a structured scripture text from the given reader.
+    pub fn from_reader<R: BufRead>(reader: R) -> io::Result<Self> {
+        let mut tanakh = Tanakh::default();
+        for (number, line) in reader.lines().enumerate() {
+            let line = line?;
+            let line = line.trim_end_matches('\r');
+            if line.trim().is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let verse = parse_verse(line).ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!(
+                        "line {}: expected 'book<TAB>chapter<TAB>verse<TAB>text'",
+                        number + 1
+                    ),
+                )
+            })?;
+            if !tanakh.books.contains(&verse.reference.book) {
+                tanakh.books.push(verse.reference.book.clone());
+            }
+            tanakh.verses.push(verse);
+        }
+
+        Ok(tanakh)
+    }
+
+    /// Loads a structured scripture text from a file.
+    pub fn load<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        Self::from_reader(BufReader::new(File::open(path)?))
+    }
+
+    /// Gets the books, in the order they were first loaded.
+    pub fn books(&self) -> &[String] {
+        &self.books
+    }
+
+    /// Gets all the verses.
+    pub fn verses(&self) -> &[Verse] {
+        &self.verses
+    }
+
+    /// Gets the verses of a book.
+    pub fn book<'a>(&'a self, book: &'a str) -> impl Iterator<Item = &'a Verse> + 'a {
+        self.verses.iter().filter(move |v| v.reference.book == book)
+    }
+
+    /// Gets the verses of a chapter of a book.
+    pub fn chapter<'a>(
+        &'a self,
+        book: &'a str,
+        chapter: u32,
+    ) -> impl Iterator<Item = &'a Verse> + 'a {
+        self.book(book)
+            .filter(move |v| v.reference.chapter == chapter)
+    }
+
+    /// Gets a single verse.
+    pub fn verse(&self, book: &str, chapter: u32, verse: u32) -> Option<&Verse> {
+        self.verses.iter().find(|v| {
+            v.reference.book == book && v.reference.chapter == chapter && v.reference.verse == verse
+        })
+    }
+
+    /// Counts the letters and words of every book and chapter.
+    pub fn counts(&self) -> CorpusCounts {
+        CorpusCounts::from_verses(
+            self.verses
+                .iter()
+                .map(|v| (&v.reference.book, v.reference.chapter, &v.text)),
+        )
+    }
+
+    /// Searches every verse for words with the given value, with the reference of every match.
+    pub fn search_matching_values(
+        &self,
+        gmctx: &GematriaContext,
+        value: &u64,
+    ) -> Vec<(VerseRef, Match)> {
+        self.verses
+            .iter()
+            .flat_map(|v| {
+                gmctx
+                    .search_matching_values(value, &v.text)
+                    .into_iter()
+                    .map(move |m| (v.reference.clone(), m))
+            })
+            .collect()
+    }
+
+    /// Searches for the verses whose total equals the target value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gematria_rs::{GematriaContext, Tanakh};
+    ///
+    /// let tanakh = Tanakh::parse("Genesis\t1\t1\tבראשית ברא אלהים\nGenesis\t1\t3\tויאמר אלהים יהי אור\n")?;
+    ///
+    /// let gmctx = GematriaContext::default();
+    /// let verses = tanakh.search_verses_by_value(&gmctx, &1202);
+    /// assert_eq!(verses[0].reference().to_string(), "Genesis 1:1");
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    pub fn search_verses_by_value(&self, gmctx: &GematriaContext, target: &u64) -> Vec<&Verse> {
+        self.verses
+            .iter()
+            .filter(|v| gmctx.calculate_value(&v.text).value() == *target)
+            .collect()
+    }
+
+    /// Gets the chapter numbers of a book, in order.
+    pub fn chapters(&self, book: &str) -> Vec<u32> {
+        let mut chapters: Vec<u32> = self.book(book).map(|v| v.reference.chapter).collect();
+        chapters.sort_unstable();
+        chapters.dedup();
+        chapters
+    }
+
+    /// Calculates the total of a chapter of a book, `None` if the chapter is missing.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gematria_rs::{GematriaContext, Tanakh};
+    ///
+    /// let tanakh = Tanakh::parse(
+    ///     "Genesis\t1\t1\tבראשית ברא אלהים\nGenesis\t1\t2\tוהארץ\nGenesis\t2\t1\tויכלו\n",
+    /// )?;
+    ///
+    /// let gmctx = GematriaContext::default();
+    /// assert_eq!(tanakh.chapter_total(&gmctx, "Genesis", 1), Some(1504));
+    /// assert_eq!(tanakh.book_total(&gmctx, "Genesis"), Some(1576));
+    /// assert_eq!(tanakh.book_total(&gmctx, "Exodus"), None);
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    pub fn chapter_total(&self, gmctx: &GematriaContext, book: &str, chapter: u32) -> Option<u64> {
+        total(gmctx, self.chapter(book, chapter))
+    }
+
+    /// Calculates the total of a book, `None` if the book is missing.
+    pub fn book_total(&self, gmctx: &GematriaContext, book: &str) -> Option<u64> {
+        total(gmctx, self.book(book))
+    }
+
+    /// Calculates statistics over the values of the words of a chapter of a book.
+    pub fn chapter_stats(&self, gmctx: &GematriaContext, book: &str, chapter: u32) -> ValueStats {
+        gmctx.value_stats(&join_verses(self.chapter(book, chapter)))
+    }
+
+    /// Calculates statistics over the values of the words of a book.
+    pub fn book_stats(&self, gmctx: &GematriaContext, book: &str) -> ValueStats {
+        gmctx.value_stats(&join_verses(self.book(book)))
+    }
+
+    /// Calculates and indexes the totals of every chapter, labeled as `"{book} {chapter}"`.
+    pub fn chapter_totals(
+        &self,
+        gmctx: &GematriaContext,
+        policy: AggregatePolicy,
+    ) -> AggregateIndex {
+        let chapters = self.books.iter().flat_map(|book| {
+            self.chapters(book).into_iter().map(move |chapter| {
+                (
+                    format!("{} {}", book, chapter),
+                    join_verses(self.chapter(book, chapter)),
+                )
+            })
+        });
+        gmctx.aggregate_documents(chapters, policy)
+    }
+
+    /// Calculates and indexes the totals of every book, labeled by the book name.
+    pub fn book_totals(&self, gmctx: &GematriaContext, policy: AggregatePolicy) -> AggregateIndex {
+        let books = self
+            .books
+            .iter()
+            .map(|book| (book.clone(), join_verses(self.book(book))));
+        gmctx.aggregate_documents(books, policy)
+    }
+}
+
+// Util function to sum the totals of verses, `None` if there is none.
+fn total<'a, I: Iterator<Item = &'a Verse>>(gmctx: &GematriaContext, verses: I) -> Option<u64> {
+    verses
+        .map(|v| gmctx.calculate_value(&v.text).value())
+        .reduce(|a, b| a + b)
+}
+
+// Util function to join the texts of verses into a single text.
+pub(crate) fn join_verses<'a, I: Iterator<Item = &'a Verse>>(verses: I) -> String {
+    verses
+        .map(|v| v.text.as_str())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+// Util function to parse a `book<TAB>chapter<TAB>verse<TAB>text` line.
+fn parse_verse(line: &str) -> Option<Verse> {
+    let mut fields = line.splitn(4, '\t');
+    let book = fields.next()?.trim();
+    let chapter = fields.next()?.trim().parse().ok()?;
+    let verse = fields.next()?.trim().parse().ok()?;
+    let text = fields.next()?.trim();
+    if book.is_empty() {
+        return None;
+    }
+
+    Some(Verse {
+        reference: VerseRef::new(book, chapter, verse),
+        text: text.to_string(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::TextCounts;
+
+    #[test]
+    fn test_parse_structure() {
+        let tanakh = Tanakh::parse(
+            "# book\tchapter\tverse\ttext\n\
+             Genesis\t1\t1\tבראשית ברא\n\
+             Genesis\t2\t1\tויכלו השמים\n\
+             \n\
+             Exodus\t1\t1\tואלה שמות\r\n",
+        )
+        .unwrap();
+
+        assert_eq!(tanakh.books(), ["Genesis", "Exodus"]);
+        assert_eq!(tanakh.verses().len(), 3);
+        assert_eq!(tanakh.book("Genesis").count(), 2);
+        assert_eq!(tanakh.verse("Exodus", 1, 1).unwrap().text(), "ואלה שמות");
+        assert_eq!(tanakh.verse("Exodus", 1, 2), None);
+        assert_eq!(
+            tanakh.counts().chapter("Genesis", 2),
+            Some(TextCounts::new(10, 2))
+        );
+    }
+
+    #[test]
+    fn test_parse_rejects_malformed_line() {
+        let err = Tanakh::parse("Genesis\t1\t1\tבראשית\nGenesis\tone\t2\tברא\n").unwrap_err();
+
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+        assert!(err.to_string().starts_with("line 2:"));
+    }
+
+    #[test]
+    fn test_chapter_and_book_totals() {
+        let tanakh = Tanakh::parse(
+            "Genesis\t1\t1\tיין סוד\n\
+             Genesis\t1\t2\tיין\n\
+             Genesis\t2\t1\tא\n\
+             Exodus\t1\t1\tב\n",
+        )
+        .unwrap();
+        let gmctx = GematriaContext::default();
+
+        assert_eq!(tanakh.chapters("Genesis"), [1, 2]);
+        let chapters = tanakh.chapter_totals(&gmctx, AggregatePolicy::DistinctWords);
+        let totals: Vec<(&str, u64)> = chapters
+            .documents()
+            .iter()
+            .map(|doc| (doc.label(), doc.value()))
+            .collect();
+        assert_eq!(
+            totals,
+            [("Genesis 1", 140), ("Genesis 2", 1), ("Exodus 1", 2)]
+        );
+        assert_eq!(
+            tanakh
+                .book_totals(&gmctx, AggregatePolicy::Sum)
+                .with_value(211)[0]
+                .label(),
+            "Genesis"
+        );
+
+        let stats = tanakh.chapter_stats(&gmctx, "Genesis", 1);
+        assert_eq!(stats.count(70), 3);
+        assert_eq!(stats.words(), 3);
+    }
+}
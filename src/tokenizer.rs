@@ -0,0 +1,268 @@
+// Configurable splitting of texts into words.
+
+use std::ops::Range;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{is_hebrew_vowel, GERESH, GERSHAYIM, MAQAF, PALEO_WORD_SEPARATOR, PASEQ, SOF_PASUK};
+
+/// Enumerates how a [`Tokenizer`] handles punctuation (such as commas, periods or quotes) attached to words.
+///
+/// The geresh (׳) and gershayim (״) are never treated as punctuation, so numerals and abbreviations are kept.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Punctuation {
+    /// Punctuation is kept as part of the words, and does not contribute to their letter values.
+    #[default]
+    Keep,
+    /// Punctuation is trimmed from the edges of the words.
+    Strip,
+    /// Punctuation separates words, apart from quotes between letters (as in the abbreviation צה"ל).
+    Split,
+}
+
+/// `Tokenizer` configures how texts are split into words.
+///
+/// By default words are split on whitespace and on the maqaf, paseq, sof pasuk and Paleo-Hebrew word separator,
+/// punctuation is kept and empty tokens are dropped, which suits biblical texts.
+/// Modern texts usually call for [`Punctuation::Strip`] or [`Punctuation::Split`].
+///
+/// # Examples
+///
+/// ```
+/// use gematria_rs::{Punctuation, Tokenizer};
+///
+/// let tokenizer = Tokenizer::new()
+///     .with_maqaf_split(false)
+///     .with_punctuation(Punctuation::Strip)
+///     .with_delimiters(['/']);
+///
+/// let words: Vec<&str> = tokenizer.tokenize("שלום, עולם! בית־אל/צה\"ל").collect();
+/// assert_eq!(words, ["שלום", "עולם", "בית־אל", "צה\"ל"]);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Tokenizer {
+    // Extra characters separating words.
+    delimiters: Vec<char>,
+
+    // Whether the maqaf separates words.
+    split_maqaf: bool,
+
+    // How punctuation attached to words is handled.
+    punctuation: Punctuation,
+
+    // Whether empty tokens are dropped.
+    drop_empty: bool,
+
+    // Whether texts are Yiddish, valuing the Yiddish ligatures as their digraphs.
+    #[cfg_attr(feature = "serde", serde(default))]
+    yiddish: bool,
+}
+
+impl Default for Tokenizer {
+    fn default() -> Self {
+        Tokenizer {
+            delimiters: Vec::new(),
+            split_maqaf: true,
+            punctuation: Punctuation::Keep,
+            drop_empty: true,
+            yiddish: false,
+        }
+    }
+}
+
+impl Tokenizer {
+    /// Creates a tokenizer with the default configuration.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds extra characters separating words, besides whitespace, paseq, sof pasuk and the Paleo-Hebrew word separator.
+    pub fn with_delimiters<I: IntoIterator<Item = char>>(mut self, delimiters: I) -> Self {
+        self.delimiters.extend(delimiters);
+        self
+    }
+
+    /// Sets whether the maqaf (־) separates words, defaulted to true.
+    ///
+    /// Joined words (such as בית־אל) are valued as a single word when the maqaf is kept.
+    pub fn with_maqaf_split(mut self, split: bool) -> Self {
+        self.split_maqaf = split;
+        self
+    }
+
+    /// Sets how punctuation attached to words is handled, defaulted to [`Punctuation::Keep`].
+    pub fn with_punctuation(mut self, punctuation: Punctuation) -> Self {
+        self.punctuation = punctuation;
+        self
+    }
+
+    /// Sets whether empty tokens (between consecutive delimiters, or left after stripping punctuation)
+    /// are dropped, defaulted to true.
+    pub fn with_drop_empty(mut self, drop: bool) -> Self {
+        self.drop_empty = drop;
+        self
+    }
+
+    /// Sets the Yiddish mode, where the Yiddish ligatures (װ, ױ and ײ) are valued as the digraphs
+    /// they stand for (וו, וי and יי), defaulted to false.
+    ///
+    /// Words written with ligatures or with digraphs are then valued alike, see [`crate::decompose_yiddish_ligatures`].
+    pub fn with_yiddish(mut self, yiddish: bool) -> Self {
+        self.yiddish = yiddish;
+        self
+    }
+
+    /// Gets the extra characters separating words.
+    pub fn delimiters(&self) -> &[char] {
+        &self.delimiters
+    }
+
+    /// Gets whether the maqaf separates words.
+    pub fn split_maqaf(&self) -> bool {
+        self.split_maqaf
+    }
+
+    /// Gets how punctuation attached to words is handled.
+    pub fn punctuation(&self) -> Punctuation {
+        self.punctuation
+    }
+
+    /// Gets whether empty tokens are dropped.
+    pub fn drop_empty(&self) -> bool {
+        self.drop_empty
+    }
+
+    /// Gets whether texts are tokenized in Yiddish mode.
+    pub fn yiddish(&self) -> bool {
+        self.yiddish
+    }
+
+    /// Splits a text into words.
+    pub fn tokenize<'t, 'a>(&'t self, text: &'a str) -> impl Iterator<Item = &'a str> + 't
+    where
+        'a: 't,
+    {
+        self.tokenize_with_ranges(text).map(|(_, word)| word)
+    }
+
+    /// Splits a text into words, along with the byte range of every word within the text.
+    pub fn tokenize_with_ranges<'t, 'a>(
+        &'t self,
+        text: &'a str,
+    ) -> impl Iterator<Item = (Range<usize>, &'a str)> + 't
+    where
+        'a: 't,
+    {
+        let mut position = Some(0);
+        std::iter::from_fn(move || loop {
+            let start = position?;
+            let end = match text[start..]
+                .char_indices()
+                .find(|&(i, c)| self.is_delimiter(text, start + i, c))
+            {
+                Some((i, c)) => {
+                    position = Some(start + i + c.len_utf8());
+                    start + i
+                }
+                None => {
+                    position = None;
+                    text.len()
+                }
+            };
+
+            let range = self.trim_punctuation(text, start..end);
+            if range.is_empty() && self.drop_empty {
+                continue;
+            }
+            return Some((range.clone(), &text[range]));
+        })
+    }
+
+    /// Checks whether the character at the given byte index of a text separates words.
+    pub(crate) fn is_delimiter(&self, text: &str, index: usize, c: char) -> bool {
+        c.is_whitespace()
+            || matches!(c, PASEQ | SOF_PASUK | PALEO_WORD_SEPARATOR)
+            || (c == MAQAF && self.split_maqaf)
+            || self.delimiters.contains(&c)
+            || (self.punctuation == Punctuation::Split
+                && is_punctuation(c)
+                && !is_quote_between_letters(text, index, c))
+    }
+
+    // Util function to trim the punctuation of a word, when punctuation is stripped.
+    fn trim_punctuation(&self, text: &str, range: Range<usize>) -> Range<usize> {
+        if self.punctuation != Punctuation::Strip {
+            return range;
+        }
+        let word = &text[range.clone()];
+        let trimmed = word.trim_start_matches(is_punctuation);
+        let start = range.end - trimmed.len();
+        start..start + trimmed.trim_end_matches(is_punctuation).len()
+    }
+}
+
+// Util function to check whether a character is punctuation, excluding the geresh, gershayim and Hebrew points.
+fn is_punctuation(c: char) -> bool {
+    !(c.is_alphanumeric()
+        || c.is_whitespace()
+        || is_hebrew_vowel(c)
+        || matches!(c, GERESH | GERSHAYIM))
+}
+
+// Util function to check whether a quote is placed between two letters, as in abbreviations.
+fn is_quote_between_letters(text: &str, index: usize, c: char) -> bool {
+    if !matches!(c, '"' | '\'' | '\u{201D}' | '\u{2019}') {
+        return false;
+    }
+    let before = text[..index].chars().rev().find(|&c| !is_hebrew_vowel(c));
+    let after = text[index + c.len_utf8()..].chars().next();
+    before.is_some_and(char::is_alphabetic) && after.is_some_and(char::is_alphabetic)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_matches_tokenize() {
+        let text = "  בְּרֵאשִׁית בָּרָא׃ אֶת־הָאוֹר׀ \"שלום\",  צה״ל\n";
+        assert_eq!(
+            Tokenizer::default().tokenize(text).collect::<Vec<_>>(),
+            crate::tokenize(text).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_punctuation() {
+        let text = "אמר: \"שלום, עולם\" (צה\"ל)...";
+        let words = |punctuation| {
+            Tokenizer::new()
+                .with_punctuation(punctuation)
+                .tokenize(text)
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            words(Punctuation::Keep),
+            ["אמר:", "\"שלום,", "עולם\"", "(צה\"ל)..."]
+        );
+        assert_eq!(words(Punctuation::Strip), ["אמר", "שלום", "עולם", "צה\"ל"]);
+        assert_eq!(words(Punctuation::Split), ["אמר", "שלום", "עולם", "צה\"ל"]);
+    }
+
+    #[test]
+    fn test_empty_tokens() {
+        let tokenizer = Tokenizer::new().with_drop_empty(false);
+        let words: Vec<(Range<usize>, &str)> = tokenizer.tokenize_with_ranges("א  ב").collect();
+        assert_eq!(words, [(0..2, "א"), (3..3, ""), (4..6, "ב")]);
+
+        let tokenizer = tokenizer.with_punctuation(Punctuation::Strip);
+        assert_eq!(
+            tokenizer.tokenize("א - ב").collect::<Vec<_>>(),
+            ["א", "", "ב"]
+        );
+    }
+}
@@ -0,0 +1,271 @@
+// Transliteration of Latin input (such as "shalom") into Hebrew letters before calculation.
+
+use std::borrow::Cow;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// The rules of the default scheme, Latin letters to Hebrew letters.
+///
+/// The vowels a and e are not written, o and u are written as ו and i as י.
+const DEFAULT_RULES: [(&str, &str); 32] = [
+    ("sh", "ש"),
+    ("ch", "ח"),
+    ("kh", "כ"),
+    ("tz", "צ"),
+    ("ts", "צ"),
+    ("ai", "י"),
+    ("ei", "י"),
+    ("a", ""),
+    ("b", "ב"),
+    ("c", "כ"),
+    ("d", "ד"),
+    ("e", ""),
+    ("f", "פ"),
+    ("g", "ג"),
+    ("h", "ה"),
+    ("i", "י"),
+    ("j", "ג"),
+    ("k", "כ"),
+    ("l", "ל"),
+    ("m", "מ"),
+    ("n", "נ"),
+    ("o", "ו"),
+    ("p", "פ"),
+    ("q", "ק"),
+    ("r", "ר"),
+    ("s", "ס"),
+    ("t", "ת"),
+    ("u", "ו"),
+    ("v", "ב"),
+    ("w", "ו"),
+    ("y", "י"),
+    ("z", "ז"),
+];
+
+/// The rules of the default scheme applied at the beginning of words, where vowels are written with an alef.
+const DEFAULT_INITIAL_RULES: [(&str, &str); 5] = [
+    ("a", "א"),
+    ("e", "א"),
+    ("i", "אי"),
+    ("o", "או"),
+    ("u", "או"),
+];
+
+/// `Transliteration` maps Latin transliterations of Hebrew words (such as "shalom") to Hebrew letters,
+/// see [`crate::GematriaBuilder::with_transliteration`].
+///
+/// The scheme is a set of rules from Latin letter sequences to Hebrew letters, the longest matching
+/// sequence is applied first and letters without a rule are kept as is. Rules applied at the beginning
+/// of words take precedence there, and the last letter of a word is written in its final form (ך, ם, ן, ף, ץ).
+/// Latin letters are matched regardless of their case, other characters (including Hebrew) are kept.
+///
+/// Transliterations are ambiguous, so the default scheme is a simple phonetic one (שלום from "shalom",
+/// but ברשית from "bereshit"), and words or sequences may be added as rules to fix them.
+/// The rule of the longest sequence applies, so whole words added as rules override the letter rules.
+///
+/// # Examples
+///
+/// ```
+/// use gematria_rs::Transliteration;
+///
+/// let scheme = Transliteration::new();
+/// assert_eq!(scheme.apply("shalom"), "שלום");
+/// assert_eq!(scheme.apply("Avraham"), "אברהם");
+///
+/// let scheme = scheme.with_rule("bereshit", "בראשית").with_rule("t", "ט");
+/// assert_eq!(scheme.apply("bereshit tov"), "בראשית טוב");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Transliteration {
+    // Latin letter sequences (lower case) and their Hebrew letters.
+    rules: Vec<(String, String)>,
+
+    // Rules applied at the beginning of words, taking precedence over the other rules.
+    initial_rules: Vec<(String, String)>,
+
+    // Whether the last letter of a word is written in its final form.
+    final_forms: bool,
+}
+
+impl Default for Transliteration {
+    fn default() -> Self {
+        let to_rules = |rules: &[(&str, &str)]| {
+            rules
+                .iter()
+                .map(|(latin, hebrew)| (latin.to_string(), hebrew.to_string()))
+                .collect()
+        };
+        Transliteration {
+            rules: to_rules(&DEFAULT_RULES),
+            initial_rules: to_rules(&DEFAULT_INITIAL_RULES),
+            final_forms: true,
+        }
+    }
+}
+
+impl Transliteration {
+    /// Creates the default scheme.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a scheme without any rule, writing final forms.
+    pub fn empty() -> Self {
+        Transliteration {
+            rules: Vec::new(),
+            initial_rules: Vec::new(),
+            final_forms: true,
+        }
+    }
+
+    /// Adds a rule mapping a Latin letter sequence to Hebrew letters (possibly none),
+    /// replacing the rule of the same sequence if any.
+    pub fn with_rule(mut self, latin: &str, hebrew: &str) -> Self {
+        set_rule(&mut self.rules, latin, hebrew);
+        self
+    }
+
+    /// Adds a rule applied at the beginning of words, replacing the rule of the same sequence if any.
+    pub fn with_initial_rule(mut self, latin: &str, hebrew: &str) -> Self {
+        set_rule(&mut self.initial_rules, latin, hebrew);
+        self
+    }
+
+    /// Sets whether the last letter of a word is written in its final form, defaulted to true.
+    pub fn with_final_forms(mut self, final_forms: bool) -> Self {
+        self.final_forms = final_forms;
+        self
+    }
+
+    /// Gets the rules, Latin letter sequences (lower case) and their Hebrew letters.
+    pub fn rules(&self) -> &[(String, String)] {
+        &self.rules
+    }
+
+    /// Gets the rules applied at the beginning of words.
+    pub fn initial_rules(&self) -> &[(String, String)] {
+        &self.initial_rules
+    }
+
+    /// Gets whether the last letter of a word is written in its final form.
+    pub fn final_forms(&self) -> bool {
+        self.final_forms
+    }
+
+    /// Transliterates the Latin words of a text, borrowing the text when it has no Latin letter.
+    pub fn apply<'t>(&self, text: &'t str) -> Cow<'t, str> {
+        if !text.chars().any(|c| c.is_ascii_alphabetic()) {
+            return Cow::Borrowed(text);
+        }
+
+        let mut transliterated = String::with_capacity(text.len() * 2);
+        let mut rest = text;
+        while let Some(start) = rest.find(|c: char| c.is_ascii_alphabetic()) {
+            transliterated.push_str(&rest[..start]);
+            let word = &rest[start..];
+            let end = word
+                .find(|c: char| !c.is_ascii_alphabetic())
+                .unwrap_or(word.len());
+            transliterated.push_str(&self.transliterate_word(&word[..end].to_ascii_lowercase()));
+            rest = &word[end..];
+        }
+        transliterated.push_str(rest);
+
+        Cow::Owned(transliterated)
+    }
+
+    // Util function to transliterate a single lower case Latin word.
+    fn transliterate_word(&self, word: &str) -> String {
+        let mut hebrew = String::with_capacity(word.len() * 2);
+        let mut position = 0;
+        while position < word.len() {
+            let rest = &word[position..];
+            let initial = match position {
+                0 => longest_rule(&self.initial_rules, rest),
+                _ => None,
+            };
+            // Initial rules take precedence over rules of sequences as long
+            let rule = match (initial, longest_rule(&self.rules, rest)) {
+                (Some(initial), Some(rule)) if rule.0.len() > initial.0.len() => Some(rule),
+                (initial, rule) => initial.or(rule),
+            };
+            match rule {
+                Some((latin, letters)) => {
+                    hebrew.push_str(letters);
+                    position += latin.len();
+                }
+                None => {
+                    hebrew.push_str(&rest[..1]);
+                    position += 1;
+                }
+            }
+        }
+
+        if self.final_forms {
+            if let Some(last) = hebrew.pop() {
+                hebrew.push(final_form(last));
+            }
+        }
+        hebrew
+    }
+}
+
+// Util function to add or replace a rule.
+fn set_rule(rules: &mut Vec<(String, String)>, latin: &str, hebrew: &str) {
+    let latin = latin.to_ascii_lowercase();
+    rules.retain(|(sequence, _)| *sequence != latin);
+    rules.push((latin, hebrew.to_string()));
+}
+
+// Util function to find the rule of the longest sequence starting a word.
+fn longest_rule<'r>(rules: &'r [(String, String)], word: &str) -> Option<(&'r str, &'r str)> {
+    rules
+        .iter()
+        .filter(|(latin, _)| !latin.is_empty() && word.starts_with(latin.as_str()))
+        .max_by_key(|(latin, _)| latin.len())
+        .map(|(latin, hebrew)| (latin.as_str(), hebrew.as_str()))
+}
+
+// Util function to get the final form of a letter, the letter itself if it has none.
+fn final_form(letter: char) -> char {
+    match letter {
+        'כ' => 'ך',
+        'מ' => 'ם',
+        'נ' => 'ן',
+        'פ' => 'ף',
+        'צ' => 'ץ',
+        letter => letter,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::GematriaBuilder;
+
+    #[test]
+    fn test_default_scheme() {
+        let scheme = Transliteration::new();
+        assert_eq!(scheme.apply("emet chesed, Torah!"), "אמת חסד, תורה!");
+        assert_eq!(scheme.apply("melekh tzitzit"), "מלך ציצית");
+        assert!(matches!(scheme.apply("שלום"), Cow::Borrowed("שלום")));
+        assert_eq!(scheme.with_final_forms(false).apply("shalom"), "שלומ");
+        // Letters without a rule are kept
+        assert_eq!(Transliteration::empty().apply("shalom"), "shalom");
+    }
+
+    #[test]
+    fn test_transliterated_values() {
+        let gmctx = GematriaBuilder::new()
+            .with_transliteration(Transliteration::new())
+            .init_gematria();
+
+        let result = gmctx.calculate_value("shalom");
+        assert_eq!(result.value(), 376);
+        assert_eq!(result.word(), "שלום");
+        assert_eq!(result.original_word(), "shalom");
+        assert_eq!(gmctx.calculate_value("Shalom שלום").value(), 752);
+    }
+}
@@ -0,0 +1,90 @@
+// Yiddish ligatures (U+05F0 to U+05F2), the precomposed digraphs of Yiddish orthography.
+
+use std::borrow::Cow;
+
+/// The Yiddish ligatures and the digraphs they stand for.
+const YIDDISH_LIGATURES: [(char, &str); 3] = [
+    // Tsvey vovn
+    ('\u{05F0}', "וו"),
+    // Vov yud
+    ('\u{05F1}', "וי"),
+    // Tsvey yudn
+    ('\u{05F2}', "יי"),
+];
+
+// Util function to get the digraph of a Yiddish ligature.
+fn yiddish_ligature(c: char) -> Option<&'static str> {
+    YIDDISH_LIGATURES
+        .iter()
+        .find(|(ligature, _)| *ligature == c)
+        .map(|(_, digraph)| *digraph)
+}
+
+/// Checks whether a character is a Yiddish ligature.
+pub(crate) fn is_yiddish_ligature(c: char) -> bool {
+    yiddish_ligature(c).is_some()
+}
+
+/// Replaces the Yiddish ligatures of a text (װ, ױ and ײ) with the digraphs they stand for (וו, וי and יי),
+/// borrowing the text when it has none.
+///
+/// Contexts apply this mapping when their tokenizer is in Yiddish mode, see [`crate::Tokenizer::with_yiddish`],
+/// so words are valued the same whether their digraphs are written with ligatures or with two letters.
+/// The pointed ligature ײַ (U+FB1F) and pointed letters such as אַ and אָ are valued as their letters in any mode.
+///
+/// # Examples
+///
+/// ```
+/// use gematria_rs::{decompose_yiddish_ligatures, GematriaBuilder, Tokenizer};
+///
+/// assert_eq!(decompose_yiddish_ligatures("\u{05F2}ִדיש"), "ייִדיש");
+///
+/// let gmctx = GematriaBuilder::new()
+///     .with_tokenizer(Tokenizer::new().with_yiddish(true))
+///     .init_gematria();
+/// assert_eq!(
+///     gmctx.calculate_value("\u{05F0}אַסער").value(),
+///     gmctx.calculate_value("וואסער").value()
+/// );
+/// ```
+pub fn decompose_yiddish_ligatures(text: &str) -> Cow<'_, str> {
+    if !text.chars().any(is_yiddish_ligature) {
+        return Cow::Borrowed(text);
+    }
+
+    let mut decomposed = String::with_capacity(text.len() * 2);
+    for c in text.chars() {
+        match yiddish_ligature(c) {
+            Some(digraph) => decomposed.push_str(digraph),
+            None => decomposed.push(c),
+        }
+    }
+    Cow::Owned(decomposed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{GematriaBuilder, GematriaContext, Tokenizer};
+
+    #[test]
+    fn test_yiddish_mode() {
+        let yiddish = GematriaBuilder::new()
+            .with_tokenizer(Tokenizer::new().with_yiddish(true))
+            .init_gematria();
+        // Ligatures, digraphs and the pointed ligature are valued alike
+        for word in ["\u{05F2}ִדיש", "ייִדיש", "\u{FB1F}דיש"] {
+            assert_eq!(yiddish.calculate_value(word).value(), 334, "{}", word);
+        }
+        assert_eq!(yiddish.calculate_value("\u{05F1}").value(), 16);
+        assert!(matches!(
+            decompose_yiddish_ligatures("ייִדיש"),
+            Cow::Borrowed(_)
+        ));
+        assert!(yiddish.calculate_value_strict("\u{05F0}אַסער").is_ok());
+
+        // Ligatures are not letters out of Yiddish mode
+        let gmctx = GematriaContext::default();
+        assert_eq!(gmctx.calculate_value("\u{05F2}ִדיש").value(), 314);
+    }
+}